@@ -1,15 +1,49 @@
 use reqwest::{Client, StatusCode}; // 🔥 Added StatusCode import
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use anyhow::{Result, anyhow, Context};
+use crate::api::session::{AgentSession, SessionOutcome};
+use crate::utils::rules;
+use std::fmt;
 use std::time::Duration;
 
 #[derive(Clone)]
 pub struct ApiClient {
     base_url: String,
     client: Client,
+    model: Option<String>, // 🎛️ User-selected model (None = Server Smart Routing)
+    no_cache: bool,        // ⚡ Server Response Cache ကို ကျော်မယ် (`--no-cache`)
 }
 
+/// Server က ပြန်ပေးတဲ့ Error (HTTP Status / Agent Session `code`)
+/// `ci::Exit::classify` က Error Text ကို မကြည့်ဘဲ ဒါကို ကြည့်ပြီး Exit Code ခွဲမယ်
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: Option<StatusCode>,
+    pub code: Option<String>,
+    message: String,
+}
+
+impl ApiError {
+    /// `"{context} ({status}): {body}"`
+    fn http(context: &str, status: StatusCode, body: String) -> anyhow::Error {
+        anyhow::Error::new(Self { status: Some(status), code: None, message: format!("{} ({}): {}", context, status, body) })
+    }
+
+    /// Session `error` Event (`{"type": "error", "code": ..., "error": ...}`)
+    pub fn session(code: &str, error: &str) -> anyhow::Error {
+        anyhow::Error::new(Self { status: None, code: Some(code.to_string()), message: format!("{} ({})", error, code) })
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
 #[derive(Debug, Deserialize)]
 pub struct DeviceFlowInit {
     pub device_code: String,
@@ -18,6 +52,52 @@ pub struct DeviceFlowInit {
     pub interval: u64,
 }
 
+/// `/api/agent/plan` Request Body
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PlanRequest {
+    pub prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    /// Conversation so far (Server က Token Budget ထဲ History section အဖြစ် သီးသန့် ခွဲဝေမယ်)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// `.neurust/rules.md` + Global Rules (None ဆိုရင် Client က Disk ကနေ ဖြည့်မယ်)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
+}
+
+impl PlanRequest {
+    pub fn new(prompt: &str) -> Self {
+        Self { prompt: prompt.to_string(), ..Default::default() }
+    }
+}
+
+/// `/api/agent/estimate` Response
+#[derive(Debug, Clone, Deserialize)]
+pub struct CostEstimate {
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens_min: u64,
+    pub output_tokens_max: u64,
+    pub cost_usd_min: f64,
+    pub cost_usd_max: f64,
+    pub credits_min: i64,
+    pub credits_max: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelEntry {
+    pub id: String,
+    pub label: String,
+    pub tier: String,
+    pub input_price_per_m: f64,
+    pub output_price_per_m: f64,
+    #[serde(default)]
+    pub available: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PollResponse {
     pub status: String, // "pending", "verified", "expired"
@@ -49,15 +129,47 @@ impl ApiClient {
                 .timeout(Duration::from_secs(600)) // 10 mins timeout
                 .build()
                 .unwrap(),
+            model: None,
+            no_cache: false,
+        }
+    }
+
+    /// Request တိုင်းမှာ `model` field ထည့်ပို့မယ် (`--model <id>`)
+    pub fn with_model(mut self, model: Option<String>) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// `Cache-Control: no-cache` ထည့်ပို့မယ် (Plan/Audit အဖြေ အသစ် လိုချင်ရင်)
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    fn cache_control(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if self.no_cache {
+            builder.header(reqwest::header::CACHE_CONTROL, "no-cache")
+        } else {
+            builder
         }
     }
 
     /// Existing Method: Fetch AI Plan
     pub async fn fetch_plan(&self, prompt: &str, context: Option<String>) -> Result<serde_json::Value> {
+        let request = PlanRequest { context, ..PlanRequest::new(prompt) };
+        self.fetch_plan_with(&request).await
+    }
+
+    /// Fetch AI Plan with full request options (history, model, ...)
+    pub async fn fetch_plan_with(&self, request: &PlanRequest) -> Result<serde_json::Value> {
         let url = format!("{}/api/agent/plan", self.base_url);
-        let payload = json!({ "prompt": prompt, "context": context });
+        let mut payload = serde_json::to_value(request)?;
+        if request.model.is_none() && self.model.is_some() {
+            payload["model"] = json!(self.model);
+        }
+        attach_rules(&mut payload);
 
-        let response = self.client.post(&url)
+        let response = self.cache_control(self.client.post(&url))
             .json(&payload)
             .send()
             .await
@@ -67,18 +179,63 @@ impl ApiClient {
 
         if !status.is_success() {
             let err = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Server Error ({}): {}", status, err));
+            return Err(ApiError::http("Server Error", status, err));
         }
 
         Ok(response.json().await?)
     }
 
+    /// 🔮 Preflight: Plan Request တစ်ခု ဘယ်လောက်ကျမလဲ (LLM မခေါ်၊ Credit မဖြတ်)
+    pub async fn estimate_plan(&self, request: &PlanRequest) -> Result<CostEstimate> {
+        let url = format!("{}/api/agent/estimate", self.base_url);
+        let mut payload = serde_json::to_value(request)?;
+        if request.model.is_none() && self.model.is_some() {
+            payload["model"] = json!(self.model);
+        }
+        attach_rules(&mut payload);
+
+        let response = self.client.post(&url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to Brain: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let err = response.text().await.unwrap_or_default();
+            return Err(ApiError::http("Estimate Error", status, err));
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        serde_json::from_value(body["estimate"].clone()).context("Invalid estimate from server")
+    }
+
+    /// 🛰️ Server-side Agent Loop (WebSocket)။ Tool Call တွေကို Local မှာ ဖြေပြီး Final Plan ကို ပြန်ပေးမယ်
+    /// `Ok(None)` = Server က Session မပံ့ပိုးဘူး (Legacy `/api/agent/plan` Loop ကို သုံးပါ)
+    pub async fn run_session(&self, request: &PlanRequest) -> Result<Option<SessionOutcome>> {
+        let mut payload = serde_json::to_value(request)?;
+        if request.model.is_none() && self.model.is_some() {
+            payload["model"] = json!(self.model);
+        }
+        attach_rules(&mut payload);
+
+        let session = match AgentSession::connect(&self.base_url).await {
+            Ok(session) => session,
+            Err(e) => {
+                println!("⚠️  {}. Falling back to the legacy agent loop.", e);
+                return Ok(None);
+            }
+        };
+        session.run(payload).await.map(Some)
+    }
+
     /// Existing Method: Audit Code
     pub async fn audit_code(&self, code_payload: &str) -> Result<String> {
         let url = format!("{}/api/agent/audit", self.base_url);
-        let payload = json!({ "code": code_payload });
+        let mut payload = json!({ "code": code_payload, "model": self.model });
+        attach_rules(&mut payload);
 
-        let response = self.client.post(&url)
+        let response = self.cache_control(self.client.post(&url))
             .json(&payload)
             .send()
             .await?;
@@ -86,7 +243,8 @@ impl ApiClient {
         let status = response.status();
 
         if !status.is_success() {
-            return Err(anyhow!("Audit Failed: {}", status));
+            let err = response.text().await.unwrap_or_default();
+            return Err(ApiError::http("Audit Failed", status, err));
         }
 
         let body: serde_json::Value = response.json().await?;
@@ -100,12 +258,48 @@ impl ApiClient {
         let res = self.client.post(&url).json(&payload).send().await?;
         
         let status = res.status();
-        if !status.is_success() { return Err(anyhow!("Scraper Error")); }
+        if !status.is_success() { return Err(ApiError::http("Scraper Error", status, String::new())); }
         
         let body: serde_json::Value = res.json().await?;
         body["content"].as_str().map(|s| s.to_string()).ok_or(anyhow!("No content"))
     }
 
+    /// Model Catalog: Server ပေါ်မှာ ရွေးလို့ရတဲ့ Model များ (စျေးနှုန်းနဲ့တကွ)
+    pub async fn list_models(&self) -> Result<Vec<ModelEntry>> {
+        let url = format!("{}/api/models", self.base_url);
+        let response = self.client.get(&url)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to Brain: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let err = response.text().await.unwrap_or_default();
+            return Err(ApiError::http("Model List Error", status, err));
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        let models = serde_json::from_value(body["models"].clone())
+            .context("Invalid model list from server")?;
+        Ok(models)
+    }
+
+    /// Account (Credits / Role) - `/api/user/me`
+    pub async fn get_me(&self) -> Result<serde_json::Value> {
+        let url = format!("{}/api/user/me", self.base_url);
+        let response = self.client.get(&url)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to connect to Brain: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let err = response.text().await.unwrap_or_default();
+            return Err(ApiError::http("Account Error", status, err));
+        }
+        Ok(response.json().await?)
+    }
+
     // 🔥🔥🔥 DEVICE FLOW METHODS 🔥🔥🔥
 
     /// Step 1: Initiate Login Flow
@@ -124,7 +318,7 @@ impl ApiClient {
 
         if !status.is_success() {
             let err = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Auth Init Failed ({}): {}", status, err));
+            return Err(ApiError::http("Auth Init Failed", status, err));
         }
 
         let data: DeviceFlowInit = response.json().await
@@ -164,10 +358,20 @@ impl ApiClient {
                  });
              }
              
-             return Err(anyhow!("Poll Error: {}", status));
+             return Err(ApiError::http("Poll Error", status, String::new()));
         }
 
         let data: PollResponse = response.json().await?;
         Ok(data)
     }
-}
\ No newline at end of file
+}
+
+/// Agent Request တိုင်းမှာ Project Rules ကို `rules` field အဖြစ် ထည့်ပို့မယ်
+fn attach_rules(payload: &mut serde_json::Value) {
+    if payload.get("rules").is_some_and(|r| !r.is_null()) {
+        return;
+    }
+    if let Some(rules) = rules::load_merged() {
+        payload["rules"] = json!(rules);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::api::client::ApiError;
+use crate::utils::events::{self, Event};
+use crate::utils::{ci, tools};
+use anyhow::{anyhow, Result};
+use colored::*;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::io::{self, Write};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+// --- 🛰️ AGENT SESSION CLIENT (WebSocket) ---
+// Server က Agent Loop ကို Run မယ်။ CLI က File Tools (read_file/list_dir/grep) နဲ့ ask_user ကိုပဲ ဖြေပေးရမယ်။
+
+/// Session ပြီးဆုံးပုံ
+pub enum SessionOutcome {
+    /// Final Plan (`/api/agent/plan` Response နဲ့ Format အတူတူ)
+    Plan(Value),
+    /// User က ask_user မှာ exit/quit လို့ ဖြေလိုက်တယ်
+    Cancelled,
+}
+
+pub struct AgentSession {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl AgentSession {
+    /// `http://host` -> `ws://host/api/agent/session`
+    pub async fn connect(base_url: &str) -> Result<Self> {
+        let ws_url = format!(
+            "{}/api/agent/session",
+            base_url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1)
+        );
+        let (socket, _) = connect_async(ws_url.as_str())
+            .await
+            .map_err(|e| anyhow!("Agent session unavailable: {}", e))?;
+        Ok(Self { socket })
+    }
+
+    async fn send(&mut self, message: Value) -> Result<()> {
+        self.socket.send(Message::Text(message.to_string())).await?;
+        Ok(())
+    }
+
+    /// `start` payload (PlanRequest + model/rules) ကို ပို့ပြီး Plan ရတဲ့အထိ Tool Call တွေကို ဖြေမယ်
+    pub async fn run(mut self, mut start: Value) -> Result<SessionOutcome> {
+        start["type"] = json!("start");
+        self.send(start).await?;
+
+        while let Some(message) = self.socket.next().await {
+            let text = match message? {
+                Message::Text(text) => text,
+                Message::Close(_) => break,
+                _ => continue,
+            };
+            let event: Value = serde_json::from_str(&text)?;
+
+            match event["type"].as_str().unwrap_or("") {
+                "tool_call" => {
+                    let name = event["name"].as_str().unwrap_or("");
+                    println!("{} {} {}", "🔎".blue(), name.cyan(), describe_args(&event["arguments"]).dimmed());
+
+                    let reply = match tools::execute(name, &event["arguments"]) {
+                        Ok(output) => json!({ "type": "tool_result", "id": event["id"], "ok": true, "output": output }),
+                        Err(e) => {
+                            println!("   {} {}", "⚠️".yellow(), e);
+                            json!({ "type": "tool_result", "id": event["id"], "ok": false, "output": e.to_string() })
+                        }
+                    };
+                    self.send(reply).await?;
+                }
+                "ask_user" => {
+                    println!("\n{} Neurust: {}", "🤖".green(), event["question"].as_str().unwrap_or(""));
+                    print!("{} Reply > ", "👤".blue());
+                    io::stdout().flush()?;
+
+                    let mut answer = String::new();
+                    if ci::interactive() {
+                        io::stdin().read_line(&mut answer)?;
+                    } else {
+                        answer = ci::auto_reply(event["question"].as_str().unwrap_or(""));
+                    }
+                    let answer = answer.trim();
+
+                    if answer.eq_ignore_ascii_case("exit") || answer.eq_ignore_ascii_case("quit") {
+                        self.send(json!({ "type": "cancel" })).await?;
+                        let _ = self.socket.close(None).await;
+                        return Ok(SessionOutcome::Cancelled);
+                    }
+                    self.send(json!({ "type": "user_reply", "id": event["id"], "message": answer })).await?;
+                }
+                "turn" => {
+                    let tools: Vec<String> = event["tools"]
+                        .as_array()
+                        .map(|t| t.iter().filter_map(|v| v.as_str()).map(str::to_string).collect())
+                        .unwrap_or_default();
+                    events::emit(Event::TurnCompleted { turn: event["turn"].as_u64().unwrap_or_default(), tools });
+                }
+                "plan" => {
+                    println!(
+                        "{} Plan ready after {} turn(s), {} tool call(s) [{}]",
+                        "✅".green(),
+                        event["turns"],
+                        event["tool_calls"],
+                        event["model"].as_str().unwrap_or("model")
+                    );
+                    let _ = self.socket.close(None).await;
+                    return Ok(SessionOutcome::Plan(event["plan"].clone()));
+                }
+                "error" => {
+                    return Err(ApiError::session(
+                        event["code"].as_str().unwrap_or("error"),
+                        event["error"].as_str().unwrap_or("Unknown error"),
+                    ));
+                }
+                other => println!("{} Unknown session event: {}", "⚠️".yellow(), other),
+            }
+        }
+
+        Err(anyhow!("Agent session closed before a plan was produced"))
+    }
+}
+
+fn describe_args(args: &Value) -> String {
+    ["path", "pattern"]
+        .iter()
+        .filter_map(|key| args[*key].as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,107 @@
+use crate::api::client::ApiClient;
+use crate::utils::planfile::PlanFile;
+use crate::utils::ci::{self, Exit};
+use crate::utils::events::{self, Event};
+use crate::utils::{executor, memory};
+use anyhow::{bail, Result};
+use colored::*;
+
+/// `neurust apply` Options
+#[derive(Debug, Clone, Default)]
+pub struct ApplyOptions {
+    /// Plan file written by `neurust ask --plan-out`
+    pub file: String,
+    /// Replay the edits on files that changed since the plan was saved
+    pub rebase: bool,
+    /// Apply even if target files changed (overwrites those changes)
+    pub force: bool,
+    /// Model for auto-healing fix plans (None = Server Smart Routing)
+    pub model: Option<String>,
+}
+
+/// `neurust apply plan.json` - သိမ်းထားတဲ့ Plan ကို `executor::execute_plan` နဲ့ Run မယ်
+/// Plan သိမ်းပြီးနောက် Target Files ပြောင်းသွားရင် Default က ငြင်းမယ်
+pub async fn execute(options: ApplyOptions) -> Result<()> {
+    let file = PlanFile::load(&options.file)?;
+    println!(
+        "{} Plan file {} ({} action(s), saved {})",
+        "📋".cyan(),
+        options.file.bold(),
+        file.action_count(),
+        file.created_at.dimmed()
+    );
+    println!("   {} {}", "Prompt:".bold(), file.prompt);
+    if let Some(message) = &file.message {
+        println!("\n{} Neurust: {}", "🤖".green(), message);
+    }
+
+    let changed = file.changed();
+    let plan = if changed.is_empty() {
+        file.plan.clone()
+    } else {
+        println!("\n{} {} file(s) changed since the plan was saved:", "⚠️".yellow(), changed.len());
+        for change in &changed {
+            println!("   {:<8} {}", change.how.yellow(), change.path);
+        }
+
+        if options.force {
+            println!("{} --force: applying the plan as saved (those changes may be overwritten).", "⚠️".yellow());
+            file.plan.clone()
+        } else if options.rebase {
+            let rebase = file.rebase(&changed);
+            if !rebase.conflicts.is_empty() {
+                println!("\n{} Cannot rebase the plan onto the current files:", "❌".red());
+                for conflict in &rebase.conflicts {
+                    println!("   - {}", conflict.red());
+                }
+                ci::fail(Exit::Refused);
+                bail!("Rebase failed with {} conflict(s); nothing was changed", rebase.conflicts.len());
+            }
+            println!("{} Rebased onto the current files:", "🔁".cyan());
+            for note in &rebase.rebased {
+                println!("   - {}", note);
+            }
+            rebase.plan
+        } else {
+            ci::fail(Exit::Refused);
+            bail!(
+                "Refusing to apply {}: the tree changed since the plan was saved. \
+                 Use --rebase to replay the edits on the current files, or --force to apply it as saved",
+                options.file
+            );
+        }
+    };
+
+    let label = format!("{} (plan file {})", file.prompt, options.file);
+    events::emit(Event::PlanReceived {
+        label: label.clone(),
+        message: file.message.clone(),
+        init_command: None,
+        actions: plan.clone(),
+        dry_run: false,
+    });
+
+    let client = ApiClient::new("http://127.0.0.1:8000".to_string()).with_model(options.model);
+    let mut mem = memory::ProjectMemory::load();
+
+    println!("{} Executing Plan...", "⚙️".cyan());
+    let report = executor::execute_plan(&plan, &client, &mut mem, &label).await?;
+
+    if report.ended {
+        println!("{} Plan stopped at an `ask_user` step (answered \"exit\"); the remaining actions were skipped.", "🛑".yellow());
+    } else if report.commands.is_empty() || report.all_succeeded() {
+        println!("{} Plan applied.", "✅".green());
+    } else {
+        println!("{} Plan applied, but some commands failed. Check the output above.", "⚠️".yellow());
+    }
+    if report.has_failures() {
+        ci::fail(Exit::VerificationFailed);
+    }
+    if let Some(id) = &report.run_id {
+        println!("   {}", format!("Run {} (undo with `neurust undo {}`)", id, id).dimmed());
+    }
+
+    mem.record_event(&format!("Applied plan file {} ({} action(s))", options.file, file.action_count()));
+    let _ = mem.save();
+    Ok(())
+}
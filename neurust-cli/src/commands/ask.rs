@@ -1,14 +1,50 @@
 use colored::*;
 use anyhow::Result;
-use crate::api::client::ApiClient;
-use crate::utils::{fs, context, memory, executor}; 
+use crate::api::client::{ApiClient, PlanRequest};
+use crate::api::session::SessionOutcome;
+use crate::utils::{fs, ci, context, events, memory, executor, history, planfile, rules, tools, verify};
+use crate::utils::ci::{Exit, Prompt};
+use crate::utils::config::{FeedBack, NeurustConfig};
+use dialoguer::{theme::ColorfulTheme, Confirm};
 use std::io::{self, Write};
 use std::path::Path;
 
-pub async fn execute(prompt: String) -> Result<()> {
+/// `neurust ask` Options
+#[derive(Debug, Clone, Default)]
+pub struct AskOptions {
+    /// Model to use (None = Server Smart Routing)
+    pub model: Option<String>,
+    /// Refuse any single AI request estimated above this many credits
+    pub max_cost: Option<i64>,
+    /// Bypass the server response cache
+    pub no_cache: bool,
+    /// Preview the plan without writing or running anything
+    pub dry_run: bool,
+    /// Save the final plan as a plan file (`neurust apply`)
+    pub plan_out: Option<String>,
+}
+
+pub async fn execute(prompt: String, options: AskOptions) -> Result<()> {
     println!("{} Neurust Agent listening: '{}'", "🤖".purple(), prompt);
+    if let Some(m) = &options.model {
+        println!("{} Using model: {}", "🎛️".cyan(), m.bold());
+    }
 
-    let client = ApiClient::new("http://127.0.0.1:8000".to_string());
+    let config = NeurustConfig::load();
+    let guard = CostGuard {
+        max_cost: options.max_cost.or(config.cost.max_cost),
+        confirm_above: config.cost.confirm_above,
+    };
+
+    let rule_sources = rules::sources();
+    if !rule_sources.is_empty() {
+        let labels: Vec<&str> = rule_sources.iter().map(|s| s.label).collect();
+        println!("{} Applying project rules ({})", "📜".cyan(), labels.join(" + "));
+    }
+
+    let client = ApiClient::new("http://127.0.0.1:8000".to_string())
+        .with_model(options.model)
+        .with_no_cache(options.no_cache);
 
     // 1. Memory Load & Smart Context
     let mut mem = memory::ProjectMemory::load();
@@ -36,6 +72,37 @@ pub async fn execute(prompt: String) -> Result<()> {
     
     println!("{} Environment detected & Files loaded.", "🌍".blue());
 
+    // --- 🛰️ Server-side Agent Loop (Tool Calling) ---
+    // Server က read_file/list_dir/grep ကို လိုသလို ခေါ်မယ်။ Session မရရင် Legacy Loop ကို ဆက်သုံးမယ်
+    let request = PlanRequest { context: current_context.clone(), ..PlanRequest::new(&prompt) };
+    if !guard.allows(&client, &request).await {
+        println!("{} Request cancelled before spending credits.", "🛑".yellow());
+        return Ok(());
+    }
+
+    match client.run_session(&request).await {
+        Ok(Some(SessionOutcome::Plan(response))) => {
+            report_context_trimming(&response);
+            if let Some(msg) = response["message"].as_str().filter(|m| !m.trim().is_empty()) {
+                println!("\n{} Neurust: {}", "🤖".green(), msg);
+            }
+            if !stage_plan(&prompt, &response, options.dry_run, options.plan_out.as_deref())? {
+                return Ok(());
+            }
+            return execute_and_observe(prompt, response, &client, &mut mem, &guard, &config).await;
+        }
+        Ok(Some(SessionOutcome::Cancelled)) => {
+            println!("{} Ending conversation.", "👋".blue());
+            return Ok(());
+        }
+        Ok(None) => {}
+        Err(e) => {
+            println!("{} AI Connection Error: {}", "❌".red(), e);
+            ci::fail_with(&e);
+            return Ok(());
+        }
+    }
+
     let mut conversation_history = String::new(); 
 
     // --- The Agent Loop (Max 10 Turns) ---
@@ -54,22 +121,35 @@ pub async fn execute(prompt: String) -> Result<()> {
              }
         }
 
-        let full_prompt = if conversation_history.is_empty() {
-            prompt.clone()
-        } else {
-            format!("ORIGINAL REQUEST: {}\n\nCONVERSATION HISTORY:\n{}", prompt, conversation_history)
+        // History ကို Prompt ထဲ မရောဘဲ သီးသန့်ပို့မယ် (Server Token Budget က အသစ်ဆုံးကို ထားပေးမယ်)
+        let request = PlanRequest {
+            context: current_context.clone(),
+            history: (!conversation_history.is_empty()).then(|| conversation_history.clone()),
+            ..PlanRequest::new(&prompt)
         };
 
+        // 🔮 Preflight Cost Guard (--max-cost / [cost].confirm_above)
+        if !guard.allows(&client, &request).await {
+            println!("{} Request cancelled before spending credits.", "🛑".yellow());
+            return Ok(());
+        }
+
         // Call AI Brain
-        let response_result = client.fetch_plan(&full_prompt, current_context.clone()).await;
+        let response_result = client.fetch_plan_with(&request).await;
         let response = match response_result {
             Ok(res) => res,
             Err(e) => {
-                println!("{} AI Connection Error: {}", "❌".red(), e); 
+                println!("{} AI Connection Error: {}", "❌".red(), e);
+                ci::fail_with(&e);
                 return Ok(()); 
             }
         };
 
+        report_context_trimming(&response);
+        if response["cached"].as_bool().unwrap_or(false) {
+            println!("{} Served from response cache (reduced billing). Use --no-cache for a fresh answer.", "⚡".cyan());
+        }
+
         let plan = response["plan"].as_array().map(|v| v.clone()).unwrap_or_default();
         let init_cmd = response["init_command"].as_str().unwrap_or("");
 
@@ -83,7 +163,9 @@ pub async fn execute(prompt: String) -> Result<()> {
                     io::stdout().flush()?;
                     
                     let mut user_reply = String::new();
-                    if io::stdin().read_line(&mut user_reply).is_err() { return Ok(()); }
+                    if !ci::interactive() {
+                        user_reply = ci::auto_reply(msg);
+                    } else if io::stdin().read_line(&mut user_reply).is_err() { return Ok(()); }
                     let trimmed_reply = user_reply.trim();
 
                     if trimmed_reply.eq_ignore_ascii_case("exit") || trimmed_reply.eq_ignore_ascii_case("quit") {
@@ -123,6 +205,33 @@ pub async fn execute(prompt: String) -> Result<()> {
                         println!("{} File not found: {}", "❌".red(), path);
                     }
                 }
+            } else if action["action"] == "list_dir" || action["action"] == "grep" {
+                let kind = action["action"].as_str().unwrap_or("");
+                let target = action["pattern"].as_str().or(action["path"].as_str()).unwrap_or(".");
+                println!("{} {}: {}", "🔎".blue(), kind, target);
+
+                let output = tools::execute(kind, action).unwrap_or_else(|e| format!("ERROR: {}", e));
+                context_buffer.push_str(&format!("\n\n--- {} {} ---\n{}\n", kind.to_uppercase(), target, output));
+                context_updated = true;
+            } else if let Some(question) = action["question"].as_str().filter(|_| action["action"] == "ask_user") {
+                println!("\n{} Neurust: {}", "🤖".green(), question);
+                print!("{} Reply > ", "👤".blue());
+                io::stdout().flush()?;
+
+                let mut answer = String::new();
+                if !ci::interactive() {
+                    answer = ci::auto_reply(question);
+                } else if io::stdin().read_line(&mut answer).is_err() { return Ok(()); }
+                let answer = answer.trim();
+                if answer.eq_ignore_ascii_case("exit") || answer.eq_ignore_ascii_case("quit") {
+                    println!("{} Ending conversation.", "👋".blue());
+                    return Ok(());
+                }
+
+                mem.record_event(&format!("Q: {} / A: {}", question, answer));
+                let _ = mem.save();
+                conversation_history.push_str(&format!("\nAI: {}\nUser: {}\n", question, answer));
+                context_updated = true;
             }
         }
 
@@ -133,20 +242,335 @@ pub async fn execute(prompt: String) -> Result<()> {
         }
 
         // 3. Execution Phase (Delegated to Executor)
-        if !init_cmd.is_empty() {
-            println!("{} Handing over to Project Creator...", "🏗️".cyan());
-            return executor::smart_create_execute(prompt, response).await;
+        if !stage_plan(&prompt, &response, options.dry_run, options.plan_out.as_deref())? {
+            return Ok(());
         }
+        return execute_and_observe(prompt, response, &client, &mut mem, &guard, &config).await;
+    }
+
+    println!("{} Conversation limit reached.", "🛑".red());
+    Ok(())
+}
+
+/// Final Plan Response ကို Executor ဆီ လွှဲမယ် (init_command -> Project Creator, plan -> Executor)
+/// ပြီးရင် Command ရလဒ်တွေကို Observation Turn အဖြစ် Model ဆီ ပြန်ပို့မယ်
+async fn execute_and_observe(
+    prompt: String,
+    response: serde_json::Value,
+    client: &ApiClient,
+    mem: &mut memory::ProjectMemory,
+    guard: &CostGuard,
+    config: &NeurustConfig,
+) -> Result<()> {
+    let settings = &config.agent;
+    if !response["init_command"].as_str().unwrap_or("").is_empty() {
+        println!("{} Handing over to Project Creator...", "🏗️".cyan());
+        return executor::smart_create_execute(prompt, response).await;
+    }
+
+    let mut response = response;
+    let mut observations = String::new();
+    // ဒီ Prompt အတွက် Run ခဲ့တဲ့ Plans (`[history].auto_rollback` အတွက်)
+    let mut run_ids: Vec<String> = Vec::new();
+    let mut verification_failed = false;
+    let mut failing = false;
+
+    // --- 👀 Observation Loop ---
+    // Success Criterion: Command အားလုံး အောင်မြင် (observe_success = false) / Model က Plan အလွတ် ပြန်ပေး
+    // Budget: [agent].max_observation_turns + Cost Guard
+    for turn in 0..=settings.max_observation_turns {
+        if response["plan"].as_array().is_none_or(|p| p.is_empty()) {
+            // Agent က "ပြီးပြီ" ပြောပေမယ့် Commands / Verification က Fail ဖြစ်နေတုန်း -> CI မှာ Success မပြဘူး
+            if failing || verification_failed {
+                println!("{} The agent stopped while commands or verification are still failing.", "⚠️".yellow());
+                ci::fail(Exit::VerificationFailed);
+            }
+            return Ok(());
+        }
+
+        println!("{} Executing Plan...", "⚙️".cyan());
+        let mut report = executor::execute_plan(&response["plan"], client, mem, &prompt).await?;
+        run_ids.extend(report.run_id.clone());
+        if report.ended {
+            return Ok(());
+        }
+
+        // 🧪 Verification Pipeline (Plan က နောက်ဆုံး Run ခဲ့တဲ့ Command နဲ့ တူရင် ထပ်မ Run ဘူး)
+        if config.verify.enabled {
+            let last_command = report.commands.last().map(|o| o.command.clone());
+            let steps: Vec<_> = verify::resolve_steps(&config.verify)
+                .into_iter()
+                .filter(|step| last_command.as_deref() != Some(step.command().as_str()))
+                .collect();
+            let verification = verify::run(&steps).await;
 
-        if !plan.is_empty() {
-            println!("{} Executing Plan...", "⚙️".cyan());
-            executor::execute_plan(&response["plan"], &client, &mut mem).await?;
-            return Ok(()); 
+            if !verification.results.is_empty() {
+                mem.record_event(&format!("Verification: {}", verification.summary()));
+                let _ = mem.save();
+            }
+            verification_failed = !verification.passed();
+            if verification_failed && !feed_back_allowed(config.verify.feed_back) {
+                println!("{} Verification failed ({}). Not sending results to the agent.", "🛑".yellow(), verification.summary());
+                ci::fail(Exit::VerificationFailed);
+                auto_rollback(&run_ids, config, mem);
+                return Ok(());
+            }
+            report.commands.extend(verification.results.into_iter().map(|r| r.outcome));
+        }
+
+        if report.commands.is_empty() {
+            return Ok(());
+        }
+        failing = report.has_failures();
+        if report.all_succeeded() && !settings.observe_success {
+            println!("{} All commands succeeded.", "✅".green());
+            return Ok(());
+        }
+        if turn == settings.max_observation_turns {
+            break;
+        }
+
+        println!(
+            "{} Observation turn {}/{}: reporting {} command result(s) back to the agent...",
+            "👀".cyan(),
+            turn + 1,
+            settings.max_observation_turns,
+            report.commands.len()
+        );
+
+        let observation = report.observation(settings.max_output_chars);
+        let request = PlanRequest {
+            context: Some(format!(
+                "{}\n\n--- PROJECT MEMORY (PAST ACTIONS) ---\n{}",
+                mem.project_context, mem.summary
+            )),
+            history: (!observations.is_empty()).then(|| observations.clone()),
+            ..PlanRequest::new(&format!(
+                "{}\n\n--- OBSERVATION (results of your last plan) ---\n{}\n\
+                 Verify these results. If the task is complete, reply with an EMPTY plan and a short summary. \
+                 Otherwise reply with a plan that fixes the problems.",
+                prompt, observation
+            ))
+        };
+        observations.push_str(&format!("\n--- OBSERVATION {} ---\n{}\n", turn + 1, observation));
+
+        if !guard.allows(client, &request).await {
+            println!("{} Observation loop stopped before spending credits.", "🛑".yellow());
+            if failing || verification_failed {
+                ci::fail(Exit::VerificationFailed);
+            }
+            return Ok(());
         }
 
-        if init_cmd.is_empty() && plan.is_empty() { return Ok(()); }
+        response = match client.fetch_plan_with(&request).await {
+            Ok(res) => res,
+            Err(e) => {
+                println!("{} AI Connection Error: {}", "❌".red(), e);
+                ci::fail_with(&e);
+                return Ok(());
+            }
+        };
+        report_context_trimming(&response);
+        if let Some(msg) = response["message"].as_str().filter(|m| !m.trim().is_empty()) {
+            println!("\n{} Neurust: {}", "🤖".green(), msg);
+        }
+        if response["plan"].as_array().is_some_and(|p| !p.is_empty()) {
+            events::plan_received(&prompt, &response, false);
+        }
     }
 
-    println!("{} Conversation limit reached.", "🛑".red());
+    println!(
+        "{} Observation budget exhausted ({} turns). Check the remaining failures manually.",
+        "🛑".yellow(),
+        settings.max_observation_turns
+    );
+    if verification_failed || failing {
+        ci::fail(Exit::VerificationFailed);
+    }
+    if verification_failed {
+        auto_rollback(&run_ids, config, mem);
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// `--dry-run` (Preview ပဲ) / `--plan-out` (Plan File သိမ်းမယ်) -> ဆက် Execute လုပ်မလား
+fn stage_plan(prompt: &str, response: &serde_json::Value, dry_run: bool, plan_out: Option<&str>) -> Result<bool> {
+    let init_cmd = response["init_command"].as_str().unwrap_or("");
+    events::plan_received(prompt, response, dry_run);
+    if dry_run {
+        println!("\n{} Dry run: nothing will be written or executed.", "📋".cyan());
+        if !init_cmd.is_empty() {
+            println!("{} The Project Creator would run: {}", "🏗️".cyan(), init_cmd.bold());
+        }
+        planfile::preview(&response["plan"]);
+    }
+
+    if let Some(path) = plan_out {
+        if !init_cmd.is_empty() {
+            println!("{} Project creation plans cannot be saved as plan files; {} was not written.", "⚠️".yellow(), path);
+        } else if response["plan"].as_array().is_none_or(|p| p.is_empty()) {
+            println!("{} The agent returned no actions; {} was not written.", "ℹ️".blue(), path);
+        } else {
+            let file = planfile::PlanFile::capture(prompt, response)?;
+            file.save(path)?;
+            println!(
+                "{} Plan saved to {} ({} action(s), {} file hash(es)). Apply it with `neurust apply {}`.",
+                "💾".green(),
+                path,
+                file.action_count(),
+                file.files.len(),
+                path
+            );
+        }
+    }
+    Ok(!dry_run)
+}
+
+/// `[history].auto_rollback`: Verification Fail နဲ့ ရပ်သွားရင် ဒီ Prompt ရဲ့ Runs တွေကို ပြောင်းပြန်အစဉ်နဲ့ Undo မယ်
+fn auto_rollback(run_ids: &[String], config: &NeurustConfig, mem: &mut memory::ProjectMemory) {
+    let Some(last) = run_ids.last() else { return };
+    if !config.history.auto_rollback {
+        println!(
+            "{} To restore the previous state, run `neurust undo` {} time(s) (latest run: {}).",
+            "⏪".blue(),
+            run_ids.len(),
+            last
+        );
+        return;
+    }
+
+    println!("{} Verification failed: rolling back {} run(s)...", "⏪".yellow(), run_ids.len());
+    for id in run_ids.iter().rev() {
+        match history::load_run(id).and_then(|mut run| run.rollback()) {
+            Ok(files) => println!("   {} {} ({} file(s) restored)", "↩️".green(), id, files.len()),
+            Err(e) => println!("   {} {}: {}", "❌".red(), id, e),
+        }
+    }
+    mem.record_event(&format!("Auto-rollback after failed verification: {}", run_ids.join(", ")));
+    let _ = mem.save();
+}
+
+/// Server Token Budget က Context ကို ဖြတ်လိုက်ရင် User ကို အသိပေးမယ်
+fn report_context_trimming(response: &serde_json::Value) {
+    let report = &response["context_report"];
+    let trimmed: Vec<&str> = report["sections"]
+        .as_array()
+        .map(|sections| {
+            sections
+                .iter()
+                .filter(|s| s["trimmed"].as_bool().unwrap_or(false))
+                .filter_map(|s| s["section"].as_str())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if trimmed.is_empty() {
+        return;
+    }
+
+    println!(
+        "{} Context trimmed to fit {} ({} tokens): {}",
+        "✂️".yellow(),
+        report["model"].as_str().unwrap_or("model"),
+        report["context_window"].as_u64().unwrap_or(0),
+        trimmed.join(", ")
+    );
+
+    if let Some(files) = report["dropped_files"].as_array().filter(|f| !f.is_empty()) {
+        let names: Vec<&str> = files.iter().filter_map(|f| f.as_str()).collect();
+        println!("   {} Omitted files: {}", "📄".dimmed(), names.join(", ").dimmed());
+    }
+}
+
+/// `[verify].feed_back`: Verification Fail ဖြစ်ရင် Agent ဆီ ပြန်ပို့မလား
+fn feed_back_allowed(policy: FeedBack) -> bool {
+    match policy {
+        FeedBack::Always => true,
+        FeedBack::Never => false,
+        FeedBack::Ask => ci::confirm(Prompt::FeedBack, "verification failures", || {
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("Verification failed. Send the failures back to the agent for a fix?")
+                .default(true)
+                .interact()
+                .unwrap_or(false)
+        }),
+    }
+}
+
+/// 🔮 Preflight Cost Guard (--max-cost / [cost].confirm_above)
+struct CostGuard {
+    max_cost: Option<i64>,
+    confirm_above: Option<i64>,
+}
+
+impl CostGuard {
+    /// Limit မသတ်မှတ်ထားရင် Estimate မခေါ်ဘဲ ခွင့်ပြုမယ်
+    async fn allows(&self, client: &ApiClient, request: &PlanRequest) -> bool {
+        if self.max_cost.is_none() && self.confirm_above.is_none() {
+            return true;
+        }
+        confirm_estimated_cost(client, request, self.max_cost, self.confirm_above).await
+    }
+}
+
+/// Server Estimate ကို ကြည့်ပြီး ဆက်လုပ်မလား ဆုံးဖြတ်မယ်
+/// - Estimate (max) > max_cost  -> Refuse
+/// - Estimate (max) > confirm_above -> User ကို မေးမယ်
+async fn confirm_estimated_cost(
+    client: &ApiClient,
+    request: &PlanRequest,
+    max_cost: Option<i64>,
+    confirm_above: Option<i64>,
+) -> bool {
+    let estimate = match client.estimate_plan(request).await {
+        Ok(estimate) => estimate,
+        Err(e) => {
+            println!("{} Could not estimate cost: {}", "⚠️".yellow(), e);
+            ci::fail_with(&e);
+            // Hard limit ရှိရင် မသိဘဲ မသုံးရဲဘူး
+            if max_cost.is_some() {
+                ci::fail(Exit::Refused);
+            }
+            return max_cost.is_none();
+        }
+    };
+
+    println!(
+        "{} Estimate [{}]: ~{} input tokens, {}-{} output tokens -> {}-{} credits (${:.4}-${:.4})",
+        "🔮".cyan(),
+        estimate.model,
+        estimate.input_tokens,
+        estimate.output_tokens_min,
+        estimate.output_tokens_max,
+        estimate.credits_min,
+        estimate.credits_max,
+        estimate.cost_usd_min,
+        estimate.cost_usd_max
+    );
+
+    if let Some(limit) = max_cost {
+        if estimate.credits_max > limit {
+            println!(
+                "{} Estimated cost (up to {} credits) exceeds --max-cost {}.",
+                "❌".red(),
+                estimate.credits_max,
+                limit
+            );
+            ci::fail(Exit::Refused);
+            return false;
+        }
+    }
+
+    match confirm_above {
+        Some(threshold) if estimate.credits_max > threshold => {
+            ci::confirm(Prompt::OverBudget, &format!("up to {} credits", estimate.credits_max), || {
+                Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("This request may cost up to {} credits. Continue?", estimate.credits_max))
+                    .default(false)
+                    .interact()
+                    .unwrap_or(false)
+            })
+        }
+        _ => true,
+    }
+}
@@ -1,22 +1,45 @@
 use crate::api::client::ApiClient;
-use crate::utils::{fs, cmd};
+use crate::utils::{ci, fs, cmd};
 use colored::*;
 use anyhow::Result;
+use regex::Regex;
 use std::path::Path;
 
-pub async fn execute(path: String) -> Result<()> {
+pub async fn execute(path: String, model: Option<String>, no_cache: bool) -> Result<()> {
     println!("{}", "🛡️  Starting Deep Security Audit...".cyan().bold());
     println!("{}", "------------------------------------------------".dimmed());
 
+    let client = ApiClient::new("http://127.0.0.1:8000".to_string())
+        .with_model(model)
+        .with_no_cache(no_cache);
+
+    match report(&path, &client).await {
+        Ok(report) => {
+            println!("\n{}\n", "=".repeat(60).green());
+            println!("{}", report); // Markdown Report Output
+            println!("{}\n", "=".repeat(60).green());
+            println!("✅ Audit Complete.");
+        },
+        Err(e) => {
+            println!("{} Brain Failure: {}", "❌".red(), e);
+            ci::fail_with(&e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Phase 1-3 (cargo-audit + Source + Auditor Brain) -> Markdown Report (`neurust lsp` ကလည်း သုံးတယ်)
+pub async fn report(path: &str, client: &ApiClient) -> Result<String> {
     // 1. Check & Run Dependency Scan (RustSec)
     println!("{}", "🔍 Phase 1: Scanning dependencies (cargo-audit)...".blue());
     
     // Check version silently first (capture output not needed, just success)
-    let has_audit_tool = cmd::execute_with_output("cargo", &["audit", "--version"], None).is_ok();
+    let has_audit_tool = cmd::execute_with_output("cargo", &["audit", "--version"], None).await.is_ok();
 
     let audit_json = if has_audit_tool {
         // 🔥 FIX: Use `execute_and_capture` here to get the JSON String
-        match cmd::execute_and_capture("cargo", &["audit", "--json"], None) {
+        match cmd::execute_and_capture("cargo", &["audit", "--json"], None).await {
             Ok(output) => {
                 // If output is essentially empty, assume failure
                 if output.trim().is_empty() {
@@ -38,7 +61,7 @@ pub async fn execute(path: String) -> Result<()> {
 
     // 2. Read Source Code
     println!("{} {}", "📖 Phase 2: Reading source code from:".cyan(), path);
-    let source_code = match fs::read_file(&path) {
+    let source_code = match fs::read_file(path) {
         Ok(code) => code,
         Err(_) => {
             println!("{}", "❌ Error: Could not read file. Audit will be limited.".red());
@@ -48,9 +71,7 @@ pub async fn execute(path: String) -> Result<()> {
 
     // 3. Send to Neurust Brain (Hybrid Analysis)
     println!("{}", "🧠 Phase 3: Consulting Neurust Auditor Brain...".magenta());
-    
-    let client = ApiClient::new("http://127.0.0.1:3000".to_string());
-    
+
     // Data နှစ်ခုကို ပေါင်းပြီး ပို့မယ်
     let combined_input = format!(
         "--- CARGO AUDIT JSON REPORT ---\n{}\n\n--- SOURCE CODE ({}) ---\n{}", 
@@ -58,17 +79,59 @@ pub async fn execute(path: String) -> Result<()> {
     );
 
     // Server ရှိ audit_code endpoint ကို လှမ်းခေါ်မယ်
-    match client.audit_code(&combined_input).await {
-        Ok(report) => {
-            println!("\n{}\n", "=".repeat(60).green());
-            println!("{}", report); // Markdown Report Output
-            println!("{}\n", "=".repeat(60).green());
-            println!("✅ Audit Complete.");
-        },
-        Err(e) => {
-            println!("{} Brain Failure: {}", "❌".red(), e);
+    client.audit_code(&combined_input).await
+}
+
+/// Report Section အလိုက် အရေးကြီးမှု
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Critical,
+    Warning,
+    Suggestion,
+}
+
+/// Report ထဲက Bullet တစ်ခု (`line` = Report က Line Number ပြောထားရင် 1-based)
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    pub line: Option<usize>,
+}
+
+/// Auditor Prompt ရဲ့ Markdown Format (`## 🚨 Critical ...` / `## ⚠️ Warnings ...` / `## ℹ️ Suggestions ...`) ကို ခွဲမယ်
+/// "Safe Patterns" Section နဲ့ Section မသိတဲ့ Bullets တွေကို ချန်မယ်
+pub fn findings(report: &str) -> Vec<Finding> {
+    let line_ref = Regex::new(r"(?i)(?:\blines?\s+|\bL|\.rs:)(\d+)").unwrap();
+    let mut severity = None;
+    let mut findings = Vec::new();
+
+    for line in report.lines() {
+        let trimmed = line.trim();
+        if let Some(heading) = trimmed.strip_prefix("##") {
+            let heading = heading.to_lowercase();
+            severity = if heading.contains("critical") {
+                Some(Severity::Critical)
+            } else if heading.contains("warning") || heading.contains("risk") {
+                Some(Severity::Warning)
+            } else if heading.contains("suggestion") {
+                Some(Severity::Suggestion)
+            } else {
+                None
+            };
+            continue;
         }
-    }
 
-    Ok(())
+        let Some(severity) = severity else { continue };
+        let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) else { continue };
+        let item = item.trim();
+        if item.is_empty() || item == "..." || item.eq_ignore_ascii_case("none") {
+            continue;
+        }
+        findings.push(Finding {
+            severity,
+            message: item.replace("**", ""),
+            line: line_ref.captures(item).and_then(|c| c[1].parse().ok()),
+        });
+    }
+    findings
 }
\ No newline at end of file
@@ -0,0 +1,31 @@
+use crate::commands::models;
+use anyhow::Result;
+use clap::builder::PossibleValuesParser;
+use clap::Command;
+use clap_complete::Shell;
+use colored::*;
+
+/// `neurust completions <shell>` - Completion Script ကို stdout ထဲ ထုတ်မယ်
+/// `--model` Values = Server Model Catalog (REPL Tab-completion နဲ့ အတူတူ)၊ Server မရရင် Free Text
+pub async fn execute(shell: Shell, mut command: Command) -> Result<()> {
+    let model_ids = models::available_ids().await;
+    if model_ids.is_empty() {
+        eprintln!("{} Model catalog unavailable: `--model` will not complete model IDs.", "⚠️".yellow());
+    } else {
+        // Script Generate ဖို့ပဲ ပြောင်းမယ် (Parse လုပ်တဲ့ Cli ကတော့ Catalog မှာ မပါတဲ့ ID ကိုလည်း လက်ခံတယ်)
+        let with_model: Vec<String> = command
+            .get_subcommands()
+            .filter(|sub| sub.get_arguments().any(|arg| arg.get_id() == "model"))
+            .map(|sub| sub.get_name().to_string())
+            .collect();
+        for name in with_model {
+            command = command.mut_subcommand(name, |sub| {
+                sub.mut_arg("model", |arg| arg.value_parser(PossibleValuesParser::new(model_ids.clone())))
+            });
+        }
+    }
+
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
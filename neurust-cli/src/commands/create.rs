@@ -1,5 +1,9 @@
 use crate::api::client::ApiClient;
-use crate::utils::{cmd, deps, fs};
+use crate::utils::config::NeurustConfig;
+use crate::utils::history::Transaction;
+use crate::utils::policy::CommandPolicy;
+use crate::utils::events::{self, FileChange};
+use crate::utils::{ci, cmd, deps, fs};
 use anyhow::{anyhow, Result};
 use colored::*;
 use std::path::Path;
@@ -16,7 +20,17 @@ pub async fn execute(raw_input: String, _project_type: String) -> Result<()> {
 }
 
 /// ask.rs မှ တိုက်ရိုက်ခေါ်သုံးမည့် Function
+/// AI ရွေးတဲ့ Commands အတွက် Policy Decisions တွေကို Run History ထဲ မှတ်မယ်
 pub async fn execute_with_plan(raw_input: String, response: serde_json::Value) -> Result<()> {
+    let mut tx = Transaction::begin(&format!("neurust create: {}", raw_input), &NeurustConfig::load().history);
+    let result = setup_project(&response, &mut tx).await;
+    if let Some(run_id) = tx.commit() {
+        println!("{} Policy decisions saved to run {}", "📝".dimmed(), run_id);
+    }
+    result
+}
+
+async fn setup_project(response: &serde_json::Value, tx: &mut Transaction) -> Result<()> {
     let suggested_name = response["suggested_name"]
         .as_str()
         .unwrap_or("neurust_project");
@@ -29,12 +43,17 @@ pub async fn execute_with_plan(raw_input: String, response: serde_json::Value) -
 
     // --- Step 1: Initialization (Smart Error Handling) ---
 
+    // AI ပေးတဲ့ Command တွေကို Command Policy နဲ့ စစ်မယ်
+    let policy = CommandPolicy::new(&NeurustConfig::load().policy);
+
     let parts: Vec<&str> = init_cmd.split_whitespace().collect();
     if let Some((program, args)) = parts.split_first() {
-        println!("{} Running init command...", "⚙️".blue());
-
-        // Command Run မယ်
-        if let Err(e) = cmd::execute(program, args, None) {
+        if !policy.permits(program, args, tx) {
+            println!("{} Init command was not run. Create the project manually: {}", "🛑".yellow(), init_cmd);
+            return Ok(());
+        }
+        // Command Run မယ် (Progress ကို `CommandStarted` / `CommandFinished` Events က ပြမယ်)
+        if let Err(e) = cmd::execute_setup(program, args, None).await {
             // Anchor ၏ Yarn Error ကို Soft Fail အနေနဲ့ ကိုင်တွယ်မယ်
             let err_msg = e.to_string();
             let folder_exists = Path::new(suggested_name).exists();
@@ -107,7 +126,7 @@ pub async fn execute_with_plan(raw_input: String, response: serde_json::Value) -
                         if let Err(e) = fs::write_file(&full_path, content) {
                             println!("{} Write Error: {}", "⚠️".yellow(), e);
                         } else {
-                            println!("{} Created: {}", "📝".green(), rel_path);
+                            events::file_written(rel_path, FileChange::Created);
                         }
                     }
                 }
@@ -118,8 +137,6 @@ pub async fn execute_with_plan(raw_input: String, response: serde_json::Value) -
                             .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
                             .unwrap_or_default();
 
-                        println!("{} Executing: {} {:?}", "⚡".yellow(), program, args);
-
                         // Yarn -> NPM Fallback Logic (AI Plan ထဲမှာ yarn ပါလာခဲ့ရင်)
                         let mut final_program = program;
                         if program == "yarn" {
@@ -130,12 +147,15 @@ pub async fn execute_with_plan(raw_input: String, response: serde_json::Value) -
                             final_program = "npm";
                         }
 
+                        if !policy.permits(final_program, &args, tx) {
+                            continue;
+                        }
+
                         // Project Folder ထဲဝင်ပြီး Run မယ်
-                        match cmd::execute(final_program, &args, Some(&abs_project_path)) {
-                            Ok(_) => println!("{} Success", "✅".green()),
-                            Err(e) => {
-                                println!("{} Task Failed (Non-critical): {}", "⚠️".yellow(), e)
-                            }
+                        if let Err(e) = cmd::execute_setup(final_program, &args, Some(&abs_project_path)).await {
+                            // Setup ကို ဆက်လုပ်မယ်၊ ဒါပေမဲ့ Exit Code မှာ မှတ်မယ်
+                            println!("{} Task Failed (continuing setup): {}", "⚠️".yellow(), e);
+                            ci::fail_with(&e);
                         }
                     }
                 }
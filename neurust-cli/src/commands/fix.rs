@@ -0,0 +1,177 @@
+use crate::api::client::{ApiClient, PlanRequest};
+use crate::utils::diagnostics::{self, DiagnosticReport};
+use crate::utils::ci::{self, Exit};
+use crate::utils::config::NeurustConfig;
+use crate::utils::events::{self, CommandSource};
+use crate::utils::history::Transaction;
+use crate::utils::sandbox::Sandbox;
+use crate::utils::{context, executor, memory};
+use anyhow::Result;
+use colored::*;
+
+/// `neurust fix` Options
+#[derive(Debug, Clone, Default)]
+pub struct FixOptions {
+    /// Model to use for the AI pass (None = Server Smart Routing)
+    pub model: Option<String>,
+    /// Local Fixes ပဲ လုပ်မယ် (AI မခေါ်ဘူး)
+    pub no_ai: bool,
+    /// `cargo clippy` ကို ကျော်မယ်
+    pub no_clippy: bool,
+}
+
+/// `neurust fix`
+/// 1. `cargo check` + `cargo clippy` (JSON) -> Structured Diagnostics
+/// 2. MachineApplicable Suggestions -> Diff Preview ပြပြီး Local မှာ Apply (Credit မကုန်)
+/// 3. ကျန်တာတွေကို Source Context နဲ့ Agent Request တစ်ခုတည်းအဖြစ် ပို့မယ်
+pub async fn execute(options: FixOptions) -> Result<()> {
+    if !std::path::Path::new("Cargo.toml").exists() {
+        println!("{} No Cargo.toml here. `neurust fix` works on Rust/Anchor projects.", "❌".red());
+        return Ok(());
+    }
+
+    println!("{} Collecting diagnostics...", "🩺".cyan());
+    let before = collect(options.no_clippy).await;
+    // Manifest / Dependency Error လို JSON Diagnostic မထွက်တဲ့ Failure က "Clean" မဟုတ်ဘူး
+    if !before.success && before.diagnostics.is_empty() {
+        println!("{} cargo failed without reporting diagnostics (see the output above).", "❌".red());
+        ci::fail(Exit::VerificationFailed);
+        return Ok(());
+    }
+    if before.diagnostics.is_empty() {
+        println!("{} Nothing to fix: no errors or warnings.", "✅".green());
+        return Ok(());
+    }
+    println!("   {} error(s), {} warning(s)", before.errors(), before.warnings());
+
+    // --- 🔧 Local Pass (Run History ထဲ မှတ်မယ် -> `neurust undo` နဲ့ ပြန်ရ) ---
+    let config = NeurustConfig::load();
+    let mut tx = Transaction::begin("neurust fix (compiler suggestions)", &config.history);
+    let local = apply_local(&before, &config, &mut tx);
+    if let Some(run_id) = tx.commit() {
+        println!("{} Local fixes saved as run {} (`neurust undo {}` reverts them).", "⏪".blue(), run_id, run_id);
+    }
+    let (local_fixes, local_files) = local?;
+    let after_local = if local_fixes > 0 { collect(options.no_clippy).await } else { before.clone() };
+    let remaining_local = after_local.diagnostics.len();
+
+    // --- 🧠 AI Pass (One Request) ---
+    let mut ai_fixed = 0;
+    let mut remaining = remaining_local;
+
+    if remaining_local > 0 && !options.no_ai {
+        println!(
+            "{} Sending {} remaining diagnostic(s) to the agent in one request...",
+            "🧠".yellow(),
+            remaining_local
+        );
+
+        let client = ApiClient::new("http://127.0.0.1:8000".to_string()).with_model(options.model);
+        let mut mem = memory::ProjectMemory::load();
+        if mem.project_context.is_empty() {
+            mem.project_context = context::get_project_context();
+            let _ = mem.save();
+        }
+
+        let request = PlanRequest {
+            context: Some(format!(
+                "{}\n\n--- PROJECT MEMORY (PAST ACTIONS) ---\n{}",
+                mem.project_context, mem.summary
+            )),
+            ..PlanRequest::new(&format!(
+                "Fix the following Rust compiler/clippy diagnostics. Use `apply_patch` with minimal edits. \
+                 Do not change behaviour beyond what the diagnostics require.\n\n--- DIAGNOSTICS ---\n{}",
+                after_local.to_prompt(3)
+            ))
+        };
+
+        match client.fetch_plan_with(&request).await {
+            Ok(response) => {
+                if let Some(msg) = response["message"].as_str().filter(|m| !m.trim().is_empty()) {
+                    println!("\n{} Neurust: {}", "🤖".green(), msg);
+                }
+                if response["plan"].as_array().is_some_and(|p| !p.is_empty()) {
+                    events::plan_received("neurust fix", &response, false);
+                    executor::execute_plan(&response["plan"], &client, &mut mem, "neurust fix").await?;
+                    let after_ai = collect(options.no_clippy).await;
+                    remaining = after_ai.diagnostics.len();
+                    ai_fixed = remaining_local.saturating_sub(remaining);
+                } else {
+                    println!("{} The agent returned no fix plan.", "⚠️".yellow());
+                }
+            }
+            Err(e) => {
+                println!("{} AI Connection Error: {}", "❌".red(), e);
+                ci::fail_with(&e);
+            }
+        }
+    }
+
+    // --- 📊 Report ---
+    println!("\n{}", "📊 Fix Summary".cyan().bold());
+    println!(
+        "   {} Fixed locally: {} suggestion(s) in {} file(s) (no credits used)",
+        "🔧".green(),
+        local_fixes,
+        local_files
+    );
+    if !options.no_ai {
+        println!("   {} Fixed by AI: {} diagnostic(s)", "🧠".green(), ai_fixed);
+    }
+    if remaining > 0 {
+        println!("   {} Remaining: {} diagnostic(s). Run `cargo check` for details.", "⚠️".yellow(), remaining);
+        ci::fail(Exit::VerificationFailed);
+    } else {
+        println!("   {} Project is clean.", "✅".green());
+    }
+
+    let mut mem = memory::ProjectMemory::load();
+    mem.record_event(&format!(
+        "neurust fix: {} local suggestion(s), {} AI fix(es), {} remaining",
+        local_fixes, ai_fixed, remaining
+    ));
+    let _ = mem.save();
+    Ok(())
+}
+
+/// `cargo check` (+ `cargo clippy`) Diagnostics (Error + Warning ပဲ)
+async fn collect(no_clippy: bool) -> DiagnosticReport {
+    let mut report = diagnostics::run_cargo(&["check", "--all-targets"], CommandSource::Check).await.report;
+
+    if !no_clippy {
+        let clippy = diagnostics::run_cargo(&["clippy", "--all-targets"], CommandSource::Check).await;
+        if clippy.outcome.stderr.contains("no such command") {
+            println!("   {} cargo clippy is not installed; skipping lints.", "ℹ️".blue());
+        } else {
+            report.merge(clippy.report);
+        }
+    }
+
+    report.diagnostics.retain(|d| d.is_error() || d.level == "warning");
+    report
+}
+
+/// MachineApplicable Suggestions ကို Diff ပြပြီး Apply မယ် (File မျှဝေတဲ့ Groups တွေကို အတူ မေးမယ်) -> (suggestions, files)
+fn apply_local(report: &DiagnosticReport, config: &NeurustConfig, tx: &mut Transaction) -> Result<(usize, usize)> {
+    let mut suggestions = report.machine_applicable();
+    // Project အပြင် (Registry / Path Dependency) နဲ့ Protected Files ကို မထိဘူး
+    let sandbox = Sandbox::new(&config.sandbox)?;
+    let mut blocked = std::collections::BTreeSet::new();
+    diagnostics::retain_groups(&mut suggestions, |s| match sandbox.check_write(&s.file) {
+        Ok(_) => true,
+        Err(e) => {
+            blocked.insert(e.to_string());
+            false
+        }
+    });
+    for reason in blocked {
+        println!("{} Skipping suggestions: {}", "🔒".yellow(), reason);
+    }
+    if suggestions.is_empty() {
+        println!("{} No machine-applicable suggestions.", "ℹ️".blue());
+        return Ok((0, 0));
+    }
+
+    let (applied, files) = diagnostics::apply_confirmed(&suggestions, &mut false, tx)?;
+    Ok((applied, files.len()))
+}
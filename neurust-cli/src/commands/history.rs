@@ -0,0 +1,171 @@
+use crate::utils::ci::{self, Prompt};
+use crate::utils::history::{self, RunRecord, RunStatus};
+use crate::utils::policy::Decision;
+use crate::utils::memory;
+use anyhow::{bail, Result};
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+
+/// `neurust history [run-id]` - Run စာရင်း (သို့) Run တစ်ခုရဲ့ Files / Commands
+pub fn list(run_id: Option<String>) -> Result<()> {
+    if let Some(id) = run_id {
+        print_run(&history::load_run(&id)?);
+        return Ok(());
+    }
+
+    let runs = history::list_runs();
+    if runs.is_empty() {
+        println!("{} No runs recorded yet ({}).", "ℹ️".blue(), history::history_dir().display());
+        return Ok(());
+    }
+
+    println!("{}", "⏪ Run History (newest first)".cyan().bold());
+    for run in &runs {
+        let status = match run.status {
+            RunStatus::Applied => "applied".green(),
+            RunStatus::Undone => "undone".dimmed(),
+        };
+        println!(
+            "   {}  {:<7}  {} file(s), {} command(s)  {}",
+            run.id.bold(),
+            status,
+            run.files.len(),
+            run.commands.len(),
+            truncate(&run.label, 60).dimmed()
+        );
+    }
+    println!("   {}", "Use `neurust history <run-id>` for details, `neurust undo [run-id]` to roll back.".dimmed());
+    Ok(())
+}
+
+/// `neurust undo [run-id]` - Run ID မပါရင် နောက်ဆုံး Applied Run
+pub fn undo(run_id: Option<String>) -> Result<()> {
+    let mut run = match run_id {
+        Some(id) => history::load_run(&id)?,
+        None => match history::latest_applied() {
+            Some(run) => run,
+            None => {
+                println!("{} Nothing to undo.", "ℹ️".blue());
+                return Ok(());
+            }
+        },
+    };
+    if run.status == RunStatus::Undone {
+        bail!("Run {} was already undone", run.id);
+    }
+
+    print_run(&run);
+
+    // Run ပြီးနောက် ထပ်ပြင်ထားတဲ့ File တွေရှိရင် အဲ့ဒီပြင်ဆင်မှုတွေ ပျောက်မယ်ဆိုတာ သတိပေးမယ်
+    let drifted = run.drifted();
+    if !drifted.is_empty() {
+        println!("\n{} These files changed after the run; undo will discard those changes too:", "⚠️".yellow());
+        for path in &drifted {
+            println!("   - {}", path);
+        }
+    }
+    if run.commands.iter().any(|c| !c.skipped) {
+        println!("\n{} Side effects of the commands above (installs, builds, deploys) are not reverted.", "ℹ️".blue());
+    }
+
+    // Non-interactive: Drift ရှိရင် အခြားသူ့ ပြင်ဆင်မှုတွေ မပျောက်အောင် အမြဲ ငြင်းမယ်
+    let confirmed = if !drifted.is_empty() && !ci::interactive() {
+        println!("{} Not undoing without a terminal: files changed after the run.", "🛑".yellow());
+        ci::fail(ci::Exit::Refused);
+        false
+    } else {
+        ci::confirm(Prompt::Undo, &run.id, || {
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Restore {} file(s) from run {}?", run.files.len(), run.id))
+                .default(drifted.is_empty())
+                .interact()
+                .unwrap_or(false)
+        })
+    };
+    if !confirmed {
+        println!("{} Undo cancelled.", "🛑".yellow());
+        return Ok(());
+    }
+
+    let restored = run.rollback()?;
+    for path in &restored {
+        println!("{} Restored: {}", "↩️".green(), path);
+    }
+    println!("{} Run {} undone.", "✅".green(), run.id);
+
+    let mut mem = memory::ProjectMemory::load();
+    for path in &restored {
+        mem.forget_file_context(path);
+    }
+    mem.record_event(&format!("Undid run {} ({} file(s) restored)", run.id, restored.len()));
+    let _ = mem.save();
+    Ok(())
+}
+
+fn print_run(run: &RunRecord) {
+    println!("\n{} Run {} ({})", "📜".cyan(), run.id.bold(), run.started_at.dimmed());
+    println!("   {} {}", "Prompt:".bold(), run.label);
+    if run.status == RunStatus::Undone {
+        println!("   {}", "Status: undone".dimmed());
+    }
+
+    if !run.files.is_empty() {
+        println!("   {}", "Files:".bold());
+        for file in &run.files {
+            let kind = if !file.existed {
+                "created".green()
+            } else if file.after.is_none() {
+                "deleted".red()
+            } else {
+                "modified".yellow()
+            };
+            println!("     {:<8} {}", kind, file.path);
+        }
+    }
+
+    if !run.commands.is_empty() {
+        println!("   {}", "Commands:".bold());
+        if let Some(log) = &run.log {
+            println!("     {}", format!("output log: {}", log).dimmed());
+        }
+        for command in &run.commands {
+            let result = if command.skipped {
+                "skipped".dimmed()
+            } else if let Some(stopped) = &command.stopped {
+                stopped.yellow()
+            } else if command.exit_code == Some(0) {
+                "ok".green()
+            } else {
+                format!("exit {}", command.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string())).red()
+            };
+            println!("     {:<8} {} ({:.1}s)", result, command.command, command.duration_secs);
+        }
+    }
+
+    // Allow + Risk မရှိတာတွေက များလို့ မပြဘူး (run.json ထဲမှာတော့ ရှိတယ်)
+    let notable: Vec<_> = run.decisions.iter().filter(|d| d.decision != Decision::Allow || !d.risks.is_empty()).collect();
+    if !notable.is_empty() {
+        println!("   {}", "Policy decisions:".bold());
+        for entry in notable {
+            let verdict = match (entry.decision, entry.approved) {
+                (Decision::Deny, _) => "denied".red(),
+                (Decision::Confirm, Some(true)) => "approved".yellow(),
+                (Decision::Confirm, _) => "declined".dimmed(),
+                (Decision::Allow, _) => "allowed".green(),
+            };
+            println!("     {:<8} {} ({})", verdict, entry.command, entry.risks.join("; ").dimmed());
+            if let Some(rule) = &entry.rule {
+                println!("              {}", format!("rule: {}", rule).dimmed());
+            }
+        }
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    let line = text.lines().next().unwrap_or("");
+    if line.chars().count() > max {
+        format!("{}...", line.chars().take(max).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
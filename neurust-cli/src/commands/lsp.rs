@@ -0,0 +1,731 @@
+use crate::api::client::{ApiClient, PlanRequest};
+use crate::commands::audit::{self, Severity};
+use crate::commands::serve::{RpcError, INVALID_PARAMS, METHOD_NOT_FOUND, PARSE_ERROR};
+use crate::utils::anchor::{self, Level};
+use crate::utils::ci;
+use crate::utils::config::{LspConfig, NeurustConfig};
+use crate::utils::events::{self, ChannelSink, Event, OutputStream};
+use crate::utils::patch;
+use crate::utils::sandbox::Sandbox;
+use anyhow::{anyhow, bail, Result};
+use colored::*;
+use regex::Regex;
+use serde_json::{json, Value};
+use similar::{DiffOp, TextDiff};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+// --- ⚓ LANGUAGE SERVER (`neurust lsp`) ---
+// LSP over stdin/stdout (`Content-Length` Framing)။ Editor မရွေး (Neovim / Helix / Zed / VS Code ...) သုံးလို့ရတယ်။
+// - Diagnostics: Anchor Checks (Local, Edit တိုင်း) + Auditor Brain (`[lsp].audit_on_save` / `neurust.audit` Command)
+// - Code Actions: "Explain this error" / "Fix with Neurust" / "Add checked arithmetic" / "Generate tests for this function"
+//   `/api/agent/plan` ကို ခေါ်ပြီး Plan (create_file / apply_patch) ကို `WorkspaceEdit` အဖြစ် ပြန်ပေးမယ်။
+//   Credit ကုန်တာမို့ Action စာရင်းပြချိန်မှာ မခေါ်ဘဲ `codeAction/resolve` (မရရင် `workspace/applyEdit`) မှာမှ ခေါ်မယ်။
+
+const REQUEST_FAILED: i64 = -32803;
+
+/// Code Action `data.action`
+const EXPLAIN: &str = "explain";
+const FIX: &str = "fix";
+const CHECKED_ARITHMETIC: &str = "checked_arithmetic";
+const TESTS: &str = "tests";
+
+/// `DiagnosticSeverity` / `MessageType`
+const ERROR: u8 = 1;
+const WARNING: u8 = 2;
+const INFO: u8 = 3;
+
+/// `initialize` မှာ Client ပြောတဲ့ Capabilities
+#[derive(Debug, Clone, Copy, Default)]
+struct ClientCaps {
+    /// `codeAction/resolve` နဲ့ `edit` ကို နောက်မှ ဖြည့်လို့ရ
+    resolve_edit: bool,
+    /// `documentChanges` + `create` Resource Operation (File အသစ်)
+    create_files: bool,
+}
+
+struct Setup {
+    config: LspConfig,
+    caps: ClientCaps,
+    client: ApiClient,
+}
+
+struct Lsp {
+    /// မူလ stdout (Protocol Messages ပဲ ရေးမယ်)
+    out: Mutex<Box<dyn Write + Send>>,
+    /// URI -> Editor ထဲက Text (Full Sync)
+    documents: Mutex<HashMap<String, String>>,
+    /// URI -> နောက်ဆုံး Audit ရဲ့ Diagnostics
+    audits: Mutex<HashMap<String, Vec<Value>>>,
+    setup: OnceLock<Setup>,
+    next_id: AtomicU64,
+}
+
+impl Lsp {
+    fn send(&self, message: Value) {
+        let body = message.to_string();
+        if let Ok(mut out) = self.out.lock() {
+            let _ = write!(out, "Content-Length: {}\r\n\r\n{}", body.len(), body);
+            let _ = out.flush();
+        }
+    }
+
+    fn notify(&self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn respond(&self, id: Value, result: Result<Value, RpcError>) {
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(e) => self.send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            })),
+        }
+    }
+
+    /// Server -> Client Request (Response ကို မစောင့်ဘူး)
+    fn request(&self, method: &str, params: Value) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.send(json!({ "jsonrpc": "2.0", "id": format!("neurust-{}", id), "method": method, "params": params }));
+    }
+
+    fn show_message(&self, kind: u8, message: &str) {
+        self.notify("window/showMessage", json!({ "type": kind, "message": message }));
+    }
+
+    fn setup(&self) -> &Setup {
+        self.setup.get_or_init(|| Setup {
+            config: LspConfig::default(),
+            caps: ClientCaps::default(),
+            client: ApiClient::new("http://127.0.0.1:8000".to_string()),
+        })
+    }
+
+    /// Editor ထဲက Text (မဖွင့်ထားရင် Disk)
+    fn text(&self, uri: &str) -> Option<String> {
+        let open = self.documents.lock().ok().and_then(|docs| docs.get(uri).cloned());
+        open.or_else(|| uri_to_path(uri).ok().and_then(|path| std::fs::read_to_string(path).ok()))
+    }
+
+    /// Path နဲ့ ဖွင့်ထားတဲ့ Document (Client ရဲ့ URI Encoding အတိုင်း ပြန်သုံးဖို့)
+    fn open_uri(&self, path: &Path) -> Option<String> {
+        let docs = self.documents.lock().ok()?;
+        docs.keys().find(|uri| uri_to_path(uri).is_ok_and(|p| p == path)).cloned()
+    }
+
+    // --- 🩺 DIAGNOSTICS ---
+
+    fn publish(&self, uri: &str) {
+        let text = self.documents.lock().ok().and_then(|docs| docs.get(uri).cloned());
+        let mut diagnostics = Vec::new();
+        if let Some(text) = text.filter(|t| self.setup().config.anchor_checks && anchor::is_anchor_source(t)) {
+            let lines: Vec<&str> = text.lines().collect();
+            for finding in anchor::check(&text) {
+                let line = lines.get(finding.line).copied().unwrap_or("");
+                diagnostics.push(json!({
+                    "range": {
+                        "start": { "line": finding.line, "character": utf16_col(line, finding.start) },
+                        "end": { "line": finding.line, "character": utf16_col(line, finding.end) },
+                    },
+                    "severity": if finding.level == Level::Error { ERROR } else { WARNING },
+                    "code": finding.code,
+                    "source": "neurust-anchor",
+                    "message": finding.message,
+                }));
+            }
+        }
+        if let Some(audit) = self.audits.lock().ok().and_then(|audits| audits.get(uri).cloned()) {
+            diagnostics.extend(audit);
+        }
+        self.notify("textDocument/publishDiagnostics", json!({ "uri": uri, "diagnostics": diagnostics }));
+    }
+
+    /// Auditor Brain (`neurust audit` Pipeline) -> Findings ကို Line တွေပေါ် တင်မယ်
+    async fn audit(&self, uri: &str) -> Result<usize> {
+        let path = uri_to_path(uri)?;
+        let report = audit::report(&path.to_string_lossy(), &self.setup().client).await?;
+        let text = self.text(uri).unwrap_or_default();
+        let lines: Vec<&str> = text.lines().collect();
+
+        let diagnostics: Vec<Value> = audit::findings(&report)
+            .into_iter()
+            .map(|finding| {
+                let line = locate(&finding, &lines);
+                let end = lines.get(line).map(|l| utf16_col(l, l.len())).unwrap_or(0);
+                json!({
+                    "range": { "start": { "line": line, "character": 0 }, "end": { "line": line, "character": end } },
+                    "severity": match finding.severity {
+                        Severity::Critical => ERROR,
+                        Severity::Warning => WARNING,
+                        Severity::Suggestion => INFO,
+                    },
+                    "source": "neurust-audit",
+                    "message": finding.message,
+                })
+            })
+            .collect();
+
+        let count = diagnostics.len();
+        if let Ok(mut audits) = self.audits.lock() {
+            audits.insert(uri.to_string(), diagnostics);
+        }
+        self.publish(uri);
+        Ok(count)
+    }
+
+    // --- 🛠️ CODE ACTIONS ---
+
+    fn code_actions(&self, params: &Value) -> Result<Value, RpcError> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing textDocument.uri"))?;
+        let line = params["range"]["start"]["line"].as_u64().unwrap_or(0) as usize;
+        let text = self.text(uri).unwrap_or_default();
+        let mut actions = Vec::new();
+
+        for diagnostic in params["context"]["diagnostics"].as_array().into_iter().flatten() {
+            let data = json!({
+                "uri": uri,
+                "line": diagnostic["range"]["start"]["line"],
+                "message": diagnostic["message"],
+            });
+            actions.push(self.action("Explain this error", "quickfix", EXPLAIN, data.clone(), diagnostic));
+            actions.push(self.action("Fix with Neurust", "quickfix", FIX, data, diagnostic));
+        }
+
+        if let Some((function, start, end)) = enclosing_fn(&text, line) {
+            let data = json!({ "uri": uri, "line": start, "function": function });
+            let unchecked = anchor::check(&text)
+                .iter()
+                .any(|f| f.code == "unchecked_arithmetic" && (start..=end).contains(&f.line));
+            if unchecked {
+                actions.push(self.action("Add checked arithmetic", "refactor.rewrite", CHECKED_ARITHMETIC, data.clone(), &Value::Null));
+            }
+            actions.push(self.action("Generate tests for this function", "refactor", TESTS, data, &Value::Null));
+        }
+
+        Ok(Value::Array(actions))
+    }
+
+    /// AI ကို မခေါ်သေးဘဲ Action ပဲ ပြမယ် (Explain = Command၊ ကျန်တာ = Resolve / applyEdit Command)
+    fn action(&self, title: &str, kind: &str, action: &str, mut data: Value, diagnostic: &Value) -> Value {
+        data["action"] = json!(action);
+        let mut code_action = json!({ "title": title, "kind": kind });
+        if !diagnostic.is_null() {
+            code_action["diagnostics"] = json!([diagnostic]);
+        }
+        if action == EXPLAIN {
+            code_action["command"] = json!({ "title": title, "command": "neurust.explain", "arguments": [data] });
+        } else if self.setup().caps.resolve_edit {
+            code_action["data"] = data;
+        } else {
+            code_action["command"] = json!({ "title": title, "command": "neurust.applyAction", "arguments": [data] });
+        }
+        code_action
+    }
+
+    /// Action -> `/api/agent/plan` Response
+    async fn agent_plan(&self, data: &Value) -> Result<Value> {
+        let uri = data["uri"].as_str().ok_or_else(|| anyhow!("Code action has no uri"))?;
+        let path = uri_to_path(uri)?;
+        let rel = relative(&path);
+        let text = self.text(uri).ok_or_else(|| anyhow!("Cannot read {}", rel))?;
+        let line = data["line"].as_u64().unwrap_or(0) + 1;
+        let message = data["message"].as_str().unwrap_or("");
+        let function = data["function"].as_str().unwrap_or("");
+
+        let prompt = match data["action"].as_str().unwrap_or("") {
+            EXPLAIN => format!(
+                "Explain this diagnostic in {} (line {}): {}\nWhat causes it and how do I fix it? \
+                 Reply with an EMPTY plan and put the explanation in `message`.",
+                rel, line, message
+            ),
+            FIX => format!(
+                "Fix this problem in {} (line {}): {}\nChange only what is needed. Reply with an apply_patch plan.",
+                rel, line, message
+            ),
+            CHECKED_ARITHMETIC => format!(
+                "In {}, rewrite function `{}` to use checked arithmetic (checked_add / checked_sub / checked_mul / \
+                 checked_div returning a proper error) instead of unchecked +, -, * and / on integers. \
+                 Do not change anything else. Reply with an apply_patch plan.",
+                rel, function
+            ),
+            TESTS => format!(
+                "Write unit tests for function `{}` in {}. Put them in the file's `#[cfg(test)] mod tests` \
+                 (add it at the end of the file if missing). Reply with an apply_patch plan.",
+                function, rel
+            ),
+            other => bail!("Unknown code action: {}", other),
+        };
+
+        let request = PlanRequest {
+            context: Some(format!("--- FILE: {} ---\n{}", rel, text)),
+            ..PlanRequest::new(&prompt)
+        };
+        self.setup().client.fetch_plan_with(&request).await
+    }
+
+    /// Plan (create_file / apply_patch) -> `WorkspaceEdit` (Line Diff အလိုက် TextEdits)
+    fn workspace_edit(&self, response: &Value) -> Result<Value> {
+        // (URI, မူလ Content (None = File အသစ်), Content အသစ်)
+        let mut files: Vec<(String, Option<String>, String)> = Vec::new();
+        // Project အပြင် / `.git` / Keypairs / `.neurust/config.toml` ကို ရေးမယ့် Plan ဆိုရင် တစ်ခုလုံး ငြင်းမယ်
+        let sandbox = Sandbox::new(&NeurustConfig::load().sandbox)?;
+
+        for action in response["plan"].as_array().into_iter().flatten() {
+            let kind = action["action"].as_str().unwrap_or("");
+            if kind != "create_file" && kind != "apply_patch" {
+                continue;
+            }
+            let Some(path) = action["path"].as_str() else { continue };
+            let relative = sandbox
+                .check_write(path)
+                .map_err(|e| anyhow!("The agent tried to write outside the allowed files: {}", e))?;
+            let full = std::env::current_dir()?.join(&relative);
+            let uri = self.open_uri(&full).unwrap_or_else(|| path_to_uri(&full));
+
+            let index = match files.iter().position(|(u, _, _)| *u == uri) {
+                Some(index) => index,
+                None => {
+                    let original = self.text(&uri);
+                    files.push((uri.clone(), original.clone(), original.unwrap_or_default()));
+                    files.len() - 1
+                }
+            };
+            let entry = &mut files[index];
+            entry.2 = if kind == "create_file" {
+                action["content"].as_str().unwrap_or("").to_string()
+            } else if entry.1.is_none() && entry.2.is_empty() {
+                bail!("The agent patched {}, which does not exist", path);
+            } else {
+                patch::apply_action(&entry.2, action).map_err(|e| anyhow!("Cannot apply the patch to {}: {}", path, e))?
+            };
+        }
+
+        files.retain(|(_, old, new)| old.as_deref() != Some(new.as_str()));
+        if files.is_empty() {
+            let message = response["message"].as_str().unwrap_or("").trim();
+            bail!("The agent returned no file changes{}", if message.is_empty() { String::new() } else { format!(": {}", message) });
+        }
+
+        let creates = files.iter().any(|(_, old, _)| old.is_none());
+        if creates && self.setup().caps.create_files {
+            let mut changes = Vec::new();
+            for (uri, old, new) in &files {
+                if old.is_none() {
+                    changes.push(json!({ "kind": "create", "uri": uri, "options": { "ignoreIfExists": true } }));
+                }
+                changes.push(json!({
+                    "textDocument": { "uri": uri, "version": null },
+                    "edits": text_edits(old.as_deref().unwrap_or(""), new),
+                }));
+            }
+            return Ok(json!({ "documentChanges": changes }));
+        }
+
+        let skipped: Vec<&str> = files.iter().filter(|(_, old, _)| old.is_none()).map(|(uri, _, _)| uri.as_str()).collect();
+        if !skipped.is_empty() {
+            if skipped.len() == files.len() {
+                bail!("The agent wants to create new files, but this editor cannot create files from a code action");
+            }
+            self.show_message(WARNING, &format!("Neurust: skipped new file(s) the editor cannot create: {}", skipped.join(", ")));
+        }
+        let changes: serde_json::Map<String, Value> = files
+            .iter()
+            .filter_map(|(uri, old, new)| old.as_ref().map(|old| (uri.clone(), json!(text_edits(old, new)))))
+            .collect();
+        Ok(json!({ "changes": changes }))
+    }
+
+    /// AI ခေါ်ရတဲ့ Requests (Background Task)
+    async fn handle_slow(&self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        let failed = |e: anyhow::Error| RpcError::new(REQUEST_FAILED, e.to_string());
+        match method {
+            "codeAction/resolve" => {
+                let response = self.agent_plan(&params["data"]).await.map_err(failed)?;
+                let mut action = params.clone();
+                action["edit"] = self.workspace_edit(&response).map_err(failed)?;
+                Ok(action)
+            }
+            "workspace/executeCommand" => {
+                let argument = &params["arguments"][0];
+                match params["command"].as_str().unwrap_or("") {
+                    "neurust.applyAction" => {
+                        let result = match self.agent_plan(argument).await {
+                            Ok(response) => self.workspace_edit(&response),
+                            Err(e) => Err(e),
+                        };
+                        match result {
+                            Ok(edit) => {
+                                self.request("workspace/applyEdit", json!({ "label": "Neurust", "edit": edit }));
+                                Ok(Value::Null)
+                            }
+                            Err(e) => {
+                                self.show_message(ERROR, &format!("Neurust: {}", e));
+                                Err(failed(e))
+                            }
+                        }
+                    }
+                    "neurust.explain" => match self.agent_plan(argument).await {
+                        Ok(response) => {
+                            let message = response["message"].as_str().unwrap_or("").trim();
+                            let text = if message.is_empty() { "Neurust had nothing to add." } else { message };
+                            self.show_message(INFO, text);
+                            Ok(Value::Null)
+                        }
+                        Err(e) => {
+                            self.show_message(ERROR, &format!("Neurust: {}", e));
+                            Err(failed(e))
+                        }
+                    },
+                    "neurust.audit" => {
+                        let uri = argument.as_str().or(argument["uri"].as_str()).ok_or_else(|| {
+                            RpcError::new(INVALID_PARAMS, "neurust.audit needs a document URI")
+                        })?;
+                        let count = self.audit(uri).await.map_err(failed)?;
+                        self.show_message(INFO, &format!("Neurust audit: {} finding(s)", count));
+                        Ok(Value::Null)
+                    }
+                    other => Err(RpcError::new(INVALID_PARAMS, format!("Unknown command: {}", other))),
+                }
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        }
+    }
+}
+
+/// `neurust lsp`
+pub async fn execute() -> Result<()> {
+    // println! တွေ Protocol ထဲ မရောအောင် fd 1 ကို stderr ဆီ ပြောင်းမယ်
+    let out: Box<dyn Write + Send> = match ci::detach_stdout() {
+        Some(file) => Box::new(file),
+        None => Box::new(std::io::stdout()),
+    };
+    let lsp = Arc::new(Lsp {
+        out: Mutex::new(out),
+        documents: Mutex::new(HashMap::new()),
+        audits: Mutex::new(HashMap::new()),
+        setup: OnceLock::new(),
+        next_id: AtomicU64::new(1),
+    });
+    eprintln!("{} Neurust language server listening on stdio", "⚓".cyan());
+
+    // Audit / Anchor Commands ရဲ့ Output ကို Editor ရဲ့ Output Panel (`window/logMessage`) ဆီ ပို့မယ်
+    let (sink, mut events_rx) = ChannelSink::new();
+    let sink = events::subscribe(sink);
+    let forward = lsp.clone();
+    tokio::spawn(async move {
+        while let Some(event) = events_rx.recv().await {
+            if let Some((kind, message)) = log_message(&event) {
+                forward.notify("window/logMessage", json!({ "type": kind, "message": message }));
+            }
+        }
+    });
+
+    let mut reader = BufReader::new(tokio::io::stdin());
+    let mut shutdown = false;
+    while let Some(body) = read_message(&mut reader).await? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                lsp.respond(Value::Null, Err(RpcError::new(PARSE_ERROR, format!("Parse error: {}", e))));
+                continue;
+            }
+        };
+        // Client ရဲ့ Response (`workspace/applyEdit`) - စောင့်မနေဘူး
+        let Some(method) = message["method"].as_str().map(str::to_string) else { continue };
+        let id = message.get("id").filter(|id| !id.is_null()).cloned();
+        let params = message["params"].clone();
+
+        match method.as_str() {
+            "exit" => break,
+            "codeAction/resolve" | "workspace/executeCommand" => {
+                let lsp = lsp.clone();
+                tokio::spawn(async move {
+                    let result = lsp.handle_slow(&method, &params).await;
+                    if let Some(id) = id {
+                        lsp.respond(id, result);
+                    }
+                });
+            }
+            _ => {
+                if method == "shutdown" {
+                    shutdown = true;
+                }
+                let result = handle(&lsp, &method, &params);
+                if let Some(id) = id {
+                    lsp.respond(id, result);
+                }
+            }
+        }
+    }
+
+    events::unsubscribe(sink);
+    if !shutdown {
+        bail!("The client exited without a shutdown request");
+    }
+    // Audit တွေမှာ ငြင်းခဲ့တဲ့ Prompt တွေရဲ့ Exit Code က Server Exit ထိ မပါအောင် (LSP: Shutdown ပြီးရင် 0)
+    ci::reset();
+    Ok(())
+}
+
+fn handle(lsp: &Arc<Lsp>, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(initialize(lsp, params)),
+        "shutdown" => Ok(Value::Null),
+        "textDocument/didOpen" => {
+            let doc = &params["textDocument"];
+            if let (Some(uri), Some(text)) = (doc["uri"].as_str(), doc["text"].as_str()) {
+                if let Ok(mut docs) = lsp.documents.lock() {
+                    docs.insert(uri.to_string(), text.to_string());
+                }
+                lsp.publish(uri);
+            }
+            Ok(Value::Null)
+        }
+        "textDocument/didChange" => {
+            // Full Sync: နောက်ဆုံး Change = Document တစ်ခုလုံး
+            let uri = params["textDocument"]["uri"].as_str();
+            let text = params["contentChanges"].as_array().and_then(|c| c.last()).and_then(|c| c["text"].as_str());
+            if let (Some(uri), Some(text)) = (uri, text) {
+                if let Ok(mut docs) = lsp.documents.lock() {
+                    docs.insert(uri.to_string(), text.to_string());
+                }
+                lsp.publish(uri);
+            }
+            Ok(Value::Null)
+        }
+        "textDocument/didSave" => {
+            if let Some(uri) = params["textDocument"]["uri"].as_str().filter(|_| lsp.setup().config.audit_on_save) {
+                let (lsp, uri) = (lsp.clone(), uri.to_string());
+                tokio::spawn(async move {
+                    if let Err(e) = lsp.audit(&uri).await {
+                        lsp.show_message(WARNING, &format!("Neurust audit failed: {}", e));
+                    }
+                });
+            }
+            Ok(Value::Null)
+        }
+        "textDocument/didClose" => {
+            if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                lsp.documents.lock().ok().and_then(|mut docs| docs.remove(uri));
+                lsp.audits.lock().ok().and_then(|mut audits| audits.remove(uri));
+                lsp.notify("textDocument/publishDiagnostics", json!({ "uri": uri, "diagnostics": [] }));
+            }
+            Ok(Value::Null)
+        }
+        "textDocument/codeAction" => lsp.code_actions(params),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    }
+}
+
+/// Workspace Root ဆီ ရွှေ့ပြီး (`.neurust/config.toml` / Rules / Memory) Config နဲ့ Client Capabilities ကို မှတ်မယ်
+fn initialize(lsp: &Arc<Lsp>, params: &Value) -> Value {
+    let root = params["rootUri"]
+        .as_str()
+        .and_then(|uri| uri_to_path(uri).ok())
+        .or_else(|| params["rootPath"].as_str().map(PathBuf::from));
+    if let Some(root) = root {
+        if let Err(e) = std::env::set_current_dir(&root) {
+            eprintln!("{} Cannot enter workspace {}: {}", "⚠️".yellow(), root.display(), e);
+        }
+    }
+
+    let config = NeurustConfig::load().lsp;
+    let model = params["initializationOptions"]["model"].as_str().map(str::to_string).or_else(|| config.model.clone());
+    let caps = &params["capabilities"];
+    let resolve_props = &caps["textDocument"]["codeAction"]["resolveSupport"]["properties"];
+    let workspace_edit = &caps["workspace"]["workspaceEdit"];
+    let client_caps = ClientCaps {
+        resolve_edit: resolve_props.as_array().is_some_and(|p| p.iter().any(|v| v == "edit")),
+        create_files: workspace_edit["documentChanges"].as_bool().unwrap_or(false)
+            && workspace_edit["resourceOperations"].as_array().is_some_and(|ops| ops.iter().any(|v| v == "create")),
+    };
+    let _ = lsp.setup.set(Setup {
+        config,
+        caps: client_caps,
+        client: ApiClient::new("http://127.0.0.1:8000".to_string()).with_model(model),
+    });
+
+    json!({
+        "capabilities": {
+            "textDocumentSync": { "openClose": true, "change": 1, "save": { "includeText": false } },
+            "codeActionProvider": {
+                "codeActionKinds": ["quickfix", "refactor", "refactor.rewrite"],
+                "resolveProvider": true,
+            },
+            "executeCommandProvider": { "commands": ["neurust.applyAction", "neurust.explain", "neurust.audit"] },
+        },
+        "serverInfo": { "name": "neurust", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/// Command Events -> (`MessageType`, Text): 2 = Warning, 4 = Log
+fn log_message(event: &Event) -> Option<(u8, String)> {
+    match event {
+        Event::CommandStarted { command, .. } => Some((4, format!("$ {}", command))),
+        Event::CommandOutput { stream: OutputStream::Notice, line, .. } => Some((2, line.clone())),
+        Event::CommandOutput { line, .. } => Some((4, line.clone())),
+        Event::CommandFinished { command, success: true, duration_secs, .. } => {
+            Some((4, format!("✓ {} ({:.1}s)", command, duration_secs)))
+        }
+        Event::CommandFinished { command, exit_code, blocked, stopped, .. } => {
+            let reason = blocked
+                .clone()
+                .or_else(|| stopped.clone())
+                .unwrap_or_else(|| exit_code.map_or("no exit code".to_string(), |code| format!("exit code {}", code)));
+            Some((2, format!("✗ {} ({})", command, reason)))
+        }
+        _ => None,
+    }
+}
+
+/// `Content-Length: N\r\n\r\n{json}` (Stream ပြီးရင် None)
+async fn read_message(reader: &mut BufReader<tokio::io::Stdin>) -> Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length.ok_or_else(|| anyhow!("LSP message without Content-Length"))?;
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(body))
+}
+
+/// Cursor ပါတဲ့ `fn` -> (Name, Start Line, End Line)
+fn enclosing_fn(text: &str, line: usize) -> Option<(String, usize, usize)> {
+    static SIGNATURE: OnceLock<Regex> = OnceLock::new();
+    let signature = SIGNATURE.get_or_init(|| {
+        Regex::new(r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s+(\w+)"#)
+            .unwrap()
+    });
+    let lines: Vec<&str> = text.lines().collect();
+    let start = (0..=line.min(lines.len().checked_sub(1)?)).rev().find(|&i| signature.is_match(lines[i]))?;
+    let name = signature.captures(lines[start])?[1].to_string();
+
+    let mut depth = 0i32;
+    let mut opened = false;
+    for (index, text) in lines.iter().enumerate().skip(start) {
+        for ch in text.chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if opened && depth <= 0 {
+            return (index >= line).then_some((name, start, index));
+        }
+        // Body မပါတဲ့ Trait Method
+        if !opened && text.trim_end().ends_with(';') {
+            return None;
+        }
+    }
+    None
+}
+
+/// Audit Finding ကို ဘယ် Line ပေါ် တင်မလဲ: Report က Line Number -> `code` ထဲက Identifier ပါတဲ့ ပထမ Line -> Line 0
+fn locate(finding: &audit::Finding, lines: &[&str]) -> usize {
+    if let Some(line) = finding.line.filter(|l| *l >= 1 && *l <= lines.len()) {
+        return line - 1;
+    }
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    let code = CODE.get_or_init(|| Regex::new(r"`([^`]{3,})`").unwrap());
+    let line = code
+        .captures_iter(&finding.message)
+        .find_map(|c| lines.iter().position(|l| l.contains(&c[1])));
+    line.unwrap_or(0)
+}
+
+/// Line Diff -> LSP TextEdits (Line တစ်ခုလုံးလိုက် အစားထိုး)
+fn text_edits(old: &str, new: &str) -> Vec<Value> {
+    let diff = TextDiff::from_lines(old, new);
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    diff.ops()
+        .iter()
+        .filter(|op| !matches!(op, DiffOp::Equal { .. }))
+        .map(|op| {
+            let (old_range, new_range) = (op.old_range(), op.new_range());
+            json!({
+                "range": {
+                    "start": { "line": old_range.start, "character": 0 },
+                    "end": { "line": old_range.end, "character": 0 },
+                },
+                "newText": new_lines[new_range].concat(),
+            })
+        })
+        .collect()
+}
+
+/// Byte Offset -> UTF-16 Column (LSP Default Position Encoding)
+fn utf16_col(line: &str, byte: usize) -> usize {
+    let mut byte = byte.min(line.len());
+    while !line.is_char_boundary(byte) {
+        byte -= 1;
+    }
+    line[..byte].encode_utf16().count()
+}
+
+fn relative(path: &Path) -> String {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    path.strip_prefix(&cwd).unwrap_or(path).to_string_lossy().to_string()
+}
+
+/// `file:///a%20b/c.rs` -> `/a b/c.rs`
+fn uri_to_path(uri: &str) -> Result<PathBuf> {
+    let encoded = uri.strip_prefix("file://").ok_or_else(|| anyhow!("Only file:// URIs are supported: {}", uri))?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%').then(|| encoded.get(i + 1..i + 3)).flatten();
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(decoded)?;
+    // Windows: `file:///C:/x` -> `C:/x`
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    };
+    Ok(PathBuf::from(path))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from(if path.starts_with('/') { "file://" } else { "file:///" });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
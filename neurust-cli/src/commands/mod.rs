@@ -1,6 +1,15 @@
+pub mod apply;
 pub mod ask;
 pub mod audit;
 pub mod auth;
+pub mod completions;
 pub mod create;
+pub mod fix;
+pub mod history;
 pub mod keygen;
+pub mod lsp;
+pub mod models;
+pub mod rules;
+pub mod serve;
 pub mod solana_cmd;
+pub mod test;
@@ -0,0 +1,37 @@
+use crate::api::client::ApiClient;
+use anyhow::Result;
+use colored::*;
+
+/// `neurust models` - Server ပေါ်က Model Catalog ကို စျေးနှုန်းနဲ့တကွ ပြမယ်
+pub async fn execute() -> Result<()> {
+    let client = ApiClient::new("http://127.0.0.1:8000".to_string());
+    let models = client.list_models().await?;
+
+    println!("{}", "🎛️  Available Models (USD per 1M tokens)".cyan().bold());
+    println!("{}", "------------------------------------------------".dimmed());
+
+    for model in &models {
+        let line = format!(
+            "{:<32} {:<10} in ${:<6.2} out ${:<6.2} {}",
+            model.id, model.tier, model.input_price_per_m, model.output_price_per_m, model.label
+        );
+        if model.available {
+            println!("  {} {}", "✅".green(), line);
+        } else {
+            println!("  {} {}", "🔒".yellow(), line.dimmed());
+        }
+    }
+
+    println!("{}", "------------------------------------------------".dimmed());
+    println!("{}", "💡 Usage: neurust ask --model <id> \"your task\"".dimmed());
+    Ok(())
+}
+
+/// Plan မှာ သုံးခွင့်ရှိတဲ့ Model ID တွေ (REPL Tab-completion + `neurust completions`) - Server မရရင် အလွတ်
+pub async fn available_ids() -> Vec<String> {
+    ApiClient::new("http://127.0.0.1:8000".to_string())
+        .list_models()
+        .await
+        .map(|models| models.into_iter().filter(|m| m.available).map(|m| m.id).collect())
+        .unwrap_or_default()
+}
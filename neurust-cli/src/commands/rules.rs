@@ -0,0 +1,39 @@
+use crate::utils::rules;
+use anyhow::Result;
+use clap::Subcommand;
+use colored::*;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Action {
+    /// Print the effective merged rules sent with every agent request
+    Show,
+}
+
+pub async fn execute(action: Action) -> Result<()> {
+    match action {
+        Action::Show => show(),
+    }
+}
+
+fn show() -> Result<()> {
+    let sources = rules::sources();
+
+    if sources.is_empty() {
+        println!("{} No rules found.", "📜".yellow());
+        println!("   Project: {}", rules::project_path().display().to_string().dimmed());
+        if let Some(global) = rules::global_path() {
+            println!("   Global:  {}", global.display().to_string().dimmed());
+        }
+        return Ok(());
+    }
+
+    for source in &sources {
+        println!("{} {} rules: {}", "📜".cyan(), source.label, source.path.display().to_string().dimmed());
+    }
+    println!("{}", "------------------------------------------------".dimmed());
+
+    if let Some(merged) = rules::load_merged() {
+        println!("{}", merged);
+    }
+    Ok(())
+}
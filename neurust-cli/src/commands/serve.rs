@@ -0,0 +1,419 @@
+use crate::api::client::ApiClient;
+use crate::commands::ask::{self, AskOptions};
+use crate::utils::ci::{self, Prompt, Responder};
+use crate::utils::events::{self, CommandSource, Event, EventSink};
+use crate::utils::{cmd, history, memory};
+use anyhow::{anyhow, bail, Result};
+use colored::*;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::Notify;
+
+// --- 🔌 EDITOR SERVER (`neurust serve --stdio`) ---
+// JSON-RPC 2.0 over stdin/stdout (Message တစ်ခု = JSON Line တစ်ကြောင်း)။ Human Output က stderr ဆီ သွားမယ်။
+// `neurust ask` နဲ့ Executor ကို ပြန်သုံးတာမို့ Editor က Terminal နဲ့ အတူတူ ရမယ်။
+//
+// Client -> Server Requests:
+//   initialize                                      -> { server, version, protocol }
+//   session/start   { model?, max_cost?, no_cache?, dry_run? } -> { session_id }
+//   session/prompt  { session_id, prompt }          -> { prompt_id } (Background မှာ Run မယ်)
+//   session/cancel  { session_id }                  -> { cancelled }
+//   session/close   { session_id }                  -> null
+//   memory/get                                      -> Project Memory (`.neurust/memory.json`)
+//   usage/get       { session_id? }                 -> Session Counters + Account Credits
+//   shutdown                                        -> null (ပြီးရင် Process ပိတ်မယ်)
+// Server -> Client Notifications:
+//   session/event    { session_id, prompt_id, event }  (Event Bus ရဲ့ Event တိုင်း)
+//   session/finished { session_id, prompt_id, exit_code, status, error }
+// Server -> Client Requests (Client က Response ပြန်ပေးရမယ်):
+//   session/confirm  { session_id, prompt_id, prompt, target } -> { approved }  (Diff / Command / ...)
+//   session/question { session_id, prompt_id, question }       -> { reply }     (null = ရပ်)
+//
+// Executor က Global State (cwd, Exit Code, Event Bus) သုံးတာမို့ Prompt တစ်ခုပဲ တစ်ပြိုင်နက် Run မယ်။
+
+pub(crate) const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+const UNKNOWN_SESSION: i64 = -32001;
+const BUSY: i64 = -32002;
+
+/// JSON-RPC Error (Request တစ်ခုရဲ့ `error`) - `neurust lsp` ကလည်း သုံးတယ်
+pub(crate) struct RpcError {
+    pub(crate) code: i64,
+    pub(crate) message: String,
+}
+
+impl RpcError {
+    pub(crate) fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+/// Session တစ်ခုရဲ့ Counters (`usage/get`)
+#[derive(Debug, Clone, Default, Serialize)]
+struct Usage {
+    prompts: u64,
+    plans: u64,
+    commands: u64,
+    failed_commands: u64,
+    files_written: u64,
+    decisions: u64,
+    last_exit_code: Option<i32>,
+}
+
+struct Session {
+    options: AskOptions,
+    usage: Usage,
+}
+
+/// Run နေတဲ့ Prompt
+#[derive(Clone)]
+struct Active {
+    session_id: String,
+    prompt_id: u64,
+}
+
+struct Server {
+    /// မူလ stdout (Protocol Messages ပဲ ရေးမယ်)
+    out: Mutex<Box<dyn Write + Send>>,
+    sessions: Mutex<HashMap<String, Session>>,
+    active: Mutex<Option<Active>>,
+    /// Server -> Client Request ID -> Response ကို စောင့်နေသူ
+    pending: Mutex<HashMap<u64, mpsc::Sender<Value>>>,
+    next_id: AtomicU64,
+    /// Cancel ပြီးနောက် Prompt တွေကို မမေးတော့ဘဲ "no" ဖြေမယ်
+    cancelled: AtomicBool,
+    cancel: Notify,
+}
+
+impl Server {
+    fn send(&self, message: Value) {
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", message);
+            let _ = out.flush();
+        }
+    }
+
+    fn notify(&self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn respond(&self, id: Value, result: Result<Value, RpcError>) {
+        match result {
+            Ok(result) => self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+            Err(e) => self.send(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": e.code, "message": e.message },
+            })),
+        }
+    }
+
+    fn active(&self) -> Option<Active> {
+        self.active.lock().ok().and_then(|a| a.clone())
+    }
+
+    /// Client ကို မေးပြီး Response ကို စောင့်မယ် (Executor Thread ကို Block လုပ်မယ်)
+    /// Cancel / Client ပိတ်သွားရင် Null
+    fn request(&self, method: &str, mut params: Value) -> Value {
+        let Some(active) = self.active() else { return Value::Null };
+        params["session_id"] = json!(active.session_id);
+        params["prompt_id"] = json!(active.prompt_id);
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel();
+        {
+            // `cancel_active` က `cancelled` ကို Set ပြီးမှ `pending` ကို ရှင်းတာမို့ Lock ထဲမှာ စစ်ရင် Cancel မလွတ်ဘူး
+            let Ok(mut pending) = self.pending.lock() else { return Value::Null };
+            if self.cancelled.load(Ordering::SeqCst) {
+                return Value::Null;
+            }
+            pending.insert(id, tx);
+        }
+        self.send(json!({ "jsonrpc": "2.0", "id": format!("neurust-{}", id), "method": method, "params": params }));
+        tokio::task::block_in_place(|| rx.recv()).unwrap_or(Value::Null)
+    }
+
+    /// Client ရဲ့ Response -> စောင့်နေတဲ့ `request` ဆီ
+    fn resolve(&self, message: &Value) {
+        let Some(id) = message["id"].as_str().and_then(|id| id.strip_prefix("neurust-")).and_then(|id| id.parse().ok())
+        else {
+            return;
+        };
+        let waiter = self.pending.lock().ok().and_then(|mut pending| pending.remove(&id));
+        if let Some(waiter) = waiter {
+            // Error Response = Reject
+            let _ = waiter.send(message["result"].clone());
+        }
+    }
+
+    /// စောင့်နေတဲ့ Request အားလုံးကို Reject လုပ်ပြီး Prompt Task ကို ရပ်ခိုင်းမယ်
+    fn cancel_active(&self) -> bool {
+        if self.active().is_none() {
+            return false;
+        }
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.clear();
+        }
+        self.cancel.notify_waiters();
+        true
+    }
+
+    fn with_session<T>(&self, session_id: &str, f: impl FnOnce(&mut Session) -> T) -> Result<T, RpcError> {
+        let mut sessions = self.sessions.lock().map_err(|_| RpcError::new(INVALID_REQUEST, "Session table poisoned"))?;
+        sessions
+            .get_mut(session_id)
+            .map(f)
+            .ok_or_else(|| RpcError::new(UNKNOWN_SESSION, format!("Unknown session: {}", session_id)))
+    }
+}
+
+/// Editor က Prompt / Question တွေကို ဖြေမယ်
+struct EditorResponder(Arc<Server>);
+
+impl Responder for EditorResponder {
+    fn confirm(&self, prompt: Prompt, target: &str) -> bool {
+        let result = self.0.request("session/confirm", json!({ "prompt": prompt.key(), "target": target }));
+        result["approved"].as_bool().or(result.as_bool()).unwrap_or(false)
+    }
+
+    fn reply(&self, question: &str) -> Option<String> {
+        let result = self.0.request("session/question", json!({ "question": question }));
+        result["reply"].as_str().or(result.as_str()).map(str::to_string)
+    }
+}
+
+/// Event Bus -> `session/event` Notifications (Confirm Request နဲ့ အစဉ်မလွဲအောင် တိုက်ရိုက် ရေးမယ်)
+struct RpcSink(Arc<Server>);
+
+impl EventSink for RpcSink {
+    fn handle(&self, event: &Event) {
+        let Some(active) = self.0.active() else { return };
+        let _ = self.0.with_session(&active.session_id, |session| {
+            let usage = &mut session.usage;
+            match event {
+                Event::PlanReceived { .. } => usage.plans += 1,
+                Event::CommandFinished { id: Some(_), source: CommandSource::Plan, success, .. } => {
+                    usage.commands += 1;
+                    if !success {
+                        usage.failed_commands += 1;
+                    }
+                }
+                Event::FileWritten { .. } => usage.files_written += 1,
+                Event::DecisionMade { .. } => usage.decisions += 1,
+                _ => {}
+            }
+        });
+        self.0.notify(
+            "session/event",
+            json!({ "session_id": active.session_id, "prompt_id": active.prompt_id, "event": event }),
+        );
+    }
+}
+
+/// `neurust serve --stdio`
+pub async fn execute(stdio: bool) -> Result<()> {
+    if !stdio {
+        bail!("Only the stdio transport is supported: run `neurust serve --stdio`");
+    }
+
+    // println! တွေ Protocol ထဲ မရောအောင် fd 1 ကို stderr ဆီ ပြောင်းမယ်
+    let out: Box<dyn Write + Send> = match ci::detach_stdout() {
+        Some(file) => Box::new(file),
+        None => Box::new(std::io::stdout()),
+    };
+    let server = Arc::new(Server {
+        out: Mutex::new(out),
+        sessions: Mutex::new(HashMap::new()),
+        active: Mutex::new(None),
+        pending: Mutex::new(HashMap::new()),
+        next_id: AtomicU64::new(1),
+        cancelled: AtomicBool::new(false),
+        cancel: Notify::new(),
+    });
+    ci::set_responder(EditorResponder(server.clone()));
+    let sink = events::subscribe(RpcSink(server.clone()));
+    eprintln!("{} Neurust JSON-RPC server listening on stdio", "🔌".cyan());
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                server.respond(Value::Null, Err(RpcError::new(PARSE_ERROR, format!("Parse error: {}", e))));
+                continue;
+            }
+        };
+
+        let Some(method) = message["method"].as_str() else {
+            // Server -> Client Request ရဲ့ Response
+            if message.get("result").is_some() || message.get("error").is_some() {
+                server.resolve(&message);
+            } else {
+                server.respond(message["id"].clone(), Err(RpcError::new(INVALID_REQUEST, "Missing method")));
+            }
+            continue;
+        };
+
+        let result = handle(&server, method, &message["params"]).await;
+        // ID မပါ = Notification (Response မပို့)
+        if let Some(id) = message.get("id").filter(|id| !id.is_null()) {
+            server.respond(id.clone(), result);
+        }
+        if method == "shutdown" || method == "exit" {
+            break;
+        }
+    }
+
+    server.cancel_active();
+    events::unsubscribe(sink);
+    Ok(())
+}
+
+async fn handle(server: &Arc<Server>, method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => Ok(json!({
+            "server": "neurust",
+            "version": env!("CARGO_PKG_VERSION"),
+            "protocol": 1,
+        })),
+        "session/start" => {
+            let options = AskOptions {
+                model: params["model"].as_str().map(str::to_string),
+                max_cost: params["max_cost"].as_i64(),
+                no_cache: params["no_cache"].as_bool().unwrap_or(false),
+                dry_run: params["dry_run"].as_bool().unwrap_or(false),
+                plan_out: None,
+            };
+            let session_id = format!("s{}", server.next_id.fetch_add(1, Ordering::SeqCst));
+            if let Ok(mut sessions) = server.sessions.lock() {
+                sessions.insert(session_id.clone(), Session { options, usage: Usage::default() });
+            }
+            Ok(json!({ "session_id": session_id }))
+        }
+        "session/prompt" => {
+            let session_id = session_param(params)?;
+            let prompt = params["prompt"]
+                .as_str()
+                .filter(|p| !p.trim().is_empty())
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing prompt"))?
+                .to_string();
+            start_prompt(server, session_id, prompt)
+        }
+        "session/cancel" => {
+            let session_id = session_param(params)?;
+            server.with_session(session_id, |_| ())?;
+            let running = server.active().is_some_and(|a| a.session_id == session_id);
+            Ok(json!({ "cancelled": running && server.cancel_active() }))
+        }
+        "session/close" => {
+            let session_id = session_param(params)?;
+            if server.active().is_some_and(|a| a.session_id == session_id) {
+                server.cancel_active();
+            }
+            server.sessions.lock().ok().and_then(|mut s| s.remove(session_id));
+            Ok(Value::Null)
+        }
+        "memory/get" => {
+            let mem = memory::ProjectMemory::load();
+            let mut value = serde_json::to_value(&mem).unwrap_or_default();
+            // Project Context က အရမ်းကြီးလို့ အရွယ်ပဲ ပြမယ်
+            if let Some(obj) = value.as_object_mut() {
+                obj.remove("project_context");
+                obj.insert("context_chars".to_string(), json!(mem.project_context.chars().count()));
+            }
+            Ok(value)
+        }
+        "usage/get" => {
+            let session = match params["session_id"].as_str() {
+                Some(id) => Some(server.with_session(id, |s| s.usage.clone())?),
+                None => None,
+            };
+            let client = ApiClient::new("http://127.0.0.1:8000".to_string());
+            let account = client.get_me().await;
+            Ok(json!({
+                "session": session,
+                "credits": account.as_ref().ok().map(|me| me["credits"].clone()),
+                "account_error": account.err().map(|e| e.to_string()),
+            }))
+        }
+        "shutdown" | "exit" => Ok(Value::Null),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    }
+}
+
+fn session_param(params: &Value) -> Result<&str, RpcError> {
+    params["session_id"].as_str().ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing session_id"))
+}
+
+/// `neurust ask` ကို Background Task အဖြစ် Run မယ် (ပြီးရင် `session/finished`)
+fn start_prompt(server: &Arc<Server>, session_id: &str, prompt: String) -> Result<Value, RpcError> {
+    let options = server.with_session(session_id, |session| session.options.clone())?;
+
+    let prompt_id = server.next_id.fetch_add(1, Ordering::SeqCst);
+    {
+        let mut active = server.active.lock().map_err(|_| RpcError::new(BUSY, "Server state poisoned"))?;
+        if let Some(running) = active.as_ref() {
+            return Err(RpcError::new(
+                BUSY,
+                format!("Prompt {} in session {} is still running", running.prompt_id, running.session_id),
+            ));
+        }
+        *active = Some(Active { session_id: session_id.to_string(), prompt_id });
+    }
+    server.cancelled.store(false, Ordering::SeqCst);
+    cmd::clear_stop();
+    let _ = server.with_session(session_id, |session| session.usage.prompts += 1);
+
+    let server = server.clone();
+    let session_id = session_id.to_string();
+    tokio::spawn(async move {
+        // Cancel = Run နေတဲ့ Command ကို ရပ်ပြီး Executor ကို နောက် Action မစခိုင်းဘူး
+        // Plan Execute နေတုန်း (Transaction ဖွင့်ထား) ဆိုရင် Commit + CommandFinished အထိ စောင့်မယ်၊ AI Request စောင့်နေတုန်းဆိုရင် ချက်ချင်း Drop
+        let execution = ask::execute(prompt, options);
+        tokio::pin!(execution);
+        let result = loop {
+            tokio::select! {
+                result = &mut execution => break result,
+                _ = server.cancel.notified() => {
+                    cmd::request_stop();
+                    if !history::in_transaction() {
+                        break Err(anyhow!("Cancelled by the client"));
+                    }
+                }
+            }
+        };
+        if let Err(e) = &result {
+            eprintln!("{} {}", "ERROR:".red().bold(), e);
+        }
+        let code = ci::finish(result.as_ref().err());
+        let exit = ci::Exit::from_code(code);
+        let _ = server.with_session(&session_id, |session| session.usage.last_exit_code = Some(code));
+
+        if let Ok(mut active) = server.active.lock() {
+            *active = None;
+        }
+        server.notify(
+            "session/finished",
+            json!({
+                "session_id": session_id,
+                "prompt_id": prompt_id,
+                "exit_code": code,
+                "status": exit.label(),
+                "error": result.err().map(|e| e.to_string()),
+            }),
+        );
+    });
+
+    Ok(json!({ "prompt_id": prompt_id }))
+}
@@ -1,5 +1,5 @@
 use crate::commands::keygen;
-use crate::utils::{cmd, fs};
+use crate::utils::{ci, cmd, fs};
 use anyhow::{anyhow, Context, Result};
 use clap::Subcommand;
 use colored::*;
@@ -49,11 +49,17 @@ async fn handle_deploy() -> Result<()> {
         vec!["Generate Stable Keypair (First Time)", "Cancel"]
     };
 
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("How do you want to handle the Program ID?")
-        .default(0)
-        .items(&selections)
-        .interact()?;
+    // Non-interactive = Default (ရှိပြီးသား Stable Key / ပထမဆုံး Keypair)
+    let selection = if ci::interactive() {
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How do you want to handle the Program ID?")
+            .default(0)
+            .items(&selections)
+            .interact()?
+    } else {
+        println!("{} Non-interactive: {}", "🤖".cyan(), selections[0]);
+        0
+    };
 
     match (key_exists, selection) {
         (true, 0) => println!("{} Using existing stable key...", "🔒".blue()),
@@ -72,10 +78,10 @@ async fn handle_deploy() -> Result<()> {
     sync_program_id(key_path)?;
 
     println!("{} Building Anchor project...", "🔨".blue());
-    cmd::execute("anchor", &["build"], None)?;
+    cmd::execute("anchor", &["build"], None).await?;
 
     println!("{} Deploying to Devnet...", "☁️".blue());
-    cmd::execute("anchor", &["deploy", "--provider.cluster", "devnet"], None)?;
+    cmd::execute("anchor", &["deploy", "--provider.cluster", "devnet"], None).await?;
 
     println!("{} Deployment Complete!", "🎉".green());
     Ok(())
@@ -110,11 +116,11 @@ async fn handle_airdrop(amount: u32) -> Result<()> {
     println!("{} Requesting {} SOL airdrop...", "💸".green(), amount);
 
     // ၁. Airdrop တောင်းခြင်း
-    cmd::execute("solana", &["airdrop", &amount.to_string()], None)?;
+    cmd::execute("solana", &["airdrop", &amount.to_string()], None).await?;
 
     // ၂. Result ထုတ်ပြခြင်း (Balance စစ်ခြင်း)
     println!("\n{} Checking new balance...", "💰".yellow());
-    cmd::execute("solana", &["balance"], None)?;
+    cmd::execute("solana", &["balance"], None).await?;
 
     Ok(())
 }
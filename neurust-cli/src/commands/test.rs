@@ -0,0 +1,235 @@
+use crate::api::client::{ApiClient, PlanRequest};
+use crate::utils::testrun::{self, Runner, TestCase, TestRun, TestStatus};
+use crate::utils::ci::{self, Exit, Prompt};
+use crate::utils::{cmd, context, events, executor, memory};
+use anyhow::Result;
+use colored::*;
+use dialoguer::{theme::ColorfulTheme, Confirm};
+use std::fs;
+
+/// Prompt ထဲ ထည့်မယ့် Failing Test အများဆုံး
+const MAX_FAILURES_IN_PROMPT: usize = 10;
+
+/// `neurust test` Options
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+    /// libtest Name Filter (`cargo test <filter>`)
+    pub filter: Option<String>,
+    /// Model to use for the fix plan (None = Server Smart Routing)
+    pub model: Option<String>,
+    /// Failures ကို မမေးဘဲ Agent ဆီ ပို့မယ်
+    pub fix: bool,
+}
+
+/// `neurust test [filter]`
+/// Test Run -> Summary -> (Confirm) Failures + Source ကို Agent ဆီ ပို့ -> Fail ဖြစ်ခဲ့တဲ့ Test တွေကိုပဲ ပြန် Run
+pub async fn execute(options: TestOptions) -> Result<()> {
+    let Some(runner) = Runner::detect() else {
+        println!("{} No Cargo.toml or Anchor.toml here. Nothing to test.", "❌".red());
+        return Ok(());
+    };
+
+    let filters: Vec<String> = options.filter.iter().cloned().collect();
+    if runner == Runner::Anchor && !filters.is_empty() {
+        println!("{} `anchor test` does not support filters; running the full suite.", "ℹ️".blue());
+    }
+
+    println!("{} Running {}...", "🧪".cyan(), if runner == Runner::Anchor { "anchor test" } else { "cargo test" });
+    let run = testrun::run(runner, &filters, false).await;
+    print_summary(&run);
+
+    if let Some(stopped) = run.outcome.stopped() {
+        println!("{} Test run {} after {:.1}s; nothing was sent to the agent.", "🛑".yellow(), stopped, run.outcome.duration.as_secs_f64());
+        return Ok(());
+    }
+
+    if run.build_failed() {
+        println!("{} Tests did not run (build or setup failed). Try `neurust fix` first.", "❌".red());
+        ci::fail(Exit::VerificationFailed);
+        let tail: Vec<&str> = run.outcome.stderr.lines().rev().take(15).collect();
+        for line in tail.iter().rev() {
+            println!("   {}", line.red());
+        }
+        return Ok(());
+    }
+
+    let failed: Vec<TestCase> = run.failed().into_iter().cloned().collect();
+    if failed.is_empty() {
+        return Ok(());
+    }
+
+    let send = options.fix
+        || ci::confirm(Prompt::SendTests, &format!("{} failing test(s)", failed.len()), || {
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Send {} failing test(s) to the agent for a fix plan?", failed.len()))
+                .default(true)
+                .interact()
+                .unwrap_or(false)
+        });
+    if !send {
+        ci::fail(Exit::VerificationFailed);
+        return Ok(());
+    }
+
+    // --- 🧠 Fix Plan ---
+    let client = ApiClient::new("http://127.0.0.1:8000".to_string()).with_model(options.model);
+    let mut mem = memory::ProjectMemory::load();
+    if mem.project_context.is_empty() {
+        mem.project_context = context::get_project_context();
+        let _ = mem.save();
+    }
+
+    let request = PlanRequest {
+        context: Some(format!(
+            "{}\n\n--- PROJECT MEMORY (PAST ACTIONS) ---\n{}",
+            mem.project_context, mem.summary
+        )),
+        ..PlanRequest::new(&format!(
+            "These tests are failing. Find the root cause and fix the code under test (only change a test if it is clearly wrong). \
+             Use `apply_patch` with minimal edits.\n\n{}",
+            failure_report(&failed)
+        ))
+    };
+
+    println!("{} Asking the agent to fix {} test(s)...", "🧠".yellow(), failed.len());
+    let response = match client.fetch_plan_with(&request).await {
+        Ok(response) => response,
+        Err(e) => {
+            println!("{} AI Connection Error: {}", "❌".red(), e);
+            ci::fail(Exit::VerificationFailed);
+            ci::fail_with(&e);
+            return Ok(());
+        }
+    };
+    if let Some(msg) = response["message"].as_str().filter(|m| !m.trim().is_empty()) {
+        println!("\n{} Neurust: {}", "🤖".green(), msg);
+    }
+    if response["plan"].as_array().is_none_or(|p| p.is_empty()) {
+        println!("{} The agent returned no fix plan.", "⚠️".yellow());
+        ci::fail(Exit::VerificationFailed);
+        return Ok(());
+    }
+    events::plan_received("neurust test", &response, false);
+    executor::execute_plan(&response["plan"], &client, &mut mem, "neurust test").await?;
+
+    // --- 🔁 Re-run only the failing tests ---
+    let mut names: Vec<String> = failed.iter().map(|c| c.name.clone()).collect();
+    names.sort();
+    names.dedup();
+    if runner == Runner::Anchor {
+        // `anchor test` မှာ Name Filter မရှိဘူး -> Suite အပြည့် Run ပြီး Fail ခဲ့တဲ့ Case တွေကိုပဲ တိုက်စစ်မယ်
+        println!(
+            "{} `anchor test` can't filter by name: re-running the full suite and checking the {} previously failing test(s)...",
+            "🔁".cyan(),
+            failed.len()
+        );
+    } else {
+        println!("{} Re-running {} previously failing test(s)...", "🔁".cyan(), failed.len());
+    }
+    let rerun = testrun::run(runner, &names, true).await;
+
+    let still_failing: Vec<&TestCase> = failed
+        .iter()
+        .filter(|case| !rerun.cases.iter().any(|c| c.same_test(case) && c.status == TestStatus::Passed))
+        .collect();
+    let fixed = failed.len() - still_failing.len();
+
+    println!("\n{} {}/{} previously failing test(s) now pass.", "📊".cyan(), fixed, failed.len());
+    for case in &still_failing {
+        println!("   {} {} {}", "❌".red(), case.name, format!("({})", case.binary).dimmed());
+    }
+    if !still_failing.is_empty() {
+        ci::fail(Exit::VerificationFailed);
+    }
+
+    mem.record_event(&format!("neurust test: fixed {}/{} failing test(s)", fixed, failed.len()));
+    let _ = mem.save();
+    Ok(())
+}
+
+fn print_summary(run: &TestRun) {
+    let (passed, failed, ignored) = (
+        run.count(TestStatus::Passed),
+        run.count(TestStatus::Failed),
+        run.count(TestStatus::Ignored),
+    );
+
+    println!(
+        "\n{} {} passed, {} failed, {} ignored ({:.1}s)",
+        "📊".cyan(),
+        passed.to_string().green(),
+        failed.to_string().red(),
+        ignored.to_string().dimmed(),
+        run.outcome.duration.as_secs_f64()
+    );
+
+    for case in run.failed() {
+        println!("   {} {} {}", "❌".red(), case.name.bold(), format!("({})", case.binary).dimmed());
+        if let Some(panic) = &case.panic {
+            if let Some((file, line)) = &panic.location {
+                println!("      at {}:{}", file, line);
+            }
+            match &panic.assertion {
+                Some((left, right)) => {
+                    println!("      {} {}", "left: ".red(), left);
+                    println!("      {} {}", "right:".green(), right);
+                }
+                None => {
+                    if let Some(first) = panic.message.lines().next() {
+                        println!("      {}", first.dimmed());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Failing Test တစ်ခုချင်းစီ: Panic / Assertion / Output + Test Source + Panic Location + Test က ခေါ်တဲ့ Functions
+fn failure_report(failed: &[TestCase]) -> String {
+    let mut out = String::new();
+
+    for case in failed.iter().take(MAX_FAILURES_IN_PROMPT) {
+        out.push_str(&format!("\n=== FAILING TEST: {} ({}) ===\n", case.name, case.binary));
+        if let Some(panic) = &case.panic {
+            out.push_str(&format!("Panic: {}\n", panic.message));
+            if let Some((left, right)) = &panic.assertion {
+                out.push_str(&format!("Assertion: left = {} | right = {}\n", left, right));
+            }
+        }
+        if !case.output.trim().is_empty() {
+            out.push_str(&format!("--- captured output ---\n{}\n", cmd::truncate_output(&case.output, 3000)));
+        }
+
+        let test_source = testrun::find_function_source(&case.name);
+        if let Some((file, source)) = &test_source {
+            out.push_str(&format!("--- test source ({}) ---\n{}\n", file, source));
+        }
+
+        if let Some((file, line)) = case.panic.as_ref().and_then(|p| p.location.clone()) {
+            if let Ok(content) = fs::read_to_string(&file) {
+                let lines: Vec<&str> = content.lines().collect();
+                let start = line.saturating_sub(11);
+                let end = (line + 10).min(lines.len());
+                out.push_str(&format!("--- panic location ({}:{}) ---\n", file, line));
+                for (i, text) in lines.iter().enumerate().take(end).skip(start) {
+                    out.push_str(&format!("{:>5} | {}\n", i + 1, text));
+                }
+            }
+        }
+
+        // Test က ခေါ်တဲ့ Functions (Code Under Test) - အများဆုံး ၅ ခု
+        if let Some((_, source)) = &test_source {
+            let short = case.name.rsplit("::").next().unwrap_or("");
+            for name in testrun::called_functions(source).iter().filter(|n| n.as_str() != short).take(5) {
+                if let Some((file, body)) = testrun::find_function_source(name) {
+                    out.push_str(&format!("--- code under test: {} ({}) ---\n{}\n", name, file, body));
+                }
+            }
+        }
+    }
+
+    if failed.len() > MAX_FAILURES_IN_PROMPT {
+        out.push_str(&format!("\n[... {} more failing tests omitted ...]\n", failed.len() - MAX_FAILURES_IN_PROMPT));
+    }
+    out
+}
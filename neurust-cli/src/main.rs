@@ -2,18 +2,40 @@ mod api;
 mod commands;
 mod utils;
 
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::*;
-use commands::{ask, audit, auth, create, solana_cmd};
+use commands::{apply, ask, audit, auth, completions, create, fix, history, lsp, models, rules, serve, solana_cmd, test};
+use utils::ci::{self, OutputFormat};
 use utils::repl; 
 
 #[derive(Parser)]
 #[command(name = "neurust")]
 #[command(about = "Neurust: AI-powered Rust & Solana Engineer", long_about = None)]
+#[command(after_help = "Exit codes:\n  \
+    0  success\n  \
+    1  error\n  \
+    2  invalid arguments\n  \
+    3  plan refused (declined prompt, command policy, cost guard, unanswered question, changed plan file)\n  \
+    4  verification failed (checks, tests or commands still failing)\n  \
+    5  authentication error\n  \
+    6  out of credits\n\n\
+If several apply, the highest code wins.")]
 struct Cli {
     /// Optional Subcommand (None = Interactive Mode)
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// Run commands inside a bubblewrap sandbox (Linux; see [sandbox] in .neurust/config.toml)
+    #[arg(long, global = true)]
+    sandbox: bool,
+
+    /// Never prompt: answer with the [auto] policy in .neurust/config.toml (for CI)
+    #[arg(short, long, global = true, visible_alias = "non-interactive")]
+    yes: bool,
+
+    /// Output format (json = JSON Lines events and a final result on stdout, logs on stderr)
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -25,7 +47,15 @@ enum Commands {
         r#type: String,
     },
     /// Audit code
-    Audit { path: String },
+    Audit {
+        path: String,
+        /// Model to use (see `neurust models`)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Ignore cached reports and force a fresh audit
+        #[arg(long)]
+        no_cache: bool,
+    },
     /// Login to network (Device Flow)
     Login,
     /// Solana DevOps Tools (Airdrop, Sync, Deploy)
@@ -37,6 +67,92 @@ enum Commands {
     Ask {
         /// Your prompt (e.g., "Give me 5 SOL", "Create a token")
         prompt: Vec<String>, 
+        /// Model to use for this task (see `neurust models`)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Refuse any single AI request estimated above this many credits
+        #[arg(long)]
+        max_cost: Option<i64>,
+        /// Ignore cached answers and force a fresh plan
+        #[arg(long)]
+        no_cache: bool,
+        /// Show the full plan (diffs, commands, URLs) without writing or running anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Save the plan with hashes of the files it touches (apply later with `neurust apply`)
+        #[arg(long, value_name = "FILE")]
+        plan_out: Option<String>,
+    },
+    /// Apply a plan saved with `neurust ask --plan-out` (refuses if its files changed since)
+    Apply {
+        /// Plan file (JSON)
+        file: String,
+        /// Replay the plan's edits on top of files that changed since it was saved
+        #[arg(long, conflicts_with = "force")]
+        rebase: bool,
+        /// Apply even if target files changed (may overwrite those changes)
+        #[arg(long)]
+        force: bool,
+        /// Model to use for auto-healing fix plans (see `neurust models`)
+        #[arg(short, long)]
+        model: Option<String>,
+    },
+    /// Fix build errors and lints: apply compiler suggestions locally, send the rest to AI
+    Fix {
+        /// Model to use for the AI pass (see `neurust models`)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Only apply local (machine-applicable) fixes; never call the AI
+        #[arg(long)]
+        no_ai: bool,
+        /// Skip `cargo clippy` and only fix `cargo check` diagnostics
+        #[arg(long)]
+        no_clippy: bool,
+    },
+    /// Run tests, summarize failures and optionally ask the agent to fix them
+    Test {
+        /// Only run tests whose name contains this filter (cargo test only)
+        filter: Option<String>,
+        /// Model to use for the fix plan (see `neurust models`)
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Send failing tests to the agent without asking
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Roll back the files changed by a plan run (default: the latest run)
+    Undo {
+        /// Run ID from `neurust history`
+        run_id: Option<String>,
+    },
+    /// List recorded plan runs, or show one run's files and commands
+    History {
+        /// Run ID to show in detail
+        run_id: Option<String>,
+    },
+    /// List AI models available on your plan (with prices)
+    Models,
+    /// Project rules sent with every agent request (.neurust/rules.md)
+    Rules {
+        #[command(subcommand)]
+        action: rules::Action,
+    },
+    /// Run as a JSON-RPC 2.0 agent server for editor integrations
+    Serve {
+        /// Speak JSON-RPC over stdin/stdout (one JSON message per line; logs go to stderr)
+        #[arg(long)]
+        stdio: bool,
+    },
+    /// Run the language server (Anchor checks, audit diagnostics, AI code actions) over stdio
+    Lsp {
+        /// Accepted for editors that pass it; stdio is the only transport
+        #[arg(long)]
+        stdio: bool,
+    },
+    /// Print a shell completion script (`--model` values come from the model catalog)
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
     },
 }
 
@@ -44,12 +160,29 @@ enum Commands {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
+    // 🤖 CI Mode (`--yes` / `--output json`) - Output မထုတ်ခင် ဆုံးဖြတ်ရမယ်
+    ci::init(cli.yes, cli.output);
+
+    // 🛑 Command Run နေတုန်း Ctrl-C = အဲ့ဒီ Command ကိုပဲ ရပ်မယ်
+    utils::cmd::install_interrupt_handler();
+
+    // 📦 Sandbox Mode (`--sandbox` / `[sandbox].exec`)
+    let sandbox = utils::config::NeurustConfig::load().sandbox;
+    if cli.sandbox || sandbox.exec {
+        utils::isolation::force_enable();
+        utils::isolation::print_banner(&sandbox);
+    }
+
     match cli.command {
         // 1. Argument ပါလာရင် Direct Command Run မယ်
         Some(cmd) => {
-            if let Err(e) = dispatch_command(cmd).await {
+            let result = dispatch_command(cmd).await;
+            if let Err(e) = &result {
                 eprintln!("{} {}", "ERROR:".red().bold(), e);
-                std::process::exit(1);
+            }
+            let code = ci::finish(result.as_ref().err());
+            if code != 0 {
+                std::process::exit(code);
             }
         }
         // 2. Argument မပါရင် Interactive REPL ထဲ ဝင်မယ်
@@ -70,8 +203,8 @@ async fn dispatch_command(cmd: Commands) -> anyhow::Result<()> {
         Commands::Create { name, r#type } => {
             create::execute(name, r#type).await?;
         }
-        Commands::Audit { path } => {
-            audit::execute(path).await?;
+        Commands::Audit { path, model, no_cache } => {
+            audit::execute(path, model, no_cache).await?;
         }
         // 🔥 FIX: auth::execute() အစား auth::login() ကို ပြောင်းခေါ်ထားပါတယ်
         Commands::Login => {
@@ -80,15 +213,45 @@ async fn dispatch_command(cmd: Commands) -> anyhow::Result<()> {
         Commands::Solana { action } => {
             solana_cmd::execute(action).await?;
         }
-        Commands::Ask { prompt } => {
+        Commands::Ask { prompt, model, max_cost, no_cache, dry_run, plan_out } => {
             // Vec<String> ကို Space ခံပြီး ပြန်ဆက်မယ်
             let prompt_text = prompt.join(" ");
             if !prompt_text.trim().is_empty() {
-                ask::execute(prompt_text).await?;
+                ask::execute(prompt_text, ask::AskOptions { model, max_cost, no_cache, dry_run, plan_out }).await?;
             } else {
                 println!("{}", "Please provide a prompt.".yellow());
             }
         }
+        Commands::Apply { file, rebase, force, model } => {
+            apply::execute(apply::ApplyOptions { file, rebase, force, model }).await?;
+        }
+        Commands::Fix { model, no_ai, no_clippy } => {
+            fix::execute(fix::FixOptions { model, no_ai, no_clippy }).await?;
+        }
+        Commands::Test { filter, model, fix } => {
+            test::execute(test::TestOptions { filter, model, fix }).await?;
+        }
+        Commands::Undo { run_id } => {
+            history::undo(run_id)?;
+        }
+        Commands::History { run_id } => {
+            history::list(run_id)?;
+        }
+        Commands::Models => {
+            models::execute().await?;
+        }
+        Commands::Rules { action } => {
+            rules::execute(action).await?;
+        }
+        Commands::Serve { stdio } => {
+            serve::execute(stdio).await?;
+        }
+        Commands::Lsp { stdio: _ } => {
+            lsp::execute().await?;
+        }
+        Commands::Completions { shell } => {
+            completions::execute(shell, Cli::command()).await?;
+        }
     }
     Ok(())
 }
\ No newline at end of file
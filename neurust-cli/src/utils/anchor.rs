@@ -0,0 +1,206 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+// --- ⚓ ANCHOR CHECKS (Local, AI မလို) ---
+// Anchor Program တွေမှာ အဖြစ်များတဲ့ အမှားတွေကို Source Text ပေါ်ကနေ ရှာမယ် (`neurust lsp` Diagnostics)။
+// Parser အပြည့်အစုံ မဟုတ်ဘဲ Line-based Heuristics ပဲ - False Positive နည်းအောင် ကျဉ်းကျဉ်းပဲ စစ်တယ်။
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// Finding တစ်ခု (`start` / `end` = Line ထဲက Byte Offset)
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+    pub level: Level,
+    /// "missing_signer" / "unchecked_arithmetic" / ...
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// Authority လို့ ယူဆရတဲ့ Field နာမည်များ (Signer ဖြစ်ရမယ်)
+const AUTHORITY_NAMES: &[&str] = &["authority", "owner", "admin", "signer", "payer", "user"];
+
+/// `anchor_lang` ကို သုံးတဲ့ File လား
+pub fn is_anchor_source(text: &str) -> bool {
+    text.contains("anchor_lang")
+}
+
+fn field_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"^\s*pub\s+(\w+)\s*:\s*(.+?),?\s*$").unwrap())
+}
+
+fn arithmetic_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"([A-Za-z_][\w.]*(?:\[[^\]]*\])?)\s*([+\-*])=\s*[^=]").unwrap())
+}
+
+pub fn check(text: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let lines: Vec<&str> = text.lines().collect();
+
+    // `#[derive(Accounts)]` Struct ထဲက Field တစ်ခုချင်းစီရဲ့ Attributes / Doc Comments
+    let mut accounts_pending = false;
+    let mut depth: Option<i32> = None;
+    let mut attrs = String::new();
+    // Line အများကြီး ခွဲရေးထားတဲ့ `#[account(...)]` (Start Line, Text)
+    let mut attribute: Option<(usize, String)> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") && !trimmed.starts_with("///") {
+            continue;
+        }
+
+        if trimmed.contains("#[derive(Accounts)]") {
+            accounts_pending = true;
+            continue;
+        }
+        if accounts_pending && trimmed.starts_with("pub struct") {
+            accounts_pending = false;
+            depth = Some(0);
+        }
+
+        if let Some(d) = depth.as_mut() {
+            *d += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+            if *d <= 0 && trimmed.contains('}') {
+                depth = None;
+                attrs.clear();
+                continue;
+            }
+
+            if trimmed.starts_with("#[account(") || attribute.is_some() {
+                let (start, text) = attribute.get_or_insert_with(|| (index, String::new()));
+                text.push_str(trimmed);
+                text.push('\n');
+                if trimmed.ends_with(")]") {
+                    check_constraints(text, *start, &lines, &mut findings);
+                    attrs.push_str(text);
+                    attribute = None;
+                }
+                continue;
+            }
+            if trimmed.starts_with("///") || trimmed.starts_with("#[") {
+                attrs.push_str(trimmed);
+                attrs.push('\n');
+                continue;
+            }
+            if let Some(caps) = field_regex().captures(line) {
+                let name = caps.get(1).unwrap();
+                let ty = caps[2].trim();
+                check_field(index, name.start(), name.as_str(), ty, &attrs, &mut findings);
+                attrs.clear();
+            }
+            continue;
+        }
+
+        check_arithmetic(index, line, &mut findings);
+    }
+
+    findings
+}
+
+/// `AccountInfo` / `UncheckedAccount` Fields: `/// CHECK:` နဲ့ Signer စစ်ဆေးမှု
+fn check_field(line: usize, col: usize, name: &str, ty: &str, attrs: &str, findings: &mut Vec<Finding>) {
+    let unchecked = ty.starts_with("AccountInfo<") || ty.starts_with("UncheckedAccount<");
+    if !unchecked {
+        return;
+    }
+    let end = col + name.len();
+
+    if !attrs.contains("/// CHECK") {
+        findings.push(Finding {
+            line,
+            start: col,
+            end,
+            level: Level::Error,
+            code: "unchecked_account",
+            message: format!(
+                "`{}` is an unchecked account without a `/// CHECK:` comment explaining why it is safe (anchor build rejects this)",
+                name
+            ),
+        });
+    }
+
+    let authority = AUTHORITY_NAMES.contains(&name) || name.ends_with("_authority");
+    if authority && !attrs.contains("signer") {
+        findings.push(Finding {
+            line,
+            start: col,
+            end,
+            level: Level::Warning,
+            code: "missing_signer",
+            message: format!(
+                "`{}` looks like an authority but is `{}`, not `Signer<'info>`: anyone can pass any account here",
+                name, ty
+            ),
+        });
+    }
+}
+
+/// `#[account(...)]` Constraints: `seeds` ပါပြီး `bump` မပါရင် Canonical Bump ကို မစစ်ဘူး၊ `init_if_needed`
+fn check_constraints(attribute: &str, first_line: usize, lines: &[&str], findings: &mut Vec<Finding>) {
+    if attribute.contains("seeds") && !attribute.contains("bump") {
+        let (line, start) = find_in_lines(lines, first_line, "seeds").unwrap_or((first_line, 0));
+        findings.push(Finding {
+            line,
+            start,
+            end: start + "seeds".len(),
+            level: Level::Error,
+            code: "pda_without_bump",
+            message: "PDA `seeds` without `bump`: the canonical bump is never checked".to_string(),
+        });
+    }
+
+    if attribute.contains("init_if_needed") {
+        let (line, start) = find_in_lines(lines, first_line, "init_if_needed").unwrap_or((first_line, 0));
+        findings.push(Finding {
+            line,
+            start,
+            end: start + "init_if_needed".len(),
+            level: Level::Warning,
+            code: "init_if_needed",
+            message: "`init_if_needed` allows re-initialization attacks: make sure the handler rejects accounts that are already initialized".to_string(),
+        });
+    }
+}
+
+fn find_in_lines(lines: &[&str], from: usize, needle: &str) -> Option<(usize, usize)> {
+    lines.iter().enumerate().skip(from).find_map(|(index, line)| line.find(needle).map(|col| (index, col)))
+}
+
+/// `x += y` / `x -= y` / `x *= y` -> Overflow ဖြစ်နိုင် (Release Build မှာ Panic မဖြစ်ဘဲ Wrap)
+fn check_arithmetic(line: usize, text: &str, findings: &mut Vec<Finding>) {
+    let code = text.split("//").next().unwrap_or("");
+    if ["checked_", "saturating_", "wrapping_"].iter().any(|safe| code.contains(safe)) {
+        return;
+    }
+    for caps in arithmetic_regex().captures_iter(code) {
+        let target = caps.get(1).unwrap();
+        let op = caps.get(2).unwrap();
+        let method = match op.as_str() {
+            "+" => "checked_add",
+            "-" => "checked_sub",
+            _ => "checked_mul",
+        };
+        findings.push(Finding {
+            line,
+            start: target.start(),
+            end: op.end() + 1,
+            level: Level::Warning,
+            code: "unchecked_arithmetic",
+            message: format!(
+                "Unchecked `{}=` on `{}` can overflow: use `{}` and return an error",
+                op.as_str(),
+                target.as_str(),
+                method
+            ),
+        });
+    }
+}
@@ -0,0 +1,285 @@
+use crate::api::client::ApiError;
+use crate::utils::config::{AutoConfig, NeurustConfig};
+use crate::utils::events::{self, DecisionSource, Event, JsonLinesSink};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::OnceLock;
+
+// --- 🤖 NON-INTERACTIVE / CI MODE ---
+// `--yes` (`--non-interactive`) = Prompt တွေကို `[auto]` Policy နဲ့ ဖြေမယ်။
+// `--yes` မပါဘဲ stdin က TTY မဟုတ်ရင် (Pipe / CI) Prompt တွေ မစောင့်ဘဲ "no" လို့ ဖြေမယ်။
+// `--output json` = Human Output ကို stderr ဆီ ရွှေ့ပြီး stdout မှာ JSON Lines (Event Bus + Result) ပဲ ထုတ်မယ်။
+// Exit Code ကို Run တစ်လျှောက် မှတ်ထားပြီး `finish` က ဆုံးဖြတ်မယ် (`neurust --help` မှာ ပြထားတယ်)။
+// `neurust serve` မှာတော့ `Responder` (Editor) က Prompt တွေကို ဖြေမယ်။
+
+/// Process Exit Codes (တစ်ခုထက်ပိုရင် Code အမြင့်ဆုံးကို ယူမယ်)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Success = 0,
+    Error = 1,
+    // 2 = Usage Error (clap)
+    /// Plan (သို့) တစ်စိတ်တစ်ပိုင်းကို ငြင်းခဲ့ (Prompt / Command Policy / Cost Guard / Plan File Drift)
+    Refused = 3,
+    /// Verification / Tests / Commands Fail ဖြစ်နေတုန်း ရပ်သွား
+    VerificationFailed = 4,
+    Auth = 5,
+    OutOfCredits = 6,
+}
+
+impl Exit {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => Exit::Success,
+            3 => Exit::Refused,
+            4 => Exit::VerificationFailed,
+            5 => Exit::Auth,
+            6 => Exit::OutOfCredits,
+            _ => Exit::Error,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Exit::Success => "success",
+            Exit::Error => "error",
+            Exit::Refused => "plan_refused",
+            Exit::VerificationFailed => "verification_failed",
+            Exit::Auth => "auth_error",
+            Exit::OutOfCredits => "out_of_credits",
+        }
+    }
+
+    /// `ApiError` (HTTP Status / Session `code`) ကနေ ခွဲမယ် (403 = Model Tier မလောက် / Admin Only -> Auth မဟုတ်ဘူး)
+    pub fn classify(error: &anyhow::Error) -> Self {
+        let Some(api) = error.chain().find_map(|e| e.downcast_ref::<ApiError>()) else {
+            return Exit::Error;
+        };
+        match (api.status, api.code.as_deref()) {
+            (Some(StatusCode::PAYMENT_REQUIRED), _) | (_, Some("insufficient_credits")) => Exit::OutOfCredits,
+            (Some(StatusCode::UNAUTHORIZED), _) => Exit::Auth,
+            _ => Exit::Error,
+        }
+    }
+}
+
+/// `[auto]` ထဲက အဖြေ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Answer {
+    Yes,
+    No,
+}
+
+/// `--output`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// JSON Lines on stdout (human output goes to stderr)
+    Json,
+}
+
+/// User ကို မေးတဲ့ Prompt အမျိုးအစား (`[auto]` Key တစ်ခုစီ)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prompt {
+    Write,
+    Delete,
+    Rename,
+    Redirect,
+    HeavyCommand,
+    RiskyCommand,
+    FeedBack,
+    SendTests,
+    OverBudget,
+    Undo,
+}
+
+impl Prompt {
+    /// `[auto]` Key (JSON `decision` Event မှာလည်း သုံးတယ်)
+    pub fn key(self) -> &'static str {
+        match self {
+            Prompt::Write => "writes",
+            Prompt::Delete => "deletes",
+            Prompt::Rename => "renames",
+            Prompt::Redirect => "redirects",
+            Prompt::HeavyCommand => "heavy_commands",
+            Prompt::RiskyCommand => "risky_commands",
+            Prompt::FeedBack => "feed_back",
+            Prompt::SendTests => "send_tests",
+            Prompt::OverBudget => "over_budget",
+            Prompt::Undo => "undo",
+        }
+    }
+
+    fn answer(self, auto: &AutoConfig) -> Answer {
+        match self {
+            Prompt::Write => auto.writes,
+            Prompt::Delete => auto.deletes,
+            Prompt::Rename => auto.renames,
+            Prompt::Redirect => auto.redirects,
+            Prompt::HeavyCommand => auto.heavy_commands,
+            Prompt::RiskyCommand => auto.risky_commands,
+            Prompt::FeedBack => auto.feed_back,
+            Prompt::SendTests => auto.send_tests,
+            Prompt::OverBudget => auto.over_budget,
+            Prompt::Undo => auto.undo,
+        }
+    }
+
+    /// "no" = Plan ကို ငြင်းတာလား (Redirect "no" = မူလ Path မှာ ရေးမယ်၊ FeedBack / SendTests က သူ့ Exit Code သူ့ဘာသာ မှတ်မယ်)
+    fn refuses(self) -> bool {
+        !matches!(self, Prompt::Redirect | Prompt::FeedBack | Prompt::SendTests)
+    }
+}
+
+struct Mode {
+    interactive: bool,
+    assume_yes: bool,
+    auto: AutoConfig,
+}
+
+static MODE: OnceLock<Mode> = OnceLock::new();
+static EXIT: AtomicI32 = AtomicI32::new(0);
+static RESPONDER: OnceLock<Box<dyn Responder>> = OnceLock::new();
+
+/// Terminal အစား Prompt / Question တွေကို ဖြေပေးမယ့်သူ (`neurust serve` = Editor Client)
+pub trait Responder: Send + Sync {
+    fn confirm(&self, prompt: Prompt, target: &str) -> bool;
+    /// None = ဖြေမယ့်သူ မရှိ (Conversation ရပ်)
+    fn reply(&self, question: &str) -> Option<String>;
+}
+
+/// Process တစ်ခုမှာ တစ်ကြိမ်ပဲ သတ်မှတ်လို့ရတယ် (stdin ကို Protocol က သုံးနေလို့ Interactive Mode ပိတ်မယ်)
+pub fn set_responder(responder: impl Responder + 'static) {
+    let _ = RESPONDER.set(Box::new(responder));
+}
+
+/// main ကနေ တစ်ကြိမ်ပဲ ခေါ်မယ် (မခေါ်ရင် Interactive + Text)
+pub fn init(yes: bool, output: OutputFormat) {
+    if output == OutputFormat::Json {
+        // မူလ stdout (fd 1 က stderr ဆီ ပြောင်းသွားပြီ) ကို JSON Lines Sink ပေးမယ်
+        let out: Box<dyn Write + Send> = match detach_stdout() {
+            Some(file) => Box::new(file),
+            None => Box::new(std::io::stdout()),
+        };
+        events::subscribe(JsonLinesSink::new(out));
+    }
+    let _ = MODE.set(Mode {
+        interactive: !yes && std::io::stdin().is_terminal(),
+        assume_yes: yes,
+        auto: NeurustConfig::load().auto,
+    });
+}
+
+fn mode() -> &'static Mode {
+    MODE.get_or_init(|| Mode { interactive: true, assume_yes: false, auto: AutoConfig::default() })
+}
+
+/// Prompt တွေကို User ကို မေးလို့ရလား
+pub fn interactive() -> bool {
+    RESPONDER.get().is_none() && mode().interactive
+}
+
+/// Prompt တစ်ခုကို ဖြေမယ်: Responder / Interactive = `ask()`၊ `--yes` = `[auto]`၊ TTY မရှိ = "no"
+pub fn confirm(prompt: Prompt, target: &str, ask: impl FnOnce() -> bool) -> bool {
+    let mode = mode();
+    let (approved, source) = if let Some(responder) = RESPONDER.get() {
+        (responder.confirm(prompt, target), DecisionSource::User)
+    } else if mode.interactive {
+        (ask(), DecisionSource::User)
+    } else if mode.assume_yes {
+        (prompt.answer(&mode.auto) == Answer::Yes, DecisionSource::Auto)
+    } else {
+        (false, DecisionSource::NoTty)
+    };
+
+    if !approved && prompt.refuses() {
+        fail(Exit::Refused);
+    }
+    events::emit(Event::DecisionMade {
+        prompt: prompt.key().to_string(),
+        target: target.to_string(),
+        approved,
+        source,
+        reason: None,
+    });
+    approved
+}
+
+/// Non-interactive မှာ Agent ရဲ့ မေးခွန်း (ask_user) ကို ဖြေမယ့်စာ
+/// `[auto].reply` မရှိရင် "exit" (Conversation ရပ် -> Plan မရ = Refused)
+pub fn auto_reply(question: &str) -> String {
+    let reply = match RESPONDER.get() {
+        Some(responder) => responder.reply(question),
+        None => mode().auto.reply.clone().filter(|_| mode().assume_yes),
+    };
+    events::emit(Event::QuestionAsked { question: question.to_string(), reply: reply.clone() });
+    match reply {
+        Some(reply) => reply,
+        None => {
+            fail(Exit::Refused);
+            "exit".to_string()
+        }
+    }
+}
+
+/// Exit Code ကို မှတ်မယ် (အမြင့်ဆုံးကို ယူမယ်)
+pub fn fail(exit: Exit) {
+    EXIT.fetch_max(exit.code(), Ordering::SeqCst);
+}
+
+/// Print ပြီး ဆက်သွားတဲ့ Error (AI Connection Error ...) ကို Exit Code အဖြစ် မှတ်မယ်
+pub fn fail_with(error: &anyhow::Error) {
+    fail(Exit::classify(error));
+}
+
+/// မှတ်ထားတဲ့ Exit Code ကို Event မထုတ်ဘဲ ဖျက်မယ် (`neurust lsp`: Shutdown ပြီးရင် 0 နဲ့ ထွက်ရမယ်)
+pub fn reset() {
+    EXIT.store(0, Ordering::SeqCst);
+}
+
+/// Command ပြီးချိန်: Exit Code + `run_finished` Event (မှတ်ထားတာကို Reset လုပ်မယ် - `serve` မှာ Prompt တစ်ခုစီ)
+pub fn finish(error: Option<&anyhow::Error>) -> i32 {
+    if let Some(e) = error {
+        fail_with(e);
+    }
+    let exit = Exit::from_code(EXIT.swap(0, Ordering::SeqCst));
+    events::emit(Event::RunFinished {
+        exit_code: exit.code(),
+        status: exit.label().to_string(),
+        error: error.map(|e| e.to_string()),
+    });
+    exit.code()
+}
+
+/// fd 1 ကို stderr ဆီ ပြောင်းမယ် (println! တွေ stderr ရောက်) -> မူလ stdout ကို Events အတွက် ပြန်ပေးမယ်
+#[cfg(unix)]
+pub fn detach_stdout() -> Option<File> {
+    use std::os::fd::FromRawFd;
+
+    let _ = std::io::stdout().flush();
+    // SAFETY: Command တွေ မ Run ခင် main ထဲမှာ dup လုပ်တာ၊ `events` fd ကို File တစ်ခုတည်းက ပိုင်တယ်
+    unsafe {
+        let events = libc::dup(1);
+        if events < 0 {
+            return None;
+        }
+        if libc::dup2(2, 1) < 0 {
+            libc::close(events);
+            return None;
+        }
+        Some(File::from_raw_fd(events))
+    }
+}
+
+#[cfg(not(unix))]
+pub fn detach_stdout() -> Option<File> {
+    None
+}
@@ -1,121 +1,827 @@
-use std::process::{Command, Stdio};
-use std::path::Path;
-use anyhow::{Result, anyhow, Context};
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use anyhow::{Result, anyhow};
 use colored::*;
-use indicatif::{ProgressBar, ProgressStyle};
 use dialoguer::{theme::ColorfulTheme, Select};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::{broadcast, mpsc};
+use tokio::time::{sleep_until, Instant};
+use crate::utils::ci::{self, Prompt};
+use crate::utils::config::{ExecConfig, NeurustConfig};
+use crate::utils::events::{self, CommandSource, Event, OutputStream};
+use crate::utils::isolation::{self, Isolation, Violation};
+
+// --- ⚙️ PROCESS RUNNER ---
+// Command အားလုံး (Plan `run_cmd`, Verification, Tests, Legacy `execute*`) ဒီ Runner ကို ဖြတ်မယ်။
+// - stdout/stderr ကို Line အလိုက် Event (`events::CommandOutput`) အဖြစ် ထုတ်မယ် (Terminal Sink က Live / Collapsed ပြမယ်)
+// - Output အကုန်ကို `.neurust/logs/<session>.log` ထဲ Tee လုပ်မယ်
+// - Timeout (`[exec].timeout_secs` / `[exec.timeouts]`) နဲ့ Ctrl-C ဆိုရင် Process Group တစ်ခုလုံးကို
+//   SIGTERM -> (Grace) -> SIGKILL နဲ့ ရပ်မယ် (Neurust ကိုယ်တိုင် မသေဘူး)
+// - Environment: `[exec].env` / `env_remove` / `clear_env`
 
 /// အချိန်ကြာမြင့်နိုင်သော Command များစာရင်း
 const HEAVY_COMMANDS: &[&str] = &["npx", "npm", "cargo", "pnpm", "yarn", "docker", "git"];
 
+/// SIGTERM ပြီး SIGKILL မပို့ခင် စောင့်မယ့်အချိန်
+const KILL_GRACE: Duration = Duration::from_secs(5);
+
+/// Main Process ပြီးသွားပြီး Background Child က Pipe ကို ဖွင့်ထားရင် ဆက်မစောင့်ဘူး
+const DRAIN_WAIT: Duration = Duration::from_millis(500);
+
+/// Terminal မှာ Output ပြပုံ (`[exec].output`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputView {
+    /// Line တိုင်းကို ချက်ချင်း ပြမယ်
+    Live,
+    /// Spinner + နောက်ဆုံး Line တစ်ကြောင်း
+    Collapsed,
+    /// ဘာမှ မပြဘူး (Log ထဲမှာတော့ ရှိမယ်)
+    Quiet,
+}
+
+/// Run မယ့် Command တစ်ခု (Builder)
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    program: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    view: Option<OutputView>,
+    echo_stdout: bool,
+    isolate: bool,
+    network: bool,
+    source: CommandSource,
+}
+
+impl CommandSpec {
+    pub fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: None,
+            view: None,
+            echo_stdout: true,
+            isolate: true,
+            network: false,
+            source: CommandSource::Check,
+        }
+    }
+
+    pub fn cwd(mut self, dir: impl AsRef<Path>) -> Self {
+        self.cwd = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn view(mut self, view: OutputView) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    /// stdout ကို Terminal မှာ မပြဘူး (e.g. `--message-format=json`)၊ Capture / Log တော့ လုပ်မယ်
+    pub fn hide_stdout(mut self) -> Self {
+        self.echo_stdout = false;
+        self
+    }
+
+    /// Sandbox Mode မှာလည်း bwrap မသုံးဘူး (User ကိုယ်တိုင် ရွေးတဲ့ Legacy Callers)
+    pub fn unisolated(mut self) -> Self {
+        self.isolate = false;
+        self
+    }
+
+    /// Sandbox ထဲမှာ `[sandbox].network = false` ဖြစ်နေလည်း Network ပေးမယ် (Package Install လို Setup Commands)
+    pub fn network(mut self) -> Self {
+        self.network = true;
+        self
+    }
+
+    /// Event ထဲမှာ ပါမယ့် Caller (Default = Check)
+    pub fn source(mut self, source: CommandSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str()).chain(self.args.iter().map(|a| a.as_str())).collect::<Vec<_>>().join(" ")
+    }
+
+    pub async fn run(self) -> CommandOutcome {
+        let config = NeurustConfig::load().exec;
+        let command = self.command_line();
+        let args: Vec<&str> = self.args.iter().map(|a| a.as_str()).collect();
+        let cwd = self.cwd.as_ref().map(|d| d.to_string_lossy().to_string());
+
+        // Plan ကို ရပ်ခိုင်းထားရင် (`serve` ရဲ့ session/cancel) Command အသစ် မစတော့ဘူး
+        if stop_requested() {
+            let outcome = CommandOutcome { cancelled: true, skipped: false, ..CommandOutcome::not_run(command, None) };
+            events::command_finished(None, self.source, &outcome);
+            return outcome;
+        }
+
+        let isolation = if self.isolate { Isolation::active() } else { None };
+        let isolation = isolation.map(|sandbox| if self.network { sandbox.with_network() } else { sandbox });
+        let mut std_cmd = match &isolation {
+            Some(sandbox) => match sandbox.command(&self.program, &args, cwd.as_deref()) {
+                Ok(cmd) => cmd,
+                Err(reason) => {
+                    println!("{} Not run: {}", "📦".red(), reason);
+                    let outcome = CommandOutcome::not_run(command, Some(format!("the sandbox: {}", reason)));
+                    events::command_finished(None, self.source, &outcome);
+                    return outcome;
+                }
+            },
+            None => {
+                let mut cmd = std::process::Command::new(&self.program);
+                cmd.args(&args);
+                if let Some(dir) = &self.cwd { cmd.current_dir(dir); }
+                cmd
+            }
+        };
+        apply_env(&mut std_cmd, &config);
+        // Terminal ရဲ့ Ctrl-C က Child ဆီ တိုက်ရိုက် မရောက်အောင် Process Group သီးသန့်ထားမယ် (Cancel ကို ကိုယ်တိုင် လုပ်မယ်)
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut std_cmd, 0);
+
+        let timeout = config.timeout_for(&command);
+        let view = self.view.unwrap_or(config.output);
+        let mut echo = Echo::start(&command, self.source, view, self.echo_stdout, cwd.as_deref(), timeout);
+
+        let mut outcome = supervise(std_cmd, command, timeout, config.max_output_kb * 1024, &mut echo).await;
+        if let Some(sandbox) = &isolation {
+            outcome.violations = sandbox.violations(&outcome);
+        }
+        echo.finish(&outcome);
+        outcome
+    }
+}
+
+/// `[exec]` Environment Control (sandbox ထဲကို bwrap က ဆက်ပေးမယ်)
+fn apply_env(cmd: &mut std::process::Command, config: &ExecConfig) {
+    if config.clear_env {
+        cmd.env_clear();
+        for key in &config.env_passthrough {
+            if let Some(value) = std::env::var_os(key) {
+                cmd.env(key, value);
+            }
+        }
+    }
+    for key in &config.env_remove {
+        cmd.env_remove(key);
+    }
+    for (key, value) in &config.env {
+        cmd.env(key, value);
+    }
+}
+
+/// `run_observed` ရဲ့ ရလဒ် (Agent ဆီ Observation အဖြစ် ပြန်ပို့မယ်)
+#[derive(Debug, Clone)]
+pub struct CommandOutcome {
+    /// e.g. "cargo check"
+    pub command: String,
+    /// None = Spawn မရ (Program မရှိ) / Signal နဲ့ ရပ်သွား / Skipped
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub duration: Duration,
+    /// User က Heavy Command ကို Skip လုပ်လိုက်တယ် (သို့) Policy က ပိတ်လိုက်တယ်
+    pub skipped: bool,
+    /// Run မလုပ်ခဲ့ရတဲ့ အကြောင်းပြချက် (Command Policy Deny / Sandbox မရနိုင်)
+    pub blocked: Option<String>,
+    /// Sandbox Mode မှာ ပိတ်ခံရတဲ့ Operations
+    pub violations: Vec<Violation>,
+    /// Timeout ပြည့်လို့ ရပ်လိုက်တယ်
+    pub timed_out: bool,
+    /// User က Ctrl-C နဲ့ ရပ်လိုက်တယ်
+    pub cancelled: bool,
+    /// stdout/stderr အလယ်ပိုင်းကို `[exec].max_output_kb` ကြောင့် ဖြတ်ထားတယ်
+    pub truncated: bool,
+}
+
+impl CommandOutcome {
+    /// Run မလုပ်ခဲ့တဲ့ Command (User Skip / Policy Deny)
+    pub fn not_run(command: String, blocked: Option<String>) -> Self {
+        Self {
+            command,
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            duration: Duration::ZERO,
+            skipped: true,
+            blocked,
+            violations: Vec::new(),
+            timed_out: false,
+            cancelled: false,
+            truncated: false,
+        }
+    }
+
+    pub fn success(&self) -> bool {
+        !self.skipped && self.exit_code == Some(0)
+    }
+
+    /// Model အတွက် Text (stdout/stderr တစ်ခုချင်းစီကို `max_chars` အတွင်း Smart Truncate လုပ်မယ်)
+    pub fn observation(&self, max_chars: usize) -> String {
+        if let Some(reason) = &self.blocked {
+            return format!(
+                "$ {}\n(blocked by {}; not executed. Do not retry it; ask the user to run it manually if it is required)\n",
+                self.command, reason
+            );
+        }
+        if self.skipped {
+            return format!("$ {}\n(skipped by the user; not executed)\n", self.command);
+        }
+
+        let exit = if self.cancelled {
+            "none (cancelled by the user with Ctrl-C; partial output below)".to_string()
+        } else if self.timed_out {
+            "none (killed after reaching its timeout; partial output below)".to_string()
+        } else {
+            self.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "none (failed to start or killed)".to_string())
+        };
+        let mut text = format!(
+            "$ {}\nexit code: {} | duration: {:.1}s\n",
+            self.command,
+            exit,
+            self.duration.as_secs_f64()
+        );
+        for (label, stream) in [("stdout", &self.stdout), ("stderr", &self.stderr)] {
+            if !stream.trim().is_empty() {
+                text.push_str(&format!("--- {} ---\n{}\n", label, truncate_output(stream, max_chars)));
+            }
+        }
+        if self.truncated {
+            text.push_str("(very long output: the middle was dropped before it reached you)\n");
+        }
+        if !self.violations.is_empty() {
+            text.push_str(&isolation::describe(&self.violations));
+        }
+        text
+    }
+
+    /// History / Console အတွက် (None = ပုံမှန် ပြီးဆုံး)
+    pub fn stopped(&self) -> Option<&'static str> {
+        if self.cancelled {
+            Some("cancelled")
+        } else if self.timed_out {
+            Some("timed out")
+        } else {
+            None
+        }
+    }
+}
+
 /// 1. Interactive Execution (Console Output Only)
-/// User မြင်အောင် Console မှာ ပြမယ်၊ Heavy command ဆိုရင် မေးမယ်။
-pub fn execute_with_output(program: &str, args: &[&str], cwd: Option<&str>) -> Result<()> {
-    // Heavy Command ဟုတ်မဟုတ် စစ်ဆေးခြင်း
-    if HEAVY_COMMANDS.contains(&program) {
-        return execute_heavy_interactive(program, args, cwd);
+///
+/// User မြင်အောင် Console မှာ Stream ပြမယ်၊ Heavy command ဆိုရင် မေးမယ်။
+pub async fn execute_with_output(program: &str, args: &[&str], cwd: Option<&str>) -> Result<()> {
+    if HEAVY_COMMANDS.contains(&program) && !confirm_heavy(program, args, cwd) {
+        println!("\n{} Skipped. Please run this manually:", "⏭️".blue());
+        let cd_cmd = if let Some(dir) = cwd { format!("cd {} && ", dir) } else { "".to_string() };
+        println!("   {}{} {}\n", cd_cmd.cyan(), program.green(), args.join(" ").green());
+        return Ok(());
+    }
+
+    let outcome = legacy_spec(program, args, cwd).run().await;
+    if outcome.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(failure_message(&outcome)))
     }
-    // Light Command ဆိုရင် ပုံမှန်အတိုင်း run မယ်
-    execute_silent(program, args, cwd)
 }
 
-/// 2. Capture Execution (Returns String) - 🔥 NEW FUNCTION
+/// 2. Capture Execution (Returns String)
+///
 /// Console မှာ မပြဘဲ Output ကို Variable ထဲ ထည့်ချင်တဲ့အခါ သုံးမယ် (e.g. Audit JSON)
-pub fn execute_and_capture(program: &str, args: &[&str], cwd: Option<&str>) -> Result<String> {
-    let mut cmd = Command::new(program);
-    cmd.args(args);
+pub async fn execute_and_capture(program: &str, args: &[&str], cwd: Option<&str>) -> Result<String> {
+    capture(legacy_spec(program, args, cwd).view(OutputView::Quiet)).await
+}
 
-    if let Some(dir) = cwd {
-        cmd.current_dir(dir);
+async fn capture(spec: CommandSpec) -> Result<String> {
+    let outcome = spec.run().await;
+    if outcome.exit_code.is_none() {
+        return Err(anyhow!(failure_message(&outcome)));
     }
 
-    // Output ကို ဖမ်းယူခြင်း
-    let output = cmd.output().context("Failed to execute capture command")?;
-
-    // Success ဖြစ်ဖြစ် Fail ဖြစ်ဖြစ် Output ကို String ပြောင်းမယ်
+    // Success ဖြစ်ဖြစ် Fail ဖြစ်ဖြစ် Output ကို ပြန်ပေးမယ်
     // (ဥပမာ cargo audit က အမှားတွေ့ရင် exit code 1 ပြန်ပေမယ့် JSON လိုချင်သေးတယ်)
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    
-    if stdout.trim().is_empty() {
+    if outcome.stdout.trim().is_empty() && !outcome.stderr.is_empty() {
         // Stdout မရှိရင် Stderr ကို ပြန်ပေးမယ်
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        if !stderr.is_empty() {
-            return Ok(stderr);
-        }
+        return Ok(outcome.stderr);
+    }
+    Ok(outcome.stdout)
+}
+
+/// 3. Observed Execution (Agent Observation Loop) - Exit Code / Duration / Output အကုန် ဖမ်းမယ်
+///
+/// Heavy Command ဆိုရင် အရင်လိုပဲ Wait/Skip မေးမယ်။ Fail ဖြစ်လည်း Err မပြန်ဘဲ Outcome ထဲ ထည့်ပေးမယ်။
+pub async fn run_observed(program: &str, args: &[&str], cwd: Option<&str>) -> CommandOutcome {
+    let command = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+
+    if HEAVY_COMMANDS.contains(&program) && !confirm_heavy(program, args, cwd) {
+        let outcome = CommandOutcome::not_run(command, None);
+        events::command_finished(None, CommandSource::Plan, &outcome);
+        return outcome;
     }
 
-    Ok(stdout)
+    let mut spec = CommandSpec::new(program, args).source(CommandSource::Plan);
+    if let Some(dir) = cwd {
+        spec = spec.cwd(dir);
+    }
+    spec.run().await
 }
 
-// --- Internal Helpers ---
+/// 4. Captured Execution (No Prompt) - Verification Pipeline လို User က Config နဲ့ ခွင့်ပြုထားတာတွေအတွက်
+///
+/// Sandbox Mode ဆိုရင် bwrap ထဲမှာ Run ပြီး Violations ကို Outcome ထဲ ထည့်မယ်
+pub async fn run_captured(program: &str, args: &[&str], cwd: Option<&str>) -> CommandOutcome {
+    let mut spec = CommandSpec::new(program, args);
+    if let Some(dir) = cwd {
+        spec = spec.cwd(dir);
+    }
+    spec.run().await
+}
 
-fn execute_heavy_interactive(program: &str, args: &[&str], cwd: Option<&str>) -> Result<()> {
-    println!("\n{} Heavy Task Detected: {} {}", "⚠️".yellow(), program, args.join(" "));
-    
+/// Backward compatibility alias (Capture + `[exec].output` အတိုင်း Stream ပြမယ် - anchor build / npx create ...)
+pub async fn execute(program: &str, args: &[&str], cwd: Option<&str>) -> Result<String> {
+    capture(legacy_spec(program, args, cwd)).await
+}
+
+/// 5. Setup Execution (`neurust create` ရဲ့ AI ရွေးထားတဲ့ Init / Dependency Commands)
+///
+/// `execute` လိုပဲ၊ ဒါပေမဲ့ Sandbox Mode ဆိုရင် bwrap ထဲမှာ Run မယ် (Registry ရောက်အောင် Network တော့ ပေးမယ်)
+pub async fn execute_setup(program: &str, args: &[&str], cwd: Option<&str>) -> Result<String> {
+    let mut spec = CommandSpec::new(program, args).network().source(CommandSource::Setup);
     if let Some(dir) = cwd {
-        println!("   📂 In Directory: {}", dir);
+        spec = spec.cwd(dir);
     }
+    capture(spec).await
+}
 
-    println!("{}", "This command requires downloading packages from the internet and may take a while.".dimmed());
+/// Output ရှည်ရင် အစ/အဆုံးကို ထားပြီး အလယ်က error/warning/panic Lines တွေကိုပဲ ရွေးထားမယ်
+pub fn truncate_output(text: &str, max_chars: usize) -> String {
+    if text.len() <= max_chars {
+        return text.to_string();
+    }
 
-    let selections = &[
-        "⏳ Wait (Neurust will run it patiently with a spinner)",
-        "✋ Skip (I will run it manually later)",
+    let lines: Vec<&str> = text.lines().collect();
+    let fit = |budget: usize, iter: &mut dyn Iterator<Item = usize>| {
+        let mut used = 0;
+        let mut picked = Vec::new();
+        for i in iter {
+            used += lines[i].len() + 1;
+            if used > budget { break; }
+            picked.push(i);
+        }
+        picked
+    };
+
+    // Budget: Head 20% / Tail 50% / Important Middle Lines 30%
+    let head = fit(max_chars / 5, &mut (0..lines.len()));
+    let mut tail = fit(max_chars / 2, &mut (0..lines.len()).rev());
+    tail.reverse();
+
+    let head_end = head.last().map(|i| i + 1).unwrap_or(0);
+    let tail_start = tail.first().copied().unwrap_or(lines.len()).max(head_end);
+    let middle = fit(
+        max_chars - max_chars / 5 - max_chars / 2,
+        &mut (head_end..tail_start).filter(|&i| is_important(lines[i])),
+    );
+
+    let mut keep: Vec<usize> = head.into_iter().chain(middle).chain(tail.into_iter().filter(|&i| i >= head_end)).collect();
+    keep.sort_unstable();
+    keep.dedup();
+
+    let mut out = String::new();
+    let mut previous: Option<usize> = None;
+    for i in keep {
+        let gap = match previous { Some(p) => i - p - 1, None => i };
+        if gap > 0 {
+            out.push_str(&format!("[... {} lines omitted ...]\n", gap));
+        }
+        out.push_str(lines[i]);
+        out.push('\n');
+        previous = Some(i);
+    }
+    if let Some(p) = previous.filter(|p| p + 1 < lines.len()) {
+        out.push_str(&format!("[... {} lines omitted ...]\n", lines.len() - p - 1));
+    }
+    out
+}
+
+fn is_important(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ["error", "warning", "panicked", "failed", "cannot find", "-->"].iter().any(|k| lower.contains(k))
+}
+
+// --- 🛑 CANCELLATION (Ctrl-C) ---
+
+/// လက်ရှိ Run နေတဲ့ Command အရေအတွက်
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+fn interrupts() -> &'static broadcast::Sender<()> {
+    static INTERRUPTS: OnceLock<broadcast::Sender<()>> = OnceLock::new();
+    INTERRUPTS.get_or_init(|| broadcast::channel(8).0)
+}
+
+/// main() မှာ တစ်ကြိမ် ခေါ်ရမယ်
+/// Command Run နေတုန်း Ctrl-C = အဲ့ဒီ Command ကိုပဲ ရပ်မယ် (နှစ်ခါနှိပ်ရင် ချက်ချင်း Kill)၊ မဟုတ်ရင် အရင်လို Exit
+pub fn install_interrupt_handler() {
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if ACTIVE.load(Ordering::SeqCst) == 0 {
+                let _ = console::Term::stdout().show_cursor();
+                println!();
+                std::process::exit(130);
+            }
+            let _ = interrupts().send(());
+        }
+    });
+}
+
+/// Prompt တစ်ခုလုံးကို ရပ်ခိုင်းထားတယ် (`serve` ရဲ့ session/cancel) - Executor က Action တစ်ခုစီ မစခင် စစ်မယ်
+static STOP: AtomicBool = AtomicBool::new(false);
+
+/// Run နေတဲ့ Command ကို Ctrl-C လို ရပ်ပြီး နောက် Action / Command တွေကို မစခိုင်းဘူး
+pub fn request_stop() {
+    STOP.store(true, Ordering::SeqCst);
+    let _ = interrupts().send(());
+}
+
+pub fn stop_requested() -> bool {
+    STOP.load(Ordering::SeqCst)
+}
+
+/// Prompt အသစ် မစခင် ခေါ်ရမယ်
+pub fn clear_stop() {
+    STOP.store(false, Ordering::SeqCst);
+}
+
+/// Run နေတဲ့ Command ကို ရေတွက်ထားမယ် (Drop မှာ လျှော့မယ်)
+struct ActiveGuard;
+
+impl ActiveGuard {
+    fn enter() -> Self {
+        ACTIVE.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Spawn -> Stream -> Timeout/Cancel -> Structured Outcome
+async fn supervise(
+    cmd: std::process::Command,
+    command: String,
+    timeout: Option<Duration>,
+    max_bytes: usize,
+    echo: &mut Echo,
+) -> CommandOutcome {
+    let _active = ActiveGuard::enter();
+    let mut interrupted = interrupts().subscribe();
+    let started = Instant::now();
+
+    let mut cmd = tokio::process::Command::from(cmd);
+    cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true);
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            let mut outcome = CommandOutcome::not_run(command, None);
+            outcome.skipped = false;
+            outcome.stderr = format!("Failed to spawn process: {}", e);
+            outcome.duration = started.elapsed();
+            return outcome;
+        }
+    };
+    let pid = child.id();
+    let mut group = GroupGuard(pid);
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let readers = [
+        child.stdout.take().map(|out| tokio::spawn(read_lines(out, OutputStream::Stdout, tx.clone()))),
+        child.stderr.take().map(|err| tokio::spawn(read_lines(err, OutputStream::Stderr, tx.clone()))),
     ];
+    drop(tx);
 
-    let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("How do you want to proceed?")
-        .default(0)
-        .items(&selections[..])
-        .interact()
-        .unwrap_or(0);
+    let mut stdout = Capture::new(max_bytes);
+    let mut stderr = Capture::new(max_bytes);
+    let mut record = |stream: OutputStream, line: String, echo: &mut Echo| {
+        echo.line(stream, &line);
+        match stream {
+            OutputStream::Stdout => stdout.push(line),
+            OutputStream::Stderr | OutputStream::Notice => stderr.push(line),
+        }
+    };
 
-    if selection == 1 {
-        // Option B: Skip
-        println!("\n{} Skipped. Please run this manually:", "⏭️".blue());
-        let cd_cmd = if let Some(dir) = cwd { format!("cd {} && ", dir) } else { "".to_string() };
-        println!("   {}{}{} {}\n", cd_cmd.cyan(), program.green(), args.join(" ").green(), "".clear());
-        return Ok(()); 
+    let deadline = timeout.map(|t| started + t);
+    let mut kill_at: Option<Instant> = None;
+    let (mut timed_out, mut cancelled) = (false, false);
+
+    let status = loop {
+        tokio::select! {
+            Some((stream, line)) = rx.recv() => record(stream, line, echo),
+            status = child.wait() => break status,
+            _ = sleep_until(deadline.unwrap_or(started)), if deadline.is_some() && !timed_out && !cancelled => {
+                timed_out = true;
+                echo.notice(&format!("⏱️ Timed out after {}s; stopping {}...", timeout.unwrap_or_default().as_secs(), command));
+                signal_group(pid, &mut child, false);
+                kill_at = Some(Instant::now() + KILL_GRACE);
+            }
+            Ok(()) = interrupted.recv() => {
+                if timed_out || cancelled {
+                    signal_group(pid, &mut child, true);
+                } else {
+                    cancelled = true;
+                    echo.notice(&format!("🛑 Cancelling {} (Ctrl-C again to kill it now)...", command));
+                    signal_group(pid, &mut child, false);
+                    kill_at = Some(Instant::now() + KILL_GRACE);
+                }
+            }
+            _ = sleep_until(kill_at.unwrap_or(started)), if kill_at.is_some() => {
+                signal_group(pid, &mut child, true);
+                kill_at = None;
+            }
+        }
+    };
+    group.0 = None;
+
+    // ကျန်နေတဲ့ Lines (Background Process က Pipe ကို ကိုင်ထားရင် ခဏပဲ စောင့်မယ်)
+    let drain_until = Instant::now() + DRAIN_WAIT;
+    while let Ok(Some((stream, line))) = tokio::time::timeout_at(drain_until, rx.recv()).await {
+        record(stream, line, echo);
+    }
+    for reader in readers.into_iter().flatten() {
+        reader.abort();
+    }
+    if timed_out || cancelled {
+        signal_group(pid, &mut child, true);
     }
 
-    // Option A: Wait -> Show Spinner
-    let pb = ProgressBar::new_spinner();
-    pb.set_style(ProgressStyle::default_spinner()
-        .template("{spinner:.green} {msg}")
-        .unwrap()
-        .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"));
-    
-    pb.set_message(format!("Running {}... (Please be patient)", program));
-    pb.enable_steady_tick(std::time::Duration::from_millis(100));
+    let duration = started.elapsed();
+    let (stdout, stdout_cut) = stdout.finish();
+    let (stderr, stderr_cut) = stderr.finish();
+    let (exit_code, stderr) = match status {
+        Ok(status) => (if timed_out || cancelled { None } else { status.code() }, stderr),
+        Err(e) => (None, format!("{}Failed to wait for process: {}", stderr, e)),
+    };
 
-    let mut cmd = Command::new(program);
-    cmd.args(args);
-    if let Some(dir) = cwd { cmd.current_dir(dir); }
+    CommandOutcome {
+        command,
+        exit_code,
+        stdout,
+        stderr,
+        duration,
+        skipped: false,
+        blocked: None,
+        violations: Vec::new(),
+        timed_out,
+        cancelled,
+        truncated: stdout_cut || stderr_cut,
+    }
+}
 
-    // Piped Output to avoid messing up the spinner
-    let output = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).output().context("Failed to spawn process")?;
+async fn read_lines(pipe: impl AsyncRead + Unpin, stream: OutputStream, tx: mpsc::UnboundedSender<(OutputStream, String)>) {
+    let mut reader = BufReader::new(pipe);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = String::from_utf8_lossy(&buf).trim_end_matches(['\n', '\r']).to_string();
+                if tx.send((stream, line)).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
 
-    pb.finish_and_clear();
+/// Process မပြီးခင် Future ကို Drop လုပ်သွားရင် (`neurust serve` Cancel) Group တစ်ခုလုံးကို Kill မယ်
+/// (`kill_on_drop` က Leader ကိုပဲ သတ်လို့ `sh -c` ရဲ့ Children တွေ ကျန်နေမယ်)
+struct GroupGuard(Option<u32>);
 
-    if output.status.success() {
-        println!("{} Command finished successfully.", "✅".green());
-        Ok(())
-    } else {
-        let err_msg = String::from_utf8_lossy(&output.stderr);
-        println!("{} Command failed:\n{}", "❌".red(), err_msg.red());
-        Err(anyhow!("External command failed"))
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.0 {
+            // SAFETY: kill(2) with a negative pid only signals the group we created with process_group(0)
+            unsafe {
+                libc::kill(-(pid as i32), libc::SIGKILL);
+            }
+        }
+    }
+}
+
+/// Process Group တစ်ခုလုံးကို SIGTERM (force = SIGKILL)
+fn signal_group(pid: Option<u32>, child: &mut tokio::process::Child, force: bool) {
+    #[cfg(unix)]
+    if let Some(pid) = pid {
+        // SAFETY: kill(2) with a negative pid only signals the group we created with process_group(0)
+        unsafe {
+            libc::kill(-(pid as i32), if force { libc::SIGKILL } else { libc::SIGTERM });
+        }
+        return;
     }
+    let _ = (pid, force);
+    let _ = child.start_kill();
 }
 
-fn execute_silent(program: &str, args: &[&str], cwd: Option<&str>) -> Result<()> {
-    let mut cmd = Command::new(program);
-    cmd.args(args);
-    if let Some(dir) = cwd { cmd.current_dir(dir); }
-    let output = cmd.output().context("Failed to execute command")?;
-    if output.status.success() { Ok(()) } else { Err(anyhow!(String::from_utf8_lossy(&output.stderr).to_string())) }
+/// Output Capture (Limit ကျော်ရင် အစ/အဆုံးကို ထားပြီး အလယ်ကို ဖြတ်မယ်)
+struct Capture {
+    head: String,
+    tail: VecDeque<String>,
+    tail_bytes: usize,
+    dropped: usize,
+    half: usize,
 }
 
-/// Backward compatibility alias
-pub fn execute(program: &str, args: &[&str], cwd: Option<&str>) -> Result<String> {
-    execute_and_capture(program, args, cwd)
-}
\ No newline at end of file
+impl Capture {
+    fn new(max_bytes: usize) -> Self {
+        Self { head: String::new(), tail: VecDeque::new(), tail_bytes: 0, dropped: 0, half: max_bytes / 2 }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.tail.is_empty() && self.head.len() + line.len() < self.half {
+            self.head.push_str(&line);
+            self.head.push('\n');
+            return;
+        }
+        self.tail_bytes += line.len() + 1;
+        self.tail.push_back(line);
+        while self.tail_bytes > self.half {
+            let Some(old) = self.tail.pop_front() else { break };
+            self.tail_bytes -= old.len() + 1;
+            self.dropped += 1;
+        }
+    }
+
+    fn finish(self) -> (String, bool) {
+        let mut text = self.head;
+        if self.dropped > 0 {
+            text.push_str(&format!("[... {} lines dropped ([exec].max_output_kb) ...]\n", self.dropped));
+        }
+        for line in self.tail {
+            text.push_str(&line);
+            text.push('\n');
+        }
+        (text, self.dropped > 0)
+    }
+}
+
+/// Command Events (Terminal Sink က Live / Collapsed ပြမယ်) + Run Log Tee
+struct Echo {
+    id: u64,
+    source: CommandSource,
+    log: Option<File>,
+}
+
+impl Echo {
+    fn start(command: &str, source: CommandSource, view: OutputView, echo_stdout: bool, cwd: Option<&str>, timeout: Option<Duration>) -> Self {
+        let id = events::next_command_id();
+        events::emit(Event::CommandStarted {
+            id,
+            command: command.to_string(),
+            cwd: cwd.map(str::to_string),
+            source,
+            view,
+            echo_stdout,
+            timeout_secs: timeout.map(|t| t.as_secs()),
+        });
+
+        let mut log = open_run_log();
+        if let Some(file) = log.as_mut() {
+            let _ = writeln!(
+                file,
+                "\n[{}] $ {}  (cwd: {}, timeout: {})",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                command,
+                cwd.unwrap_or("."),
+                timeout.map(|t| format!("{}s", t.as_secs())).unwrap_or_else(|| "none".to_string())
+            );
+        }
+        Self { id, source, log }
+    }
+
+    fn line(&mut self, stream: OutputStream, line: &str) {
+        if let Some(file) = self.log.as_mut() {
+            let _ = writeln!(file, "{}| {}", if stream == OutputStream::Stdout { "out" } else { "err" }, line);
+        }
+        events::emit(Event::CommandOutput { id: self.id, stream, line: line.to_string() });
+    }
+
+    fn notice(&mut self, message: &str) {
+        if let Some(file) = self.log.as_mut() {
+            let _ = writeln!(file, "--- {}", message);
+        }
+        events::emit(Event::CommandOutput { id: self.id, stream: OutputStream::Notice, line: message.to_string() });
+    }
+
+    fn finish(&mut self, outcome: &CommandOutcome) {
+        if let Some(file) = self.log.as_mut() {
+            let exit = outcome.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string());
+            let _ = writeln!(
+                file,
+                "[{}] exit {}{} ({:.1}s)",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+                exit,
+                outcome.stopped().map(|s| format!(", {}", s)).unwrap_or_default(),
+                outcome.duration.as_secs_f64()
+            );
+        }
+        events::command_finished(Some(self.id), self.source, outcome);
+    }
+}
+
+// --- 📄 RUN LOG ---
+
+/// CLI Process တစ်ခု (Session) အတွက် Log File: `.neurust/logs/<timestamp>.log` (`[exec].log = false` ဆိုရင် None)
+pub fn run_log_path() -> Option<PathBuf> {
+    static PATH: OnceLock<Option<PathBuf>> = OnceLock::new();
+    PATH.get_or_init(|| {
+        let config = NeurustConfig::load().exec;
+        if !config.log {
+            return None;
+        }
+        let dir = Path::new(".neurust").join("logs");
+        fs::create_dir_all(&dir).ok()?;
+        prune_logs(&dir, config.keep_logs.saturating_sub(1));
+        Some(dir.join(format!("{}-{}.log", chrono::Local::now().format("%Y%m%d-%H%M%S"), std::process::id())))
+    })
+    .clone()
+}
+
+fn open_run_log() -> Option<File> {
+    OpenOptions::new().create(true).append(true).open(run_log_path()?).ok()
+}
+
+/// နောက်ဆုံး `keep` ခုပဲ ထားမယ် (File Name = Timestamp မို့ Sort လုပ်ရင် အဟောင်းက ရှေ့မှာ)
+fn prune_logs(dir: &Path, keep: usize) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    let mut logs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "log"))
+        .collect();
+    logs.sort();
+    let excess = logs.len().saturating_sub(keep);
+    for old in logs.into_iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+}
+
+// --- Internal Helpers ---
+
+/// Legacy Callers (solana / audit - User ကိုယ်တိုင် ရွေးတဲ့ Commands): Sandbox မသုံးဘဲ Run မယ်
+fn legacy_spec(program: &str, args: &[&str], cwd: Option<&str>) -> CommandSpec {
+    let mut spec = CommandSpec::new(program, args).unisolated().source(CommandSource::Setup);
+    if let Some(dir) = cwd {
+        spec = spec.cwd(dir);
+    }
+    spec
+}
+
+fn failure_message(outcome: &CommandOutcome) -> String {
+    if let Some(stopped) = outcome.stopped() {
+        return format!("`{}` {} after {:.1}s", outcome.command, stopped, outcome.duration.as_secs_f64());
+    }
+    let stderr = outcome.stderr.trim();
+    if stderr.is_empty() {
+        format!("`{}` failed (exit {})", outcome.command, outcome.exit_code.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string()))
+    } else {
+        stderr.to_string()
+    }
+}
+
+/// Heavy Command: Wait (true) / Skip (false)
+pub fn confirm_heavy(program: &str, args: &[&str], cwd: Option<&str>) -> bool {
+    println!("\n{} Heavy Task Detected: {} {}", "⚠️".yellow(), program, args.join(" "));
+    if let Some(dir) = cwd {
+        println!("   📂 In Directory: {}", dir);
+    }
+
+    let selections = &[
+        "⏳ Wait (Neurust will stream its output; Ctrl-C cancels just this command)",
+        "✋ Skip (I will run it manually later)",
+    ];
+    let command = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+    ci::confirm(Prompt::HeavyCommand, &command, || {
+        Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How do you want to proceed?")
+            .default(0)
+            .items(&selections[..])
+            .interact()
+            .unwrap_or(0)
+            == 0
+    })
+}
@@ -0,0 +1,380 @@
+use crate::utils::ci::Answer;
+use crate::utils::cmd::OutputView;
+use crate::utils::policy::Decision;
+use colored::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Neurust CLI Config
+/// ရှာမယ့်အစဉ်: `.neurust/config.toml` (Project) -> `~/.neurust/config.toml` (Global)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NeurustConfig {
+    #[serde(default)]
+    pub cost: CostConfig,
+    #[serde(default)]
+    pub agent: AgentConfig,
+    #[serde(default)]
+    pub verify: VerifyConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub sandbox: SandboxConfig,
+    #[serde(default)]
+    pub policy: PolicyConfig,
+    #[serde(default)]
+    pub exec: ExecConfig,
+    #[serde(default)]
+    pub auto: AutoConfig,
+    #[serde(default)]
+    pub lsp: LspConfig,
+}
+
+/// `[cost]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CostConfig {
+    /// Estimate (max) က ဒီ Credit ထက်ကျော်ရင် AI မခေါ်ခင် Confirm မေးမယ်
+    pub confirm_above: Option<i64>,
+    /// Default for `--max-cost` (Request တစ်ခုချင်းစီရဲ့ Credit ကန့်သတ်ချက်)
+    pub max_cost: Option<i64>,
+}
+
+/// `[agent]` section (Observation Loop)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AgentConfig {
+    /// Plan Run ပြီးရင် Command ရလဒ်တွေကို Model ဆီ ဘယ်နှစ်ကြိမ်အထိ ပြန်ပို့မလဲ (0 = ပိတ်)
+    pub max_observation_turns: usize,
+    /// Command အားလုံး အောင်မြင်ရင်လည်း Model ကို ပြန်စစ်ခိုင်းမလား
+    pub observe_success: bool,
+    /// Command တစ်ခုရဲ့ stdout/stderr ကို Model ဆီ ဘယ်လောက်အထိ ပို့မလဲ (chars)
+    pub max_output_chars: usize,
+    /// Fix Plan ထဲက Command ပြန် Fail ရင် ထပ် Heal မယ့် အဆင့် (Nested Depth)
+    pub max_healing_depth: usize,
+    /// Fail ဖြစ်တဲ့ Command တစ်ခုအတွက် Fix ဘယ်နှစ်ခါ စမ်းမလဲ
+    pub max_healing_attempts: usize,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            max_observation_turns: 3,
+            observe_success: false,
+            max_output_chars: 6_000,
+            max_healing_depth: 2,
+            max_healing_attempts: 3,
+        }
+    }
+}
+
+/// `[verify]` section (Plan Apply ပြီးတိုင်း Run မယ့် Verification Pipeline)
+///
+/// ```toml
+/// [verify]
+/// steps = ["cargo check", "cargo clippy -- -D warnings"]  # မထည့်ရင် Stack ကနေ ရွေးမယ်
+/// feed_back = "ask"                                       # "ask" | "always" | "never"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct VerifyConfig {
+    pub enabled: bool,
+    /// Command Lines (Whitespace နဲ့ ခွဲမယ်၊ Quote မထောက်ပံ့ဘူး)
+    pub steps: Option<Vec<String>>,
+    /// Verification Fail ရင် Agent ဆီ ပြန်ပို့မလား
+    pub feed_back: FeedBack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedBack {
+    Ask,
+    Always,
+    Never,
+}
+
+impl Default for VerifyConfig {
+    fn default() -> Self {
+        Self { enabled: true, steps: None, feed_back: FeedBack::Ask }
+    }
+}
+
+/// `[history]` section (Plan Run တိုင်း File Snapshot ယူမယ် - `neurust undo`)
+///
+/// ```toml
+/// [history]
+/// keep = 50             # သိမ်းထားမယ့် Run အရေအတွက်
+/// auto_rollback = true  # Verification Fail ပြီး Agent ဆီ ဆက်မပို့တော့ရင် Run တွေကို ပြန် Undo မယ်
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    pub enabled: bool,
+    pub keep: usize,
+    pub auto_rollback: bool,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { enabled: true, keep: 50, auto_rollback: false }
+    }
+}
+
+/// `[sandbox]` section
+/// - `protected`: Agent က ရေးခွင့်မရှိတဲ့ Paths (Project Root ကနေ Glob)
+///   `.neurust/config.toml` က Override လုပ်ထားလည်း အမြဲ Protected (Guard တွေကို Agent က ပိတ်လို့မရအောင်)
+/// - `exec` ...: Command တွေကို bubblewrap ထဲမှာ Run မယ် (Linux, `--sandbox` နဲ့လည်း ဖွင့်လို့ရ)
+/// - `memory_mb`: Virtual Memory (`ulimit -v`) Limit ဖြစ်လို့ Process သုံးတဲ့ RAM မဟုတ်ဘူး။ node / rustc က Address Space
+///   အများကြီး Reserve လုပ်လို့ 4096 အောက်ဆိုရင် Allocation Error တက်တတ်တယ် (8192+ ထားပါ)
+///
+/// ```toml
+/// [sandbox]
+/// protected = [".git", ".env", "**/target/deploy/*-keypair.json", "**/migrations"]  # Default ကို အစားထိုးမယ်
+/// exec = true
+/// network = false         # Sandbox ထဲမှာ Network ပိတ်မယ်
+/// writable = ["/data/x"]  # Project + cargo/npm Caches အပြင် Write ခွင့်
+/// cpu_secs = 600
+/// memory_mb = 8192       # ulimit -v (Virtual Memory, RSS မဟုတ်)
+/// timeout_secs = 900
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SandboxConfig {
+    pub protected: Vec<String>,
+    pub exec: bool,
+    pub network: bool,
+    pub writable: Vec<String>,
+    pub cpu_secs: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub timeout_secs: u64,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            protected: [
+                ".git",
+                ".env",
+                ".env.*",
+                "**/target/deploy/*-keypair.json",
+                "**/migrations",
+                ".neurust/history",
+                ".neurust/config.toml",
+            ]
+            .iter()
+            .map(|p| p.to_string())
+            .collect(),
+            exec: false,
+            network: true,
+            writable: Vec::new(),
+            cpu_secs: None,
+            memory_mb: None,
+            timeout_secs: 900,
+        }
+    }
+}
+
+/// `[policy]` section (`run_cmd` ကို မ Run ခင် စစ်မယ့် Rules)
+///
+/// ```toml
+/// [policy]
+/// network = "confirm"       # npm install / cargo add / curl ... ("allow" | "confirm" | "deny")
+///
+/// [[policy.rules]]          # ပထမဆုံး ကိုက်တဲ့ Rule က Built-in Classification ကို Override လုပ်မယ် (Built-in Deny မှအပ)
+/// program = "rm"            # "*" = Program အားလုံး
+/// args = "^-rf? target$"    # Space နဲ့ ဆက်ထားတဲ့ Args ပေါ်က Regex (မထည့်ရင် Args အားလုံး)
+/// decision = "allow"        # "allow" | "confirm" | "deny"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    pub network: Decision,
+    pub rules: Vec<PolicyRule>,
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self { network: Decision::Allow, rules: Vec::new() }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyRule {
+    pub program: String,
+    pub args: Option<String>,
+    pub decision: Decision,
+}
+
+/// `[exec]` section (Process Runner - `utils::cmd`)
+///
+/// ```toml
+/// [exec]
+/// output = "collapsed"              # "live" (Default) | "collapsed" (Spinner + နောက်ဆုံး Line) | "quiet"
+/// timeout_secs = 1800               # Command တစ်ခုချင်းစီရဲ့ Default Timeout (0 = မကန့်သတ်)
+/// max_output_kb = 4096              # stdout/stderr တစ်ခုချင်းစီ သိမ်းမယ့် အများဆုံး (အလယ်ကို ဖြတ်မယ်)
+/// log = true                        # Output အကုန်ကို `.neurust/logs/` ထဲ Tee လုပ်မယ်
+/// keep_logs = 20
+/// env = { RUST_BACKTRACE = "1" }    # Command တိုင်းမှာ ထည့်မယ့် Variables
+/// env_remove = ["AWS_SECRET_ACCESS_KEY"]
+/// clear_env = true                  # `env_passthrough` + `env` ပဲ ပေးမယ်
+///
+/// [exec.timeouts]                   # Command Prefix အလိုက် (အရှည်ဆုံး ကိုက်တာကို ယူမယ်)
+/// "anchor build" = 3600
+/// "npm install" = 600
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ExecConfig {
+    pub output: OutputView,
+    pub timeout_secs: u64,
+    pub timeouts: BTreeMap<String, u64>,
+    pub max_output_kb: usize,
+    pub log: bool,
+    pub keep_logs: usize,
+    pub env: BTreeMap<String, String>,
+    pub env_remove: Vec<String>,
+    pub clear_env: bool,
+    pub env_passthrough: Vec<String>,
+}
+
+impl Default for ExecConfig {
+    fn default() -> Self {
+        Self {
+            output: OutputView::Live,
+            timeout_secs: 1800,
+            timeouts: BTreeMap::new(),
+            max_output_kb: 4096,
+            log: true,
+            keep_logs: 20,
+            env: BTreeMap::new(),
+            env_remove: Vec::new(),
+            clear_env: false,
+            env_passthrough: [
+                "PATH", "HOME", "USER", "LANG", "LC_ALL", "TERM", "TMPDIR", "SHELL",
+                "CARGO_HOME", "RUSTUP_HOME", "RUSTUP_TOOLCHAIN", "CARGO_TARGET_DIR", "NVM_DIR", "SSL_CERT_FILE",
+            ]
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+        }
+    }
+}
+
+impl ExecConfig {
+    /// `[exec.timeouts]` ထဲက အရှည်ဆုံး ကိုက်တဲ့ Prefix (မရှိရင် `timeout_secs`)၊ 0 = None
+    pub fn timeout_for(&self, command: &str) -> Option<Duration> {
+        let secs = self
+            .timeouts
+            .iter()
+            .filter(|(prefix, _)| command == prefix.as_str() || command.starts_with(&format!("{} ", prefix)))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, secs)| *secs)
+            .unwrap_or(self.timeout_secs);
+        (secs > 0).then(|| Duration::from_secs(secs))
+    }
+}
+
+/// `[auto]` section (`--yes` / `--non-interactive` မှာ Prompt တွေကို ဒီအတိုင်း ဖြေမယ်)
+/// `--yes` မပါဘဲ stdin က TTY မဟုတ်ရင် Prompt အားလုံးကို "no" လို့ ဖြေမယ်
+///
+/// ```toml
+/// [auto]
+/// writes = "yes"          # create_file / apply_patch
+/// deletes = "no"          # delete_file (Overwrite လုပ်မယ့် rename လည်း)
+/// renames = "yes"
+/// redirects = "no"        # Path မရှိလို့ နာမည်တူ File ဆီ ရေးမလား
+/// heavy_commands = "yes"  # cargo build / anchor test ... ကို စောင့် Run မလား
+/// risky_commands = "no"   # Command Policy က "confirm" ပေးတဲ့ Commands
+/// feed_back = "yes"       # [verify].feed_back = "ask" ဆိုရင်
+/// send_tests = "no"       # `neurust test` Failures ကို Agent ဆီ ပို့မလား (`--fix` = yes)
+/// over_budget = "no"      # [cost].confirm_above ကျော်တဲ့ Request
+/// undo = "yes"            # Run ပြီးနောက် ပြောင်းထားတဲ့ File ရှိရင် Undo ကို အမြဲ ငြင်းမယ်
+/// reply = "Proceed with your best judgement."  # Agent ရဲ့ မေးခွန်းတွေကို ဖြေမယ့်စာ (မထည့်ရင် Conversation ရပ်မယ်)
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AutoConfig {
+    pub writes: Answer,
+    pub deletes: Answer,
+    pub renames: Answer,
+    pub redirects: Answer,
+    pub heavy_commands: Answer,
+    pub risky_commands: Answer,
+    pub feed_back: Answer,
+    pub send_tests: Answer,
+    pub over_budget: Answer,
+    pub undo: Answer,
+    pub reply: Option<String>,
+}
+
+impl Default for AutoConfig {
+    fn default() -> Self {
+        Self {
+            writes: Answer::Yes,
+            deletes: Answer::No,
+            renames: Answer::Yes,
+            redirects: Answer::No,
+            heavy_commands: Answer::Yes,
+            risky_commands: Answer::No,
+            feed_back: Answer::Yes,
+            send_tests: Answer::No,
+            over_budget: Answer::No,
+            undo: Answer::Yes,
+            reply: None,
+        }
+    }
+}
+
+/// `[lsp]` section (`neurust lsp`)
+///
+/// ```toml
+/// [lsp]
+/// anchor_checks = true   # Anchor Programs ကို Local Rules နဲ့ စစ်မယ် (Credit မကုန်)
+/// audit_on_save = false  # Save တိုင်း Auditor Brain ကို ခေါ်မယ် (Credit ကုန်တယ်၊ `neurust.audit` Command နဲ့လည်း ရတယ်)
+/// model = "openai/gpt-5.1-codex-mini"  # Code Actions / Audit အတွက် (မထည့်ရင် Server Smart Routing)
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LspConfig {
+    pub anchor_checks: bool,
+    pub audit_on_save: bool,
+    pub model: Option<String>,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        Self { anchor_checks: true, audit_on_save: false, model: None }
+    }
+}
+
+impl NeurustConfig {
+    fn project_path() -> PathBuf {
+        Path::new(".neurust").join("config.toml")
+    }
+
+    fn global_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".neurust").join("config.toml"))
+    }
+
+    /// Config ကို ဖတ်မယ် (မရှိရင် Default, Parse Error ဆိုရင် သတိပေးပြီး Default)
+    pub fn load() -> Self {
+        let candidates = std::iter::once(Self::project_path()).chain(Self::global_path());
+
+        for path in candidates {
+            if !path.exists() {
+                continue;
+            }
+            match fs::read_to_string(&path).map(|content| toml::from_str::<NeurustConfig>(&content)) {
+                Ok(Ok(config)) => return config,
+                Ok(Err(e)) => {
+                    println!("{} Invalid config at {}: {}", "⚠️".yellow(), path.display(), e);
+                    return Self::default();
+                }
+                Err(_) => continue,
+            }
+        }
+
+        Self::default()
+    }
+}
@@ -2,6 +2,22 @@ use std::fs;
 use std::path::Path;
 use std::env;
 
+/// Project Root မှာ တွေ့တဲ့ Stack (Verification Pipeline က Step ရွေးဖို့ သုံးမယ်)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stack {
+    Rust,
+    Anchor,
+    Node,
+}
+
+pub fn detect_stacks() -> Vec<Stack> {
+    let mut stacks = Vec::new();
+    if Path::new("Cargo.toml").exists() { stacks.push(Stack::Rust); }
+    if Path::new("Anchor.toml").exists() { stacks.push(Stack::Anchor); }
+    if Path::new("package.json").exists() { stacks.push(Stack::Node); }
+    stacks
+}
+
 pub fn get_project_context() -> String {
     let mut context = String::from("\n--- CURRENT WORKING ENVIRONMENT ---\n");
     let current_dir = env::current_dir().unwrap_or_default();
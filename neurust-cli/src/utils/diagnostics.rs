@@ -0,0 +1,645 @@
+use crate::utils::cmd::{self, CommandOutcome};
+use crate::utils::diff::{self, ConfirmAction};
+use crate::utils::events::{self, CommandSource, FileChange};
+use crate::utils::history::Transaction;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+
+// --- 🩺 CARGO DIAGNOSTICS ---
+// `cargo check/build/clippy --message-format=json` ကို Parse လုပ်ပြီး Error စာသားအကြမ်းအစား
+// File အလိုက် စုထားတဲ့ Structured Diagnostics (Code, Spans, Snippets, Suggestions) ပေးမယ်။
+// MachineApplicable Suggestions တွေကို AI မမေးဘဲ တိုက်ရိုက် Apply လုပ်လို့ရတယ်။
+
+/// Diagnostics ထုတ်ပေးနိုင်တဲ့ Cargo Subcommands
+const DIAGNOSTIC_SUBCOMMANDS: &[&str] = &["check", "build", "clippy"];
+
+/// Prompt ထဲ ထည့်မယ့် Diagnostic အများဆုံး အရေအတွက်
+const MAX_PROMPT_DIAGNOSTICS: usize = 40;
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// "error" | "warning" | ...
+    pub level: String,
+    /// e.g. "E0308", "clippy::needless_return"
+    pub code: Option<String>,
+    pub message: String,
+    pub spans: Vec<Span>,
+    /// Child `note:` / `help:` Messages
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+    /// rustc ရဲ့ Human-readable Output
+    pub rendered: String,
+}
+
+impl Diagnostic {
+    pub fn primary_span(&self) -> Option<&Span> {
+        self.spans.iter().find(|s| s.is_primary).or(self.spans.first())
+    }
+
+    /// Grouping Key (Span မရှိရင် "(crate)")
+    pub fn file(&self) -> String {
+        self.primary_span().map(|s| s.file.clone()).unwrap_or_else(|| "(crate)".to_string())
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level.starts_with("error")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Span {
+    pub file: String,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub file: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub replacement: String,
+    /// "MachineApplicable" | "MaybeIncorrect" | "HasPlaceholders" | "Unspecified"
+    pub applicability: String,
+    pub message: String,
+    /// Multi-part Suggestion တစ်ခုရဲ့ အပိုင်းတွေက Group တူတယ် (အကုန် Apply / ဘာမှ မ Apply)
+    pub group: usize,
+}
+
+impl Suggestion {
+    pub fn is_machine_applicable(&self) -> bool {
+        self.applicability == "MachineApplicable"
+    }
+
+    /// Edit ကိုယ်တိုင် (Message / Group မပါ) - ထပ်နေတာ စစ်ဖို့
+    fn edit_key(&self) -> (&str, usize, usize, &str) {
+        (&self.file, self.byte_start, self.byte_end, &self.replacement)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticReport {
+    pub diagnostics: Vec<Diagnostic>,
+    /// `build-finished` Message ရဲ့ `success`
+    pub success: bool,
+}
+
+impl DiagnosticReport {
+    pub fn errors(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.is_error()).count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.diagnostics.iter().filter(|d| d.level == "warning").count()
+    }
+
+    /// `cargo check` + `cargo clippy` လို Report နှစ်ခုကို ပေါင်းမယ် (ထပ်နေတာ ဖယ်မယ်)
+    pub fn merge(&mut self, other: DiagnosticReport) {
+        self.success = self.success && other.success;
+        for diagnostic in other.diagnostics {
+            if !self.diagnostics.iter().any(|d| d.rendered == diagnostic.rendered) {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    /// File အလိုက် စုမယ် (Path အစဉ်)
+    pub fn by_file(&self) -> BTreeMap<String, Vec<&Diagnostic>> {
+        let mut grouped: BTreeMap<String, Vec<&Diagnostic>> = BTreeMap::new();
+        for diagnostic in &self.diagnostics {
+            grouped.entry(diagnostic.file()).or_default().push(diagnostic);
+        }
+        grouped
+    }
+
+    /// Cargo ရဲ့ `file_name` (Workspace Root ကနေ Relative) -> `cwd` ကနေ Relative (Root အပြင်ဆိုရင် Absolute)
+    pub fn relocate(&mut self, root: &Path, cwd: &Path) {
+        let resolve = |file: &mut String| {
+            let path = root.join(&*file);
+            *file = path.strip_prefix(cwd).unwrap_or(&path).to_string_lossy().to_string();
+        };
+        for diagnostic in &mut self.diagnostics {
+            diagnostic.spans.iter_mut().for_each(|s| resolve(&mut s.file));
+            diagnostic.suggestions.iter_mut().for_each(|s| resolve(&mut s.file));
+        }
+    }
+
+    /// Report တစ်ခုလုံးမှာ Group ID ကို Unique ဖြစ်အောင် ပြန်နံပါတ်တပ်မယ်
+    /// `cargo check` + `clippy` က Edit တူတဲ့ Suggestion ကို နှစ်ခါ ပေးတတ်လို့ Edit Set တူတဲ့ Group ကို တစ်ခုပဲ ထားမယ်
+    pub fn machine_applicable(&self) -> Vec<Suggestion> {
+        let mut groups: Vec<Vec<Suggestion>> = Vec::new();
+        for diagnostic in &self.diagnostics {
+            let mut local: BTreeMap<usize, Vec<Suggestion>> = BTreeMap::new();
+            for suggestion in diagnostic.suggestions.iter().filter(|s| s.is_machine_applicable()) {
+                local.entry(suggestion.group).or_default().push(suggestion.clone());
+            }
+            groups.extend(local.into_values());
+        }
+
+        let mut seen = HashSet::new();
+        let mut suggestions = Vec::new();
+        for (group, mut parts) in groups.into_iter().enumerate() {
+            parts.sort_by(|a, b| a.edit_key().cmp(&b.edit_key()));
+            parts.dedup_by(|a, b| a.edit_key() == b.edit_key());
+            let key: Vec<_> = parts.iter().map(|s| (s.file.clone(), s.byte_start, s.byte_end, s.replacement.clone())).collect();
+            if seen.insert(key) {
+                suggestions.extend(parts.into_iter().map(|s| Suggestion { group, ..s }));
+            }
+        }
+        suggestions
+    }
+
+    /// Agent ဆီ ပို့မယ့် Text: File အလိုက် Diagnostics + Span ဝန်းကျင် Source Snippets
+    pub fn to_prompt(&self, context_lines: usize) -> String {
+        if self.diagnostics.is_empty() {
+            return if self.success { "No diagnostics.".to_string() } else { "Build failed without diagnostics.".to_string() };
+        }
+
+        let mut out = format!("{} error(s), {} warning(s)\n", self.errors(), self.warnings());
+        let mut shown = 0;
+
+        for (file, diagnostics) in self.by_file() {
+            let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+            out.push_str(&format!("\n=== {} ({} error(s), {} warning(s)) ===\n", file, errors, diagnostics.len() - errors));
+            let source = fs::read_to_string(&file).ok();
+
+            for diagnostic in diagnostics {
+                if shown == MAX_PROMPT_DIAGNOSTICS {
+                    out.push_str(&format!("\n[... {} more diagnostics omitted ...]\n", self.diagnostics.len() - shown));
+                    return out;
+                }
+                shown += 1;
+
+                let code = diagnostic.code.as_deref().map(|c| format!("[{}]", c)).unwrap_or_default();
+                out.push_str(&format!("\n{}{}: {}\n", diagnostic.level, code, diagnostic.message));
+
+                if let Some(span) = diagnostic.primary_span() {
+                    out.push_str(&format!("  --> {}:{}:{}\n", span.file, span.line_start, span.column_start));
+                    if let Some(source) = &source {
+                        out.push_str(&snippet(source, diagnostic, span, context_lines));
+                    }
+                }
+                for note in &diagnostic.notes {
+                    out.push_str(&format!("  = {}\n", note));
+                }
+                for suggestion in &diagnostic.suggestions {
+                    out.push_str(&format!(
+                        "  = suggestion ({}) at line {}: replace with `{}`\n",
+                        suggestion.applicability, suggestion.line_start, suggestion.replacement
+                    ));
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Span Lines ± `context_lines` (Primary Span Lines ကို `>` နဲ့ ပြမယ်)
+fn snippet(source: &str, diagnostic: &Diagnostic, span: &Span, context_lines: usize) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let first = span.line_start.saturating_sub(context_lines + 1);
+    let last = (span.line_end + context_lines).min(lines.len());
+    let width = last.to_string().len();
+
+    let mut out = String::new();
+    for (index, line) in lines.iter().enumerate().take(last).skip(first) {
+        let number = index + 1;
+        let marked = diagnostic
+            .spans
+            .iter()
+            .any(|s| s.file == span.file && (s.line_start..=s.line_end).contains(&number));
+        out.push_str(&format!("{} {:>width$} | {}\n", if marked { ">" } else { " " }, number, line, width = width));
+    }
+    if let Some(label) = &span.label {
+        out.push_str(&format!("{}^ {}\n", " ".repeat(width + 5), label));
+    }
+    out
+}
+
+/// `cargo check` / `cargo clippy ...` လား (Program + Args)
+pub fn is_cargo_diagnostic(program: &str, args: &[&str]) -> bool {
+    program == "cargo" && args.first().is_some_and(|sub| DIAGNOSTIC_SUBCOMMANDS.contains(sub))
+}
+
+/// Cargo Run တစ်ခုရဲ့ ရလဒ်
+/// `outcome.stdout` ကို JSON အစား Structured Report (`to_prompt`) နဲ့ အစားထိုးထားမယ်
+pub struct CargoRun {
+    pub outcome: CommandOutcome,
+    pub report: DiagnosticReport,
+}
+
+/// `cargo <args> --message-format=json` (Clippy ရဲ့ `-- -D warnings` လို Args တွေကို `--` ရှေ့မှာ ထည့်မယ်)
+/// `source` = Plan ဆိုရင် Terminal က Plan Command လို ရလဒ်ပြမယ် (Healing Re-run)
+pub async fn run_cargo(args: &[&str], source: CommandSource) -> CargoRun {
+    let split = args.iter().position(|a| *a == "--").unwrap_or(args.len());
+    let mut json_args: Vec<&str> = args[..split].to_vec();
+    json_args.push("--message-format=json");
+    json_args.extend_from_slice(&args[split..]);
+
+    let mut outcome = cmd::CommandSpec::new("cargo", &json_args).hide_stdout().source(source).run().await;
+    let mut report = parse_messages(&outcome.stdout);
+    if let (Some(root), Ok(cwd)) = (workspace_root().await, std::env::current_dir()) {
+        report.relocate(&root, &cwd);
+    }
+
+    // Observation/Healing Key တွေ မပြောင်းအောင် User မြင်တဲ့ Command နာမည်ကိုပဲ သုံးမယ်
+    outcome.command = std::iter::once("cargo").chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+    outcome.stdout = report.to_prompt(2);
+    CargoRun { outcome, report }
+}
+
+/// `cargo metadata` ရဲ့ `workspace_root` (Sub-crate ထဲကနေ Run ရင် cwd နဲ့ မတူဘူး)
+async fn workspace_root() -> Option<std::path::PathBuf> {
+    let output = tokio::process::Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .stderr(std::process::Stdio::null())
+        .output()
+        .await
+        .ok()?;
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    metadata["workspace_root"].as_str().map(std::path::PathBuf::from)
+}
+
+#[derive(Deserialize)]
+struct RawLine {
+    reason: String,
+    message: Option<RawMessage>,
+    success: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct RawMessage {
+    level: String,
+    message: String,
+    code: Option<RawCode>,
+    #[serde(default)]
+    spans: Vec<RawSpan>,
+    #[serde(default)]
+    children: Vec<RawMessage>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct RawSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: usize,
+    line_end: usize,
+    column_start: usize,
+    is_primary: bool,
+    label: Option<String>,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+/// Cargo JSON Lines -> Report (Target တစ်ခုထက်ပိုရင် ထပ်နေတဲ့ Diagnostic တွေကို ဖယ်မယ်)
+pub fn parse_messages(stdout: &str) -> DiagnosticReport {
+    let mut report = DiagnosticReport::default();
+
+    for line in stdout.lines().filter(|l| l.starts_with('{')) {
+        let Ok(raw) = serde_json::from_str::<RawLine>(line) else { continue };
+        match raw.reason.as_str() {
+            "build-finished" => report.success = raw.success.unwrap_or(false),
+            "compiler-message" => {
+                let Some(message) = raw.message else { continue };
+                if is_summary_message(&message) {
+                    continue;
+                }
+                let diagnostic = convert(message);
+                if !report.diagnostics.iter().any(|d| d.rendered == diagnostic.rendered) {
+                    report.diagnostics.push(diagnostic);
+                }
+            }
+            _ => {}
+        }
+    }
+    report
+}
+
+/// "aborting due to 2 previous errors" / "3 warnings emitted" လို Span မရှိတဲ့ Summary တွေ
+fn is_summary_message(message: &RawMessage) -> bool {
+    message.spans.is_empty()
+        && (message.message.starts_with("aborting due to")
+            || message.message.ends_with("emitted")
+            || message.level == "failure-note")
+}
+
+fn convert(message: RawMessage) -> Diagnostic {
+    // Message တစ်ခု (Top-level / Child) = Suggestion တစ်ခု (Span အများကြီး ပါနိုင်)
+    let mut suggestions = Vec::new();
+    collect_suggestions(&message.message, &message.spans, 0, &mut suggestions);
+    for (index, child) in message.children.iter().enumerate() {
+        collect_suggestions(&child.message, &child.spans, index + 1, &mut suggestions);
+    }
+
+    Diagnostic {
+        level: message.level,
+        code: message.code.map(|c| c.code),
+        message: message.message,
+        spans: message
+            .spans
+            .iter()
+            .map(|s| Span {
+                file: s.file_name.clone(),
+                line_start: s.line_start,
+                line_end: s.line_end,
+                column_start: s.column_start,
+                is_primary: s.is_primary,
+                label: s.label.clone(),
+            })
+            .collect(),
+        notes: message.children.iter().map(|c| format!("{}: {}", c.level, c.message)).collect(),
+        suggestions,
+        rendered: message.rendered.unwrap_or_default(),
+    }
+}
+
+fn collect_suggestions(message: &str, spans: &[RawSpan], group: usize, out: &mut Vec<Suggestion>) {
+    for span in spans {
+        if let Some(replacement) = &span.suggested_replacement {
+            out.push(Suggestion {
+                file: span.file_name.clone(),
+                byte_start: span.byte_start,
+                byte_end: span.byte_end,
+                line_start: span.line_start,
+                replacement: replacement.clone(),
+                applicability: span.suggestion_applicability.clone().unwrap_or_else(|| "Unspecified".to_string()),
+                message: message.to_string(),
+                group,
+            });
+        }
+    }
+}
+
+/// Suggestions တွေကို File အလိုက် Apply လုပ်ပြီး `(path, old, new)` ပြန်ပေးမယ် (Disk ကို မရေးဘူး)
+/// Group (Multi-part Suggestion) တစ်ခုလုံးကို လက်ခံ (သို့) ငြင်းမယ်: အပိုင်းတစ်ခုက Stale / လက်ခံပြီးသား Edit နဲ့ ထပ်နေရင်
+/// Group တစ်ခုလုံး ကျော်မယ် (တစ်ဝက်ပဲ Apply လို့ Compile မရတော့တာ မဖြစ်အောင်)။ Edit တူတာကိုတော့ ထပ်မ Apply ဘဲ လက်ခံမယ်။
+pub fn preview_suggestions(suggestions: &[Suggestion]) -> Result<Vec<(String, String, String)>> {
+    let mut groups: BTreeMap<usize, Vec<&Suggestion>> = BTreeMap::new();
+    for suggestion in suggestions {
+        groups.entry(suggestion.group).or_default().push(suggestion);
+    }
+
+    let mut originals: BTreeMap<&str, String> = BTreeMap::new();
+    let mut accepted: BTreeMap<&str, Vec<&Suggestion>> = BTreeMap::new();
+    for parts in groups.values() {
+        for part in parts {
+            if !originals.contains_key(part.file.as_str()) {
+                let content = fs::read_to_string(&part.file).with_context(|| format!("Failed to read {}", part.file))?;
+                originals.insert(part.file.as_str(), content);
+            }
+        }
+
+        let mut fresh: Vec<&Suggestion> = Vec::new();
+        let fits = parts.iter().all(|part| {
+            let original = &originals[part.file.as_str()];
+            if part.byte_start > part.byte_end
+                || part.byte_end > original.len()
+                || !original.is_char_boundary(part.byte_start)
+                || !original.is_char_boundary(part.byte_end)
+            {
+                return false; // Stale Span
+            }
+            let taken = accepted.get(part.file.as_str()).into_iter().flatten().chain(fresh.iter().filter(|f| f.file == part.file));
+            let mut duplicate = false;
+            for other in taken {
+                if other.edit_key() == part.edit_key() {
+                    duplicate = true;
+                } else if (part.byte_start < other.byte_end && other.byte_start < part.byte_end)
+                    || (part.byte_start == part.byte_end && part.byte_start == other.byte_start)
+                {
+                    return false; // Overlap (Insertion နှစ်ခု နေရာတူ ဆိုရင်လည်း အစဉ်မသေချာလို့ ငြင်းမယ်)
+                }
+            }
+            if !duplicate {
+                fresh.push(part);
+            }
+            true
+        });
+
+        if fits {
+            for part in fresh {
+                accepted.entry(part.file.as_str()).or_default().push(part);
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (file, mut edits) in accepted {
+        let original = originals.remove(file).unwrap_or_default();
+        let mut updated = original.clone();
+        // နောက်ဆုံး Byte ကနေ ရှေ့ကို Apply လုပ်မယ် (ရှေ့က Offset တွေ မပြောင်းအောင်)
+        edits.sort_by_key(|s| std::cmp::Reverse((s.byte_start, s.byte_end)));
+        for edit in edits {
+            updated.replace_range(edit.byte_start..edit.byte_end, &edit.replacement);
+        }
+        if updated != original {
+            changes.push((file.to_string(), original, updated));
+        }
+    }
+    Ok(changes)
+}
+
+/// Group အလိုက် စစ်မယ်: အပိုင်းတစ်ခုက `keep` မဖြစ်ရင် Group တစ်ခုလုံး ဖယ်မယ်
+pub fn retain_groups(suggestions: &mut Vec<Suggestion>, mut keep: impl FnMut(&Suggestion) -> bool) {
+    let rejected: HashSet<usize> = suggestions.iter().filter(|s| !keep(s)).map(|s| s.group).collect();
+    suggestions.retain(|s| !rejected.contains(&s.group));
+}
+
+/// File မျှဝေတဲ့ Groups တွေကို စုမယ် (Group တစ်ခုက File နှစ်ခု ဖြတ်သွားရင် နှစ်ခုလုံး အတူ လက်ခံ/ငြင်းရမယ်)
+pub fn file_clusters(suggestions: &[Suggestion]) -> Vec<Vec<Suggestion>> {
+    let mut clusters: Vec<(BTreeSet<String>, Vec<Suggestion>)> = Vec::new();
+    let mut groups: BTreeMap<usize, Vec<Suggestion>> = BTreeMap::new();
+    for suggestion in suggestions {
+        groups.entry(suggestion.group).or_default().push(suggestion.clone());
+    }
+
+    for parts in groups.into_values() {
+        let mut files: BTreeSet<String> = parts.iter().map(|s| s.file.clone()).collect();
+        let mut members = parts;
+        // File တူတဲ့ Cluster အဟောင်းတွေကို ပေါင်းမယ်
+        let (touching, rest): (Vec<_>, Vec<_>) = clusters.into_iter().partition(|(f, _)| !f.is_disjoint(&files));
+        for (f, m) in touching {
+            files.extend(f);
+            members.extend(m);
+        }
+        clusters = rest;
+        clusters.push((files, members));
+    }
+    clusters.into_iter().map(|(_, members)| members).collect()
+}
+
+/// Diff ပြပြီး (`[auto].writes` / Editor Responder) Cluster အလိုက် Apply မယ် -> (Applied Groups, Files)
+/// ရေးမယ့် File တိုင်းကို `tx` ထဲ Snapshot ယူမယ် (`neurust undo` နဲ့ ပြန်ရအောင်)
+pub fn apply_confirmed(
+    suggestions: &[Suggestion],
+    always_allow: &mut bool,
+    tx: &mut Transaction,
+) -> Result<(usize, Vec<String>)> {
+    let (mut applied, mut files) = (0, Vec::new());
+
+    for cluster in file_clusters(suggestions) {
+        let changes = preview_suggestions(&cluster)?;
+        if changes.is_empty() {
+            continue;
+        }
+        let count = cluster.iter().map(|s| s.group).collect::<BTreeSet<_>>().len();
+        let reason = format!("{} machine-applicable compiler/clippy suggestion(s)", count);
+
+        let accept = *always_allow
+            || match diff::show_diffs_and_confirm(&changes, &reason) {
+                ConfirmAction::Yes => true,
+                ConfirmAction::No => false,
+                ConfirmAction::All => {
+                    *always_allow = true;
+                    true
+                }
+            };
+        if !accept {
+            continue;
+        }
+
+        for (file, _, _) in &changes {
+            tx.snapshot(file)?;
+        }
+        for (file, _, updated) in changes {
+            fs::write(&file, updated).with_context(|| format!("Failed to write {}", file))?;
+            events::file_written(&file, FileChange::Fixed);
+            files.push(file);
+        }
+        applied += count;
+    }
+    Ok((applied, files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn span(file: &str, start: usize, end: usize, replacement: Option<&str>, applicability: &str) -> serde_json::Value {
+        json!({
+            "file_name": file, "byte_start": start, "byte_end": end, "line_start": 1, "line_end": 1, "column_start": start + 1,
+            "is_primary": replacement.is_none(), "label": null,
+            "suggested_replacement": replacement, "suggestion_applicability": replacement.map(|_| applicability),
+        })
+    }
+
+    fn message_line(rendered: &str, children: serde_json::Value) -> String {
+        json!({
+            "reason": "compiler-message",
+            "message": {
+                "level": "warning", "message": "unused variable", "code": { "code": "unused_variables" },
+                "spans": [span("src/lib.rs", 4, 5, None, "")], "children": children, "rendered": rendered,
+            },
+        })
+        .to_string()
+    }
+
+    fn suggestion(file: &str, start: usize, group: usize) -> Suggestion {
+        Suggestion {
+            file: file.to_string(),
+            byte_start: start,
+            byte_end: start + 1,
+            line_start: 1,
+            replacement: "_x".to_string(),
+            applicability: "MachineApplicable".to_string(),
+            message: String::new(),
+            group,
+        }
+    }
+
+    fn two_part_fix() -> serde_json::Value {
+        json!([
+            { "level": "help", "message": "rename both", "spans": [
+                span("src/lib.rs", 4, 5, Some("_x"), "MachineApplicable"),
+                span("src/lib.rs", 20, 21, Some("_x"), "MachineApplicable"),
+            ] },
+            { "level": "help", "message": "or remove it", "spans": [span("src/lib.rs", 0, 10, Some(""), "MaybeIncorrect")] },
+        ])
+    }
+
+    #[test]
+    fn parses_child_suggestions_into_groups() {
+        let stdout = [
+            message_line("warning: unused variable", two_part_fix()),
+            message_line("warning: unused variable", two_part_fix()),
+            json!({ "reason": "compiler-message", "message": { "level": "warning", "message": "1 warning emitted", "spans": [] } }).to_string(),
+            json!({ "reason": "build-finished", "success": true }).to_string(),
+        ]
+        .join("\n");
+
+        let report = parse_messages(&stdout);
+        assert!(report.success);
+        assert_eq!(report.diagnostics.len(), 1);
+        let suggestions = &report.diagnostics[0].suggestions;
+        assert_eq!(suggestions.len(), 3);
+        assert_eq!(suggestions.iter().filter(|s| s.group == 1).count(), 2);
+        assert_eq!(suggestions.iter().filter(|s| s.is_machine_applicable()).count(), 2);
+    }
+
+    #[test]
+    fn machine_applicable_drops_groups_repeated_by_clippy() {
+        let mut report = parse_messages(&message_line("warning: unused variable (check)", two_part_fix()));
+        report.merge(parse_messages(&message_line("warning: unused variable (clippy)", two_part_fix())));
+        assert_eq!(report.diagnostics.len(), 2);
+
+        let suggestions = report.machine_applicable();
+        assert_eq!(suggestions.len(), 2);
+        assert!(suggestions.iter().all(|s| s.group == suggestions[0].group));
+    }
+
+    #[test]
+    fn retain_groups_drops_the_whole_group() {
+        let mut suggestions = vec![suggestion("src/a.rs", 0, 0), suggestion("src/b.rs", 0, 0), suggestion("src/a.rs", 5, 1)];
+        retain_groups(&mut suggestions, |s| s.file != "src/b.rs");
+        assert_eq!(suggestions, vec![suggestion("src/a.rs", 5, 1)]);
+    }
+
+    #[test]
+    fn file_clusters_merge_groups_sharing_a_file() {
+        let suggestions = vec![
+            suggestion("src/a.rs", 0, 0),
+            suggestion("src/c.rs", 0, 1),
+            suggestion("src/b.rs", 0, 2),
+            suggestion("src/a.rs", 5, 2),
+        ];
+        let clusters = file_clusters(&suggestions);
+        assert_eq!(clusters.len(), 2);
+        let groups: Vec<BTreeSet<usize>> = clusters.iter().map(|c| c.iter().map(|s| s.group).collect()).collect();
+        assert!(groups.contains(&BTreeSet::from([0, 2])));
+        assert!(groups.contains(&BTreeSet::from([1])));
+    }
+
+    #[test]
+    fn relocate_makes_files_relative_to_cwd() {
+        let mut report = DiagnosticReport {
+            diagnostics: vec![Diagnostic {
+                level: "warning".to_string(),
+                code: None,
+                message: String::new(),
+                spans: Vec::new(),
+                notes: Vec::new(),
+                suggestions: vec![suggestion("member/src/lib.rs", 0, 0), suggestion("other/src/lib.rs", 0, 1)],
+                rendered: String::new(),
+            }],
+            success: true,
+        };
+        report.relocate(Path::new("/ws"), Path::new("/ws/member"));
+        let files: Vec<&str> = report.diagnostics[0].suggestions.iter().map(|s| s.file.as_str()).collect();
+        assert_eq!(files, vec!["src/lib.rs", "/ws/other/src/lib.rs"]);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::utils::ci::{self, Prompt};
+use crate::utils::events::{self, Event};
 use colored::Colorize;
+use dialoguer::{theme::ColorfulTheme, Confirm};
 use console::{Style, Term};
 use similar::{ChangeTag, TextDiff};
 use std::io::Write;
@@ -16,6 +19,75 @@ pub fn show_diff_and_confirm(
     new_content: &str,
     reason: &str,
 ) -> ConfirmAction {
+    // Terminal Sink က `print_diff` နဲ့ ပြမယ်
+    events::emit(Event::DiffProposed {
+        path: file_path.to_string(),
+        old: old_content.to_string(),
+        new: new_content.to_string(),
+        reason: reason.to_string(),
+    });
+    if old_content == new_content {
+        return ConfirmAction::Yes;
+    }
+
+    // Non-interactive = `[auto].writes` (File တစ်ခုချင်းစီ ဆုံးဖြတ်မယ်)
+    let mut choice = ConfirmAction::Yes;
+    let approved = ci::confirm(Prompt::Write, file_path, || {
+        choice = ask_apply(file_path);
+        !matches!(choice, ConfirmAction::No)
+    });
+    if approved { choice } else { ConfirmAction::No }
+}
+
+/// File အများကြီးကို တစ်ခါတည်း လက်ခံ/ငြင်းမယ် (e.g. File နှစ်ခု ဖြတ်သွားတဲ့ Compiler Suggestion) - `changes` = `(path, old, new)`
+pub fn show_diffs_and_confirm(changes: &[(String, String, String)], reason: &str) -> ConfirmAction {
+    if let [(file, old, new)] = changes {
+        return show_diff_and_confirm(file, old, new, reason);
+    }
+    for (file, old, new) in changes {
+        events::emit(Event::DiffProposed { path: file.clone(), old: old.clone(), new: new.clone(), reason: reason.to_string() });
+    }
+
+    let target = changes.iter().map(|(file, _, _)| file.as_str()).collect::<Vec<_>>().join(", ");
+    let mut choice = ConfirmAction::Yes;
+    let approved = ci::confirm(Prompt::Write, &target, || {
+        choice = ask_apply(&target);
+        !matches!(choice, ConfirmAction::No)
+    });
+    if approved { choice } else { ConfirmAction::No }
+}
+
+// 3. Improved Confirmation Loop
+fn ask_apply(file_path: &str) -> ConfirmAction {
+    loop {
+        // Option တွေကို ရှင်းရှင်းလင်းလင်း ပြမယ်
+        println!(
+            "{}",
+            "Options: [y]es, [n]o, [a]ll (accept all changes)".blue()
+        );
+        print!("{} Apply changes? > ", "❓".yellow());
+        std::io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input).unwrap();
+
+        match input.trim().to_lowercase().as_str() {
+            "y" | "yes" => return ConfirmAction::Yes,
+            "n" | "no" => {
+                println!("❌ Skipped: {}", file_path);
+                return ConfirmAction::No;
+            }
+            "a" | "all" => {
+                println!("🚀 Accepting ALL future changes for this session!",);
+                return ConfirmAction::All;
+            }
+            _ => println!("Please type 'y', 'n', or 'a'."),
+        }
+    }
+}
+
+/// Header + Diff View ပဲ ပြမယ် (`--dry-run` Preview လည်း သုံးတယ်) -> ပြောင်းလဲမှု ရှိ/မရှိ
+pub fn print_diff(file_path: &str, old_content: &str, new_content: &str, reason: &str) -> bool {
     let diff = TextDiff::from_lines(old_content, new_content);
     let mut changes_found = false;
 
@@ -48,35 +120,72 @@ pub fn show_diff_and_confirm(
         print!("{}{}", style.apply_to(sign).bold(), style.apply_to(change));
     }
     println!("---------------------------------------------------");
+    changes_found
+}
 
-    if !changes_found {
-        return ConfirmAction::Yes;
+/// 🗑️ delete_file: ဖျက်မယ့် File ရဲ့ အစပိုင်းကို ပြပြီး မေးမယ် ("all" နဲ့ ကျော်လို့ မရ၊ Default = No)
+pub fn confirm_delete(file_path: &str, content: &str, reason: &str) -> bool {
+    println!("\n{}", Style::new().red().bold().apply_to(format!("🗑️  DELETE: {}", file_path)));
+    if !reason.is_empty() {
+        println!("💡 Reason: {}", Style::new().yellow().italic().apply_to(reason));
     }
+    println!("---------------------------------------------------");
+    let total = content.lines().count();
+    for line in content.lines().take(15) {
+        println!("{}{}", Style::new().red().bold().apply_to("-"), Style::new().red().apply_to(line));
+    }
+    if total > 15 {
+        println!("{}", Style::new().dim().apply_to(format!("... ({} more lines)", total - 15)));
+    }
+    println!("---------------------------------------------------");
 
-    // 3. Improved Confirmation Loop
-    loop {
-        // Option တွေကို ရှင်းရှင်းလင်းလင်း ပြမယ်
-        println!(
-            "{}",
-            "Options: [y]es, [n]o, [a]ll (accept all changes)".blue()
-        );
-        print!("{} Apply changes? > ", "❓".yellow());
-        std::io::stdout().flush().unwrap();
-
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+    ci::confirm(Prompt::Delete, file_path, || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Delete {} ({} lines)?", file_path, total))
+            .default(false)
+            .interact()
+            .unwrap_or(false)
+    })
+}
 
-        match input.trim().to_lowercase().as_str() {
-            "y" | "yes" => return ConfirmAction::Yes,
-            "n" | "no" => {
-                println!("❌ Skipped: {}", file_path);
-                return ConfirmAction::No;
-            }
-            "a" | "all" => {
-                println!("🚀 Accepting ALL future changes for this session!",);
-                return ConfirmAction::All;
-            }
-            _ => println!("Please type 'y', 'n', or 'a'."),
-        }
+/// 🔀 rename_file: From -> To ကို ပြပြီး မေးမယ် (Target ရှိပြီးသားဆိုရင် Overwrite သတိပေးမယ်)
+pub fn confirm_rename(from: &str, to: &str, reason: &str, overwrites: bool) -> bool {
+    println!(
+        "\n{}",
+        Style::new().cyan().bold().apply_to(format!("🔀 RENAME: {} -> {}", from, to))
+    );
+    if !reason.is_empty() {
+        println!("💡 Reason: {}", Style::new().yellow().italic().apply_to(reason));
+    }
+    if overwrites {
+        println!("{}", format!("⚠️  {} already exists and will be overwritten.", to).red());
     }
+
+    // Overwrite = Target ကို ဖျက်တာမို့ `[auto].deletes` နဲ့ ဆုံးဖြတ်မယ်
+    let prompt = if overwrites { Prompt::Delete } else { Prompt::Rename };
+    ci::confirm(prompt, &format!("{} -> {}", from, to), || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Apply rename?")
+            .default(!overwrites)
+            .interact()
+            .unwrap_or(false)
+    })
+}
+
+/// Model ပေးတဲ့ Path မရှိလို့ နာမည်တူ File ကို တွေ့တဲ့အခါ (Path နှစ်ခုလုံး ပြပြီး မေးမယ်၊ "All" နဲ့ မကျော်ဘူး)
+pub fn confirm_redirect(requested: &str, found: &str) -> bool {
+    println!(
+        "\n{}",
+        Style::new().cyan().bold().apply_to(format!("🔀 REDIRECT: {} does not exist", requested))
+    );
+    println!("   Requested: {}", requested);
+    println!("   Found:     {}", found);
+
+    ci::confirm(Prompt::Redirect, &format!("{} -> {}", requested, found), || {
+        Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Use {} instead?", found))
+            .default(false)
+            .interact()
+            .unwrap_or(false)
+    })
 }
@@ -0,0 +1,414 @@
+use crate::utils::cmd::{CommandOutcome, OutputView};
+use crate::utils::diff;
+use crate::utils::isolation::Violation;
+use colored::*;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// --- 📡 EVENT BUS ---
+// Executor / Process Runner / Agent Session က Console ကို တိုက်ရိုက် မရေးဘဲ Event တွေ ထုတ်မယ်။
+// Sink တွေက ကိုယ့်နည်းကိုယ့်ဟန်နဲ့ ပြမယ်:
+// - `TerminalSink`: အရင် Console Output အတိုင်း (Default၊ အမြဲ ပါတယ်)
+// - `JsonLinesSink`: Event တစ်ခု = JSON Line တစ်ကြောင်း (`--output json`)
+// - `ChannelSink`: tokio Channel ထဲ ပို့မယ် (IDE / RPC Frontends)
+
+/// Plan Execution တစ်လျှောက် ဖြစ်ပျက်မှုများ (JSON: `{"event": "command_started", ...}`)
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// Agent / Plan File / Healing ဆီက Plan (Execute မလုပ်ခင်)
+    PlanReceived {
+        /// Prompt (သို့) "neurust fix" / "healing: cargo check" ...
+        label: String,
+        message: Option<String>,
+        init_command: Option<String>,
+        actions: Value,
+        dry_run: bool,
+    },
+    /// File ပြောင်းမယ့် Diff (Confirm မမေးခင်)
+    DiffProposed { path: String, old: String, new: String, reason: String },
+    /// Prompt / Command Policy / Sandbox ရဲ့ ဆုံးဖြတ်ချက်
+    DecisionMade {
+        /// `[auto]` Key ("writes", "risky_commands", ...) (သို့) "policy" / "sandbox"
+        prompt: String,
+        target: String,
+        approved: bool,
+        source: DecisionSource,
+        reason: Option<String>,
+    },
+    CommandStarted {
+        id: u64,
+        command: String,
+        cwd: Option<String>,
+        source: CommandSource,
+        view: OutputView,
+        echo_stdout: bool,
+        timeout_secs: Option<u64>,
+    },
+    CommandOutput { id: u64, stream: OutputStream, line: String },
+    /// `id` = None: Run မလုပ်ခဲ့ (Skip / Policy / Sandbox)
+    CommandFinished {
+        id: Option<u64>,
+        command: String,
+        source: CommandSource,
+        exit_code: Option<i32>,
+        success: bool,
+        skipped: bool,
+        blocked: Option<String>,
+        stopped: Option<String>,
+        duration_secs: f64,
+        stderr_tail: Vec<String>,
+        violations: Vec<Violation>,
+    },
+    FileWritten { path: String, change: FileChange, from: Option<String> },
+    HealingStarted { command: String, attempt: usize, max_attempts: usize, depth: usize },
+    /// `reason` = None: Healed, Some: ရပ်လိုက်ရတဲ့ အကြောင်း (`attempts` = "fix -> result")
+    HealingFinished { command: String, healed: bool, reason: Option<String>, attempts: Vec<String> },
+    /// Server-side Agent Loop ရဲ့ Turn တစ်ခု ပြီး
+    TurnCompleted { turn: u64, tools: Vec<String> },
+    /// Agent က မေးခွန်းမေးပြီး Non-interactive Mode က ဖြေလိုက် (None = ဖြေမယ့်သူ မရှိလို့ ရပ်)
+    QuestionAsked { question: String, reply: Option<String> },
+    VerificationFinished { passed: bool, summary: String },
+    /// Process ပြီးဆုံး (Exit Code Scheme: `neurust --help`)
+    RunFinished { exit_code: i32, status: String, error: Option<String> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionSource {
+    User,
+    /// `--yes` + `[auto]`
+    Auto,
+    /// TTY မရှိ -> "no"
+    NoTty,
+    Policy,
+    Sandbox,
+}
+
+/// Command ကို ဘယ်သူ Run ခိုင်းတာလဲ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandSource {
+    /// Agent Plan ရဲ့ `run_cmd` (Healing Re-run ပါ)
+    Plan,
+    /// Verification / Tests / Diagnostics (ခေါ်သူက Summary ကိုယ်တိုင် ပြမယ်)
+    Check,
+    /// Project Creator / Solana / Audit
+    Setup,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+    /// Neurust ရဲ့ သတိပေးချက် (Timeout / Cancel)
+    Notice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Created,
+    Updated,
+    Patched,
+    Deleted,
+    Renamed,
+    /// Compiler Suggestion (MachineApplicable) ကို Local မှာ Apply
+    Fixed,
+}
+
+pub trait EventSink: Send + Sync {
+    fn handle(&self, event: &Event);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SinkId(u64);
+
+struct Bus {
+    sinks: RwLock<Vec<(SinkId, Arc<dyn EventSink>)>>,
+    next: AtomicU64,
+}
+
+fn bus() -> &'static Bus {
+    static BUS: OnceLock<Bus> = OnceLock::new();
+    BUS.get_or_init(|| Bus {
+        sinks: RwLock::new(vec![(SinkId(0), Arc::new(TerminalSink::default()) as Arc<dyn EventSink>)]),
+        next: AtomicU64::new(1),
+    })
+}
+
+/// Sink အားလုံးဆီ ပို့မယ် (ထည့်ခဲ့တဲ့ အစဉ်အတိုင်း)
+pub fn emit(event: Event) {
+    let sinks = bus().sinks.read().map(|s| s.clone()).unwrap_or_default();
+    for (_, sink) in sinks {
+        sink.handle(&event);
+    }
+}
+
+pub fn subscribe(sink: impl EventSink + 'static) -> SinkId {
+    let id = SinkId(bus().next.fetch_add(1, Ordering::SeqCst));
+    if let Ok(mut sinks) = bus().sinks.write() {
+        sinks.push((id, Arc::new(sink)));
+    }
+    id
+}
+
+/// Sink ကို ဖြုတ်မယ် (Frontend ပိတ်ချိန် - Sink ကို Drop လုပ်လို့ ChannelSink Receiver တွေလည်း ပြီးသွားမယ်)
+pub fn unsubscribe(id: SinkId) {
+    if let Ok(mut sinks) = bus().sinks.write() {
+        sinks.retain(|(sink, _)| *sink != id);
+    }
+}
+
+/// Command တစ်ခုစီအတွက် ID (Started / Output / Finished ကို တွဲဖို့)
+pub fn next_command_id() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::SeqCst)
+}
+
+/// Plan Response (`message` / `init_command` / `plan`) -> PlanReceived
+pub fn plan_received(label: &str, response: &Value, dry_run: bool) {
+    emit(Event::PlanReceived {
+        label: label.to_string(),
+        message: response["message"].as_str().filter(|m| !m.trim().is_empty()).map(str::to_string),
+        init_command: response["init_command"].as_str().filter(|c| !c.is_empty()).map(str::to_string),
+        actions: response["plan"].clone(),
+        dry_run,
+    });
+}
+
+pub fn command_finished(id: Option<u64>, source: CommandSource, outcome: &CommandOutcome) {
+    let tail: Vec<String> = outcome.stderr.lines().rev().take(20).map(str::to_string).collect();
+    emit(Event::CommandFinished {
+        id,
+        command: outcome.command.clone(),
+        source,
+        exit_code: outcome.exit_code,
+        success: outcome.success(),
+        skipped: outcome.skipped,
+        blocked: outcome.blocked.clone(),
+        stopped: outcome.stopped().map(str::to_string),
+        duration_secs: outcome.duration.as_secs_f64(),
+        stderr_tail: tail.into_iter().rev().collect(),
+        violations: outcome.violations.clone(),
+    });
+}
+
+pub fn file_written(path: &str, change: FileChange) {
+    emit(Event::FileWritten { path: path.to_string(), change, from: None });
+}
+
+// --- 🖥️ TERMINAL SINK ---
+
+/// Run နေတဲ့ Command တစ်ခုရဲ့ Terminal State
+struct Running {
+    view: OutputView,
+    echo_stdout: bool,
+    spinner: Option<ProgressBar>,
+}
+
+/// Emoji Console Output (Live "│" Lines / Collapsed Spinner)
+#[derive(Default)]
+pub struct TerminalSink {
+    running: Mutex<HashMap<u64, Running>>,
+}
+
+impl EventSink for TerminalSink {
+    fn handle(&self, event: &Event) {
+        match event {
+            Event::DiffProposed { path, old, new, reason } => {
+                diff::print_diff(path, old, new, reason);
+            }
+            Event::DecisionMade { prompt, target, approved, source, reason } => match source {
+                DecisionSource::User => {}
+                DecisionSource::Auto => println!(
+                    "{} Auto-answer [auto].{} = {}: {}",
+                    "🤖".cyan(),
+                    prompt,
+                    if *approved { "yes".green() } else { "no".red() },
+                    target
+                ),
+                DecisionSource::NoTty => println!(
+                    "{} No terminal: answering no to {} ({}). Pass --yes to use the [auto] answers.",
+                    "🛑".yellow(),
+                    prompt,
+                    target
+                ),
+                DecisionSource::Policy => println!(
+                    "{} Blocked by command policy: {} ({})",
+                    "🚫".red(),
+                    target,
+                    reason.as_deref().unwrap_or("")
+                ),
+                DecisionSource::Sandbox => println!("{} Blocked write: {}", "🚫".red(), reason.as_deref().unwrap_or(target)),
+            },
+            Event::CommandStarted { id, command, source, view, echo_stdout, .. } => {
+                if *source != CommandSource::Check {
+                    println!("{} Executing: {}", "⚡".yellow(), command);
+                }
+                let spinner = (*view == OutputView::Collapsed).then(|| {
+                    let pb = ProgressBar::new_spinner();
+                    pb.set_style(ProgressStyle::default_spinner()
+                        .template("{spinner:.green} [{elapsed}] {msg}")
+                        .unwrap()
+                        .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏"));
+                    pb.set_message(format!("Running {}...", command));
+                    pb.enable_steady_tick(Duration::from_millis(100));
+                    pb
+                });
+                if *view == OutputView::Live {
+                    println!("   {} {}", "┌".dimmed(), format!("$ {}", command).dimmed());
+                }
+                if let Ok(mut running) = self.running.lock() {
+                    running.insert(*id, Running { view: *view, echo_stdout: *echo_stdout, spinner });
+                }
+            }
+            Event::CommandOutput { id, stream, line } => {
+                let Ok(running) = self.running.lock() else { return };
+                let Some(state) = running.get(id) else { return };
+                if *stream == OutputStream::Notice {
+                    match &state.spinner {
+                        Some(pb) => pb.println(line.yellow().to_string()),
+                        None => println!("{}", line.yellow()),
+                    }
+                    return;
+                }
+                if *stream == OutputStream::Stdout && !state.echo_stdout {
+                    return;
+                }
+                match state.view {
+                    OutputView::Live => println!("   {} {}", "│".dimmed(), line),
+                    OutputView::Collapsed => {
+                        if let Some(pb) = &state.spinner {
+                            let short: String = line.trim().chars().take(80).collect();
+                            if !short.is_empty() {
+                                pb.set_message(short);
+                            }
+                        }
+                    }
+                    OutputView::Quiet => {}
+                }
+            }
+            Event::CommandFinished { id, command, source, exit_code, success, skipped, blocked, stopped, duration_secs, stderr_tail, violations } => {
+                if let Some(state) = id.and_then(|id| self.running.lock().ok()?.remove(&id)) {
+                    if let Some(pb) = state.spinner {
+                        pb.finish_and_clear();
+                    }
+                    if state.view == OutputView::Live {
+                        println!("   {}", "└".dimmed());
+                    }
+                }
+                for violation in violations {
+                    println!("{} Sandbox denied ({:?}): {}", "📦".yellow(), violation.kind, violation.detail);
+                }
+
+                // Plan / Setup Command ရလဒ် (Verification / Tests တွေက ကိုယ်ပိုင် Summary ပြမယ်)
+                if *source == CommandSource::Check || blocked.is_some() {
+                    return;
+                }
+                if *skipped {
+                    println!("{} Skipped: {}", "⏭️".blue(), command);
+                } else if stopped.as_deref() == Some("cancelled") {
+                    println!("{} Cancelled: {} ({:.1}s)", "🛑".yellow(), command, duration_secs);
+                } else if stopped.is_some() {
+                    println!("{} Timed out: {} ({:.1}s, see [exec].timeout_secs)", "⏱️".red(), command, duration_secs);
+                } else if *success {
+                    println!("{} Success ({:.1}s)", "✅".green(), duration_secs);
+                } else {
+                    let code = exit_code.map(|c| c.to_string()).unwrap_or_else(|| "none".to_string());
+                    println!("{} Command Failed (exit {}, {:.1}s)", "❌".red(), code, duration_secs);
+                    for line in stderr_tail {
+                        println!("   {}", line.red());
+                    }
+                }
+            }
+            Event::FileWritten { path, change, from } => match change {
+                FileChange::Created => println!("{} Created: {}", "📝".green(), path),
+                FileChange::Updated => println!("{} Updating file: {}", "📝".green(), path),
+                FileChange::Patched => println!("{} Patching file: {}", "🩹".green(), path),
+                FileChange::Deleted => println!("{} Deleted: {}", "🗑️".red(), path),
+                FileChange::Renamed => println!("{} Renamed: {} -> {}", "🔀".green(), from.as_deref().unwrap_or("?"), path),
+                FileChange::Fixed => println!("{} Fixed locally: {}", "🔧".green(), path),
+            },
+            Event::HealingStarted { attempt, max_attempts, .. } => {
+                println!("{} Attempting Auto-Healing ({}/{})...", "🩹".yellow(), attempt, max_attempts);
+            }
+            Event::HealingFinished { command, healed: true, attempts, .. } => {
+                println!("{} Healed `{}` after {} attempt(s).", "🩹".green(), command, attempts.len());
+            }
+            Event::HealingFinished { command, reason, attempts, .. } => {
+                println!("\n{} Auto-healing gave up on `{}`: {}", "🛑".red(), command, reason.as_deref().unwrap_or(""));
+                if attempts.is_empty() {
+                    println!("   {}", "No fix was applied.".dimmed());
+                }
+                for (i, attempt) in attempts.iter().enumerate() {
+                    println!("   {}. {}", i + 1, attempt);
+                }
+                println!("   {}", "Review the errors above and fix manually, or re-run with a more specific prompt.".dimmed());
+            }
+            Event::TurnCompleted { turn, tools } => {
+                println!("{} Turn {} done ({})", "🧠".yellow(), turn, tools.join(", "));
+            }
+            Event::QuestionAsked { reply, .. } => match reply {
+                Some(reply) => println!("{} Auto-reply [auto].reply: {}", "🤖".cyan(), reply),
+                None => println!("{} The agent needs an answer but nobody can reply (set [auto].reply). Stopping.", "🛑".yellow()),
+            },
+            Event::PlanReceived { label, .. } if label.starts_with("healing: ") => {
+                println!("{} Applying Fix...", "🧠".cyan());
+            }
+            // Message / Summary တွေကို ခေါ်သူက Chat / Report အဖြစ် ပြပြီးသား
+            Event::PlanReceived { .. } | Event::VerificationFinished { .. } | Event::RunFinished { .. } => {}
+        }
+    }
+}
+
+// --- 📜 JSON LINES SINK ---
+
+/// Event တစ်ခု = JSON Line တစ်ကြောင်း
+pub struct JsonLinesSink<W: Write + Send> {
+    out: Mutex<W>,
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    pub fn new(out: W) -> Self {
+        Self { out: Mutex::new(out) }
+    }
+}
+
+impl<W: Write + Send> EventSink for JsonLinesSink<W> {
+    fn handle(&self, event: &Event) {
+        let Ok(line) = serde_json::to_string(event) else { return };
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", line);
+            let _ = out.flush();
+        }
+    }
+}
+
+// --- 🔌 CHANNEL SINK ---
+
+/// Event တွေကို Async Frontend (IDE / RPC) ဆီ ပို့မယ် (Receiver ပိတ်သွားရင် ဆက်မပို့ဘူး)
+pub struct ChannelSink {
+    tx: mpsc::UnboundedSender<Event>,
+}
+
+impl ChannelSink {
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<Event>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { tx }, rx)
+    }
+}
+
+impl EventSink for ChannelSink {
+    fn handle(&self, event: &Event) {
+        let _ = self.tx.send(event.clone());
+    }
+}
@@ -1,45 +1,158 @@
 use colored::*;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use std::pin::Pin;
 use std::future::Future;
-use crate::api::client::ApiClient;
-use crate::utils::{fs, cmd, diff, memory};
+use crate::api::client::{ApiClient, PlanRequest};
+use crate::utils::{fs, ci, cmd, diagnostics, diff, events, memory, patch, tools};
+use crate::utils::events::{CommandSource, DecisionSource, Event, FileChange};
+use crate::utils::config::NeurustConfig;
+use crate::utils::diagnostics::DiagnosticReport;
+use crate::utils::healing::HealingLog;
+use crate::utils::history::Transaction;
+use crate::utils::policy::{self, CommandPolicy, Decision};
+use crate::utils::sandbox::Sandbox;
 use crate::utils::diff::ConfirmAction;
 use crate::commands::create;
 
+/// `execute_plan` ရဲ့ ရလဒ် - Run ခဲ့တဲ့ Command တွေ (Observation Loop က Model ဆီ ပြန်ပို့မယ်)
+#[derive(Debug, Default)]
+pub struct PlanReport {
+    pub commands: Vec<cmd::CommandOutcome>,
+    /// `.neurust/history/<run-id>` (File / Command တစ်ခုမှ မထိခဲ့ရင် None)
+    pub run_id: Option<String>,
+    /// `ask_user` ကို "exit" / "quit" လို့ ဖြေပြီး Plan ကို ရပ်ခဲ့ (Conversation ဆက်မသွားဘူး)
+    pub ended: bool,
+}
+
+impl PlanReport {
+    /// Command တစ်ခုချင်းစီရဲ့ နောက်ဆုံး Run က အောင်မြင်ရမယ် (Healing က ပြန် Run ပြီး ပြင်သွားရင် OK)
+    pub fn all_succeeded(&self) -> bool {
+        self.commands.iter().enumerate().all(|(i, outcome)| {
+            self.commands[i..].iter().rev().find(|o| o.command == outcome.command).is_some_and(|o| o.success())
+        })
+    }
+
+    /// Skip ခဲ့တဲ့ Command (User / Policy က ငြင်း) တွေ မပါဘဲ နောက်ဆုံး Run က Fail နေတဲ့ Command ရှိလား (Exit Code အတွက်)
+    pub fn has_failures(&self) -> bool {
+        self.commands.iter().enumerate().any(|(i, outcome)| {
+            !outcome.skipped
+                && self.commands[i..].iter().rev().find(|o| o.command == outcome.command).is_some_and(|o| !o.success() && !o.skipped)
+        })
+    }
+
+    pub fn observation(&self, max_chars: usize) -> String {
+        self.commands.iter().map(|o| o.observation(max_chars)).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Top-level Plan တစ်ခုစာ State (Nested Fix Plan တွေက မျှသုံးမယ်)
+struct PlanRun {
+    healing: HealingLog,
+    tx: Transaction,
+    sandbox: Sandbox,
+    policy: CommandPolicy,
+    /// နောက်ဆုံး Run ခဲ့တဲ့ Cargo Command ရဲ့ Structured Diagnostics (Healing က Cargo ကို ထပ်မ Build ရအောင်)
+    diagnostics: Option<(String, DiagnosticReport)>,
+    /// `ask_user` မှာ "exit" ဖြေထားရင် Nested Fix Plan တွေပါ ရပ်မယ်
+    ended: bool,
+}
+
 /// Executes the JSON plan returned by the AI.
+/// Healing Ledger, History Transaction, Write Sandbox က Top-level Plan တစ်ခုစာ
+/// `label` = Run History မှာ ပြမယ့် နာမည် (Prompt / Command)
+pub async fn execute_plan(
+    plan_json: &serde_json::Value,
+    client: &ApiClient,
+    mem: &mut memory::ProjectMemory,
+    label: &str,
+) -> Result<PlanReport> {
+    let config = NeurustConfig::load();
+    let mut run = PlanRun {
+        healing: HealingLog::new(config.agent),
+        tx: Transaction::begin(label, &config.history),
+        sandbox: Sandbox::new(&config.sandbox)?,
+        policy: CommandPolicy::new(&config.policy),
+        diagnostics: None,
+        ended: false,
+    };
+
+    let result = execute_plan_with(plan_json, client, mem, &mut run).await;
+    // 🛑 Cancel = ကျန်တဲ့ Actions မလုပ်ဘဲ ရပ်ပြီးသား -> Commit ပြီးရင် Verification / Observation မဆက်ဘူး
+    let result = result.and_then(|report| if cmd::stop_requested() { Err(anyhow!("Cancelled by the client")) } else { Ok(report) });
+
+    // Error တက်လည်း Snapshot တွေ သိမ်းပြီးသား -> Undo လုပ်လို့ရတယ်
+    let run_id = run.tx.commit();
+    match result {
+        Ok(mut report) => {
+            report.run_id = run_id;
+            report.ended = run.ended;
+            Ok(report)
+        }
+        Err(e) => {
+            if let Some(id) = run_id {
+                println!("{} Plan stopped halfway. Run `neurust undo {}` to restore the touched files.", "⏪".yellow(), id);
+            }
+            Err(e)
+        }
+    }
+}
+
 /// 🔥 FIX: Recursive async calls require explicit Boxing (Pin<Box<...>>) to break infinite size cycles.
-pub fn execute_plan<'a>(
+fn execute_plan_with<'a>(
     plan_json: &'a serde_json::Value,
     client: &'a ApiClient,
-    mem: &'a mut memory::ProjectMemory
-) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+    mem: &'a mut memory::ProjectMemory,
+    run: &'a mut PlanRun,
+) -> Pin<Box<dyn Future<Output = Result<PlanReport>> + Send + 'a>> {
     Box::pin(async move {
         // Flag to skip confirmations if user selects "All"
         let mut always_allow = false;
+        let mut report = PlanReport::default();
 
         if let Some(actions) = plan_json.as_array() {
             for action in actions {
+                if cmd::stop_requested() {
+                    println!("{} Plan cancelled: skipping the remaining actions.", "🛑".yellow());
+                    break;
+                }
+                if run.ended {
+                    break;
+                }
                 let action_type = action["action"].as_str().unwrap_or("");
 
                 match action_type {
                     "create_file" => {
                         // handle_create_file is synchronous, so no await needed
-                        handle_create_file(action, &mut always_allow, mem)?;
+                        handle_create_file(action, &mut always_allow, mem, run)?;
                     },
                     "run_cmd" => {
-                        handle_run_cmd(action, client, mem).await?;
+                        report.commands.extend(handle_run_cmd(action, &mut always_allow, client, mem, run).await?);
                     },
                     "read_url" => {
                         handle_read_url(action, client).await?;
                     },
+                    "apply_patch" => {
+                        handle_apply_patch(action, &mut always_allow, mem, run)?;
+                    },
+                    "delete_file" => {
+                        handle_delete_file(action, mem, run)?;
+                    },
+                    "rename_file" => {
+                        handle_rename_file(action, mem, run)?;
+                    },
+                    "list_dir" | "grep" => {
+                        handle_inspect(action_type, action);
+                    },
+                    "ask_user" => {
+                        run.ended = !handle_ask_user(action, mem)?;
+                    },
                     _ => {}
                 }
             }
         }
-        Ok(())
+        Ok(report)
     })
 }
 
@@ -47,21 +160,14 @@ pub fn execute_plan<'a>(
 fn handle_create_file(
     action: &serde_json::Value,
     always_allow: &mut bool,
-    mem: &mut memory::ProjectMemory
+    mem: &mut memory::ProjectMemory,
+    run: &mut PlanRun,
 ) -> Result<()> {
     if let Some(path) = action["path"].as_str() {
         let new_content = action["content"].as_str().unwrap_or("");
         let reason = action["reason"].as_str().unwrap_or("No reason provided.");
         
-        let mut final_path = path.to_string();
-        
-        // Smart Path Resolution
-        if !Path::new(path).exists() {
-             if let Some(found) = fs::find_file_recursive(path) {
-                 final_path = found.to_string_lossy().to_string();
-                 println!("{} Redirecting write to: {}", "🔀".cyan(), final_path);
-             }
-        }
+        let Some(final_path) = resolve_target(path, &run.sandbox) else { return Ok(()) };
 
         let should_write = if *always_allow {
             true
@@ -86,8 +192,12 @@ fn handle_create_file(
             if let Some(parent) = Path::new(&final_path).parent() {
                 std::fs::create_dir_all(parent)?;
             }
-            println!("{} Updating file: {}", "📝".green(), final_path);
-            let _ = fs::write_file(&final_path, new_content);
+            let change = if Path::new(&final_path).exists() { FileChange::Updated } else { FileChange::Created };
+            run.tx.snapshot(&final_path)?;
+            match fs::write_file(&final_path, new_content) {
+                Ok(_) => events::file_written(&final_path, change),
+                Err(e) => println!("{} Write Error: {}", "⚠️".yellow(), e),
+            }
 
             // 🔥 MEMORY UPDATE: Smart Context Injection
             mem.append_file_context(&final_path, new_content);
@@ -99,59 +209,462 @@ fn handle_create_file(
     Ok(())
 }
 
+/// Sandbox + Smart Path Resolution
+/// - Project အပြင် / Protected Path ဆိုရင် Block (None)
+/// - Path မရှိရင် Project ထဲမှာ နာမည်တူ File ကို ရှာပြီး Path နှစ်ခုလုံးပြ Confirm မေးမယ် (No = မူလ Path)
+fn resolve_target(path: &str, sandbox: &Sandbox) -> Option<String> {
+    let requested = match sandbox.check_write(path) {
+        Ok(relative) => relative,
+        Err(e) => {
+            sandbox_denied(path, &e.to_string());
+            return None;
+        }
+    };
+    if Path::new(&requested).exists() {
+        return Some(requested);
+    }
+
+    let found = fs::find_file_recursive(path)
+        .and_then(|found| sandbox.check_write(&found.to_string_lossy()).ok())
+        .filter(|found| found != &requested);
+    match found {
+        Some(found) if diff::confirm_redirect(&requested, &found) => {
+            println!("{} Redirecting write to: {}", "🔀".cyan(), found);
+            Some(found)
+        }
+        _ => Some(requested),
+    }
+}
+
+/// Delete / Rename အတွက် Sandbox Check (Redirect မလုပ်ဘူး)
+fn checked_path(path: &str, sandbox: &Sandbox) -> Option<String> {
+    match sandbox.check_write(path) {
+        Ok(relative) => Some(relative),
+        Err(e) => {
+            sandbox_denied(path, &e.to_string());
+            None
+        }
+    }
+}
+
+fn sandbox_denied(path: &str, reason: &str) {
+    events::emit(Event::DecisionMade {
+        prompt: "sandbox".to_string(),
+        target: path.to_string(),
+        approved: false,
+        source: DecisionSource::Sandbox,
+        reason: Some(reason.to_string()),
+    });
+}
+
+/// Handles `apply_patch` (unified diff / search-replace) with Diff View & Confirmation
+fn handle_apply_patch(
+    action: &serde_json::Value,
+    always_allow: &mut bool,
+    mem: &mut memory::ProjectMemory,
+    run: &mut PlanRun,
+) -> Result<()> {
+    let Some(path) = action["path"].as_str() else { return Ok(()) };
+    let reason = action["reason"].as_str().unwrap_or("No reason provided.");
+    let Some(final_path) = resolve_target(path, &run.sandbox) else { return Ok(()) };
+
+    let old_content = match fs::read_file(&final_path) {
+        Ok(content) => content,
+        Err(_) => {
+            println!("{} Cannot patch missing file: {}", "❌".red(), final_path);
+            return Ok(());
+        }
+    };
+
+    let new_content = match patch::apply_action(&old_content, action) {
+        Ok(content) => content,
+        Err(e) => {
+            println!("{} Patch does not apply to {}: {}", "❌".red(), final_path, e);
+            return Ok(());
+        }
+    };
+
+    let should_write = *always_allow
+        || match diff::show_diff_and_confirm(&final_path, &old_content, &new_content, reason) {
+            ConfirmAction::Yes => true,
+            ConfirmAction::No => false,
+            ConfirmAction::All => {
+                *always_allow = true;
+                true
+            }
+        };
+
+    if should_write {
+        run.tx.snapshot(&final_path)?;
+        fs::write_file(&final_path, &new_content)?;
+        events::file_written(&final_path, FileChange::Patched);
+
+        // Memory ထဲက File အဟောင်းကို အသစ်နဲ့ အစားထိုးမယ်
+        mem.forget_file_context(&final_path);
+        mem.append_file_context(&final_path, &new_content);
+        mem.touch_file(&final_path);
+        let _ = mem.save();
+    } else {
+        println!("{} Skipped patching: {}", "🛑".yellow(), final_path);
+    }
+    Ok(())
+}
+
+/// Handles `delete_file` (Redirect မလုပ်ဘူး၊ "all" ရွေးထားလည်း အမြဲ မေးမယ်)
+fn handle_delete_file(action: &serde_json::Value, mem: &mut memory::ProjectMemory, run: &mut PlanRun) -> Result<()> {
+    let Some(path) = action["path"].as_str() else { return Ok(()) };
+    let reason = action["reason"].as_str().unwrap_or("No reason provided.");
+    let Some(path) = checked_path(path, &run.sandbox) else { return Ok(()) };
+    let path = path.as_str();
+    let target = Path::new(path);
+
+    if !target.is_file() {
+        println!("{} Not a file, skipping delete: {}", "⚠️".yellow(), path);
+        return Ok(());
+    }
+
+    let content = fs::read_file(path).unwrap_or_default();
+    if !diff::confirm_delete(path, &content, reason) {
+        println!("{} Kept: {}", "🛑".yellow(), path);
+        return Ok(());
+    }
+
+    run.tx.snapshot(path)?;
+    std::fs::remove_file(target)?;
+    events::file_written(path, FileChange::Deleted);
+
+    mem.forget_file_context(path);
+    mem.active_files.retain(|f| f != path);
+    mem.record_event(&format!("Deleted: {}", path));
+    let _ = mem.save();
+    Ok(())
+}
+
+/// Handles `rename_file` (`from` -> `to`, Parent Folder မရှိရင် ဆောက်မယ်)
+fn handle_rename_file(action: &serde_json::Value, mem: &mut memory::ProjectMemory, run: &mut PlanRun) -> Result<()> {
+    let (Some(from), Some(to)) = (action["from"].as_str(), action["to"].as_str()) else { return Ok(()) };
+    let reason = action["reason"].as_str().unwrap_or("No reason provided.");
+    let (Some(from), Some(to)) = (checked_path(from, &run.sandbox), checked_path(to, &run.sandbox)) else { return Ok(()) };
+    let (from, to) = (from.as_str(), to.as_str());
+
+    if !Path::new(from).exists() {
+        println!("{} Cannot rename missing path: {}", "❌".red(), from);
+        return Ok(());
+    }
+
+    if !diff::confirm_rename(from, to, reason, Path::new(to).exists()) {
+        println!("{} Skipped rename: {}", "🛑".yellow(), from);
+        return Ok(());
+    }
+
+    if let Some(parent) = Path::new(to).parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    run.tx.snapshot(from)?;
+    run.tx.snapshot(to)?;
+    std::fs::rename(from, to)?;
+    events::emit(Event::FileWritten { path: to.to_string(), change: FileChange::Renamed, from: Some(from.to_string()) });
+
+    mem.forget_file_context(from);
+    mem.forget_file_context(to);
+    if let Ok(content) = fs::read_file(to) {
+        mem.append_file_context(to, &content);
+    }
+    mem.active_files.retain(|f| f != from);
+    mem.touch_file(to);
+    mem.record_event(&format!("Renamed: {} -> {}", from, to));
+    let _ = mem.save();
+    Ok(())
+}
+
+/// Handles read-only `list_dir` / `grep` (Output ကို ပြရုံပဲ)
+fn handle_inspect(action_type: &str, action: &serde_json::Value) {
+    let target = action["pattern"].as_str().or(action["path"].as_str()).unwrap_or(".");
+    println!("{} {}: {}", "🔎".blue(), action_type, target);
+
+    match tools::execute(action_type, action) {
+        Ok(output) => {
+            let lines: Vec<&str> = output.lines().collect();
+            for line in lines.iter().take(40) {
+                println!("   {}", line.dimmed());
+            }
+            if lines.len() > 40 {
+                println!("   {}", format!("... ({} more lines)", lines.len() - 40).dimmed());
+            }
+        }
+        Err(e) => println!("{} {} failed: {}", "❌".red(), action_type, e),
+    }
+}
+
+/// Handles `ask_user` inside a plan (Answer ကို Memory ထဲ မှတ်ပြီး Plan ဆက်သွားမယ်)
+/// Plan ကို ဆက်လုပ်ရမလား (`ask` လိုပဲ "exit" / "quit" = ရပ်)
+fn handle_ask_user(action: &serde_json::Value, mem: &mut memory::ProjectMemory) -> Result<bool> {
+    let Some(question) = action["question"].as_str() else { return Ok(true) };
+    println!("\n{} Neurust: {}", "🤖".green(), question);
+    print!("{} Reply > ", "👤".blue());
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut answer = String::new();
+    if ci::interactive() {
+        std::io::stdin().read_line(&mut answer)?;
+    } else {
+        answer = ci::auto_reply(question);
+    }
+    let answer = answer.trim();
+
+    if answer.eq_ignore_ascii_case("exit") || answer.eq_ignore_ascii_case("quit") {
+        println!("{} Ending conversation: skipping the remaining actions.", "👋".blue());
+        return Ok(false);
+    }
+    if !answer.is_empty() {
+        mem.record_event(&format!("Q: {} / A: {}", question, answer));
+        let _ = mem.save();
+    }
+    Ok(true)
+}
+
 /// Handles Command Execution with Auto-Healing Logic
+/// Outcome (Healing က Run ခဲ့တာတွေပါ) ကို ပြန်ပေးမယ်
 async fn handle_run_cmd(
     action: &serde_json::Value,
+    always_allow: &mut bool,
     client: &ApiClient,
-    mem: &mut memory::ProjectMemory
-) -> Result<()> {
-    if let Some(program) = action["program"].as_str() {
-        let args: Vec<&str> = action["args"].as_array()
-            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
-            .unwrap_or_default();
+    mem: &mut memory::ProjectMemory,
+    run: &mut PlanRun,
+) -> Result<Vec<cmd::CommandOutcome>> {
+    let Some(program) = action["program"].as_str() else { return Ok(Vec::new()) };
+    let args: Vec<&str> = action["args"].as_array()
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
 
-        println!("{} Executing: {} {:?}", "⚡".yellow(), program, args);
-        
-        match cmd::execute_with_output(program, &args, None) {
-            Ok(_) => println!("{} Success", "✅".green()),
-            Err(e) => {
-                println!("{} Command Failed: {}", "❌".red(), e);
-                // Call recursive auto-healing
-                attempt_auto_healing(program, &args, &e.to_string(), client, mem).await?;
+    let outcome = run_and_report(program, &args, run).await;
+    if outcome.success() || outcome.skipped || outcome.cancelled {
+        return Ok(vec![outcome]);
+    }
+
+    // Call bounded auto-healing
+    attempt_auto_healing(program, &args, outcome, always_allow, client, mem, run).await
+}
+
+/// Command Policy စစ်ပြီး Run မယ်၊ ရလဒ်ကို Console မှာ ပြမယ် (Decision + Outcome ကို Run History ထဲ မှတ်မယ်)
+async fn run_and_report(program: &str, args: &[&str], run: &mut PlanRun) -> cmd::CommandOutcome {
+    let command = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+    let assessment = run.policy.evaluate(program, args);
+
+    let outcome = match assessment.decision {
+        Decision::Deny => {
+            let reason = assessment.risks.join("; ");
+            policy::denied(&command, &assessment);
+            run.tx.record_decision(&command, &assessment, None);
+            not_run(cmd::CommandOutcome::not_run(command, Some(format!("the command policy: {}", reason))))
+        }
+        Decision::Confirm => {
+            let approved = policy::confirm(&command, &assessment);
+            run.tx.record_decision(&command, &assessment, Some(approved));
+            // Policy မှာ မေးပြီးသားမို့ Heavy Prompt ထပ်မမေးဘူး
+            if approved {
+                run_plan_command(program, args, run).await
+            } else {
+                not_run(cmd::CommandOutcome::not_run(command, None))
+            }
+        }
+        Decision::Allow => {
+            run.tx.record_decision(&command, &assessment, None);
+            if !diagnostics::is_cargo_diagnostic(program, args) {
+                cmd::run_observed(program, args, None).await
+            } else if cmd::confirm_heavy(program, args, None) {
+                run_plan_command(program, args, run).await
+            } else {
+                not_run(cmd::CommandOutcome::not_run(command, None))
             }
         }
+    };
+
+    run.tx.record_command(&outcome);
+    outcome
+}
+
+/// `cargo check/build/clippy` = JSON Diagnostics နဲ့ Run ပြီး Report ကို `run.diagnostics` ထဲ သိမ်းမယ်
+async fn run_plan_command(program: &str, args: &[&str], run: &mut PlanRun) -> cmd::CommandOutcome {
+    if !diagnostics::is_cargo_diagnostic(program, args) {
+        return cmd::CommandSpec::new(program, args).source(CommandSource::Plan).run().await;
     }
-    Ok(())
+    let cargo = diagnostics::run_cargo(args, CommandSource::Plan).await;
+    run.diagnostics = Some((cargo.outcome.command.clone(), cargo.report));
+    cargo.outcome
+}
+
+/// Process Runner ကို မရောက်ခဲ့တဲ့ Command (Policy Deny / Declined) - Runner ကိုရောက်ရင် သူကိုယ်တိုင် CommandFinished ထုတ်မယ်
+fn not_run(outcome: cmd::CommandOutcome) -> cmd::CommandOutcome {
+    events::command_finished(None, CommandSource::Plan, &outcome);
+    outcome
 }
 
 /// Specialized Auto-Healing Subroutine
+/// - Project Context + အရင် Fix မှတ်တမ်းနဲ့ Fix Plan တောင်းမယ်
+/// - Fix ကို Apply ပြီး မူလ Command ကို ပြန် Run ပြီး စစ်မယ်
+/// - Attempt / Depth Limit ပြည့်ရင်၊ Fix အတူတူ ပြန်လာရင် ရပ်ပြီး Summary ပြမယ်
 async fn attempt_auto_healing(
     program: &str,
     args: &[&str],
-    error_msg: &str,
+    failure: cmd::CommandOutcome,
+    always_allow: &mut bool,
     client: &ApiClient,
-    mem: &mut memory::ProjectMemory
-) -> Result<()> {
-    println!("{} Attempting Auto-Healing...", "🩹".yellow());
-    
-    let fix_prompt = format!(
-        "The command '{} {:?}' failed with this error:\n\n{}\n\nAnalyze the error. Provide a JSON plan to fix it immediately.", 
-        program, args, error_msg
-    );
+    mem: &mut memory::ProjectMemory,
+    run: &mut PlanRun,
+) -> Result<Vec<cmd::CommandOutcome>> {
+    let command = failure.command.clone();
+    let mut latest = failure.clone();
+    let mut outcomes = vec![failure];
+
+    if !run.healing.can_descend() {
+        run.healing.finish(&command, Some("healing depth limit reached; leaving it to the outer fix"));
+        return Ok(outcomes);
+    }
+
+    loop {
+        if latest.success() {
+            run.healing.finish(&command, None);
+            mem.record_event(&format!("Auto-healed `{}` ({})", command, run.healing.prompt_record(&command).replace('\n', "; ")));
+            let _ = mem.save();
+            return Ok(outcomes);
+        }
+
+        if latest.skipped {
+            run.healing.finish(&command, Some("verification was skipped"));
+            break;
+        }
+        if latest.cancelled {
+            run.healing.finish(&command, Some("the command was cancelled"));
+            break;
+        }
+
+        if run.healing.attempts_for(&command) >= run.healing.settings.max_healing_attempts {
+            run.healing.finish(&command, Some(&format!("{} attempt(s) used", run.healing.settings.max_healing_attempts)));
+            break;
+        }
+
+        events::emit(Event::HealingStarted {
+            command: command.clone(),
+            attempt: run.healing.attempts_for(&command) + 1,
+            max_attempts: run.healing.settings.max_healing_attempts,
+            depth: run.healing.depth(),
+        });
 
-    if let Ok(fix_response) = client.fetch_plan(&fix_prompt, None).await {
-        if let Some(fix_actions) = fix_response["plan"].as_array() {
-            if !fix_actions.is_empty() {
-                println!("{} Applying Fix...", "🧠".cyan());
-                // Recursively call execute_plan for the fix
-                // Since execute_plan returns Pin<Box<Future>>, we can await it here.
-                return execute_plan(&fix_response["plan"], client, mem).await;
+        // 🩺 Cargo ဆိုရင် Structured Diagnostics ယူမယ်၊ MachineApplicable Suggestions ကို AI မမေးခင် Local မှာ အရင် Apply မယ်
+        let mut details = latest.observation(run.healing.settings.max_output_chars);
+        if diagnostics::is_cargo_diagnostic(program, args) {
+            // `latest` ကို Run တုန်းက သိမ်းထားတဲ့ Report (မရှိမှ ထပ် Run မယ်)
+            let report = match run.diagnostics.take() {
+                Some((ran, report)) if ran == command => report,
+                _ => {
+                    let cargo = diagnostics::run_cargo(args, CommandSource::Check).await;
+                    details = cargo.outcome.observation(run.healing.settings.max_output_chars);
+                    cargo.report
+                }
+            };
+            let mut local = report.machine_applicable();
+            // Project အပြင် (Registry / Path Dependency) နဲ့ Protected Files ကို မထိဘူး
+            diagnostics::retain_groups(&mut local, |s| run.sandbox.check_write(&s.file).is_ok());
+            let fingerprint = format!("local:{:?}", local);
+
+            // `neurust fix` လိုပဲ Diff ပြပြီး `[auto].writes` / Editor Responder နဲ့ မေးမယ်
+            let files = if !local.is_empty() && !run.healing.has_fingerprint(&fingerprint) {
+                diagnostics::apply_confirmed(&local, always_allow, &mut run.tx)?.1
+            } else {
+                Vec::new()
+            };
+            if !files.is_empty() {
+                let attempt = run.healing.record_local(
+                    &command,
+                    format!("{} machine-applicable compiler suggestion(s) in {}", local.len(), files.join(", ")),
+                    fingerprint,
+                );
+                for file in &files {
+                    mem.forget_file_context(file);
+                    if let Ok(content) = fs::read_file(file) {
+                        mem.append_file_context(file, &content);
+                    }
+                }
+
+                let rerun = run_plan_command(program, args, run).await;
+                run.tx.record_command(&rerun);
+                outcomes.push(rerun.clone());
+                run.healing.resolve(attempt, &rerun);
+                latest = rerun;
+                continue;
             }
         }
+
+        let previous = run.healing.prompt_record(&command);
+        let fix_prompt = format!(
+            "The command '{} {:?}' failed:\n\n{}\n\n{}Analyze the error. Provide a JSON plan to fix it immediately.",
+            program,
+            args,
+            details,
+            if previous.is_empty() {
+                String::new()
+            } else {
+                format!("--- PREVIOUS FIX ATTEMPTS (do NOT repeat them) ---\n{}\n\n", previous)
+            }
+        );
+        let request = PlanRequest {
+            context: Some(format!(
+                "{}\n\n--- PROJECT MEMORY (PAST ACTIONS) ---\n{}",
+                mem.project_context, mem.summary
+            )),
+            ..PlanRequest::new(&fix_prompt)
+        };
+
+        let fix_plan = match client.fetch_plan_with(&request).await {
+            Ok(response) if response["plan"].as_array().is_some_and(|p| !p.is_empty()) => response,
+            Ok(_) => {
+                run.healing.finish(&command, Some("the agent returned no fix"));
+                break;
+            }
+            Err(e) => {
+                run.healing.finish(&command, Some(&format!("could not fetch a fix ({})", e)));
+                ci::fail_with(&e);
+                break;
+            }
+        };
+
+        if run.healing.is_repeat(&fix_plan["plan"]) {
+            run.healing.finish(&command, Some("the agent proposed an identical fix again"));
+            break;
+        }
+        events::plan_received(&format!("healing: {}", command), &fix_plan, false);
+        let fix_plan = fix_plan["plan"].clone();
+
+        let attempt = run.healing.record(&command, &fix_plan);
+
+        run.healing.enter();
+        let report = execute_plan_with(&fix_plan, client, mem, run).await;
+        run.healing.leave();
+        let report = report?;
+        if run.ended {
+            outcomes.extend(report.commands);
+            run.healing.finish(&command, Some("the conversation was ended"));
+            return Ok(outcomes);
+        }
+
+        // Fix Plan က မူလ Command ကို ပြန် Run ပြီးသားဆိုရင် အဲ့ဒီရလဒ်ကို ယူမယ်၊ မဟုတ်ရင် ကိုယ်တိုင် ပြန် Run မယ်
+        let rerun = report.commands.iter().rev().find(|o| o.command == command).cloned();
+        outcomes.extend(report.commands);
+        latest = match rerun {
+            Some(outcome) => outcome,
+            None => {
+                let outcome = run_and_report(program, args, run).await;
+                outcomes.push(outcome.clone());
+                outcome
+            }
+        };
+        run.healing.resolve(attempt, &latest);
     }
-    
-    println!("{} Could not fetch auto-healing plan.", "⚠️".yellow());
-    Ok(())
+
+    mem.record_event(&format!("Auto-healing gave up on `{}` after {} attempt(s)", command, run.healing.attempts_for(&command)));
+    let _ = mem.save();
+    Ok(outcomes)
 }
 
 /// Handles browsing URLs
@@ -164,7 +677,10 @@ async fn handle_read_url(action: &serde_json::Value, client: &ApiClient) -> Resu
                 let _ = fs::write_file("neurust_browsing_cache.txt", &content);
                 println!("{} Saved to cache.", "💾".blue());
             },
-            Err(e) => println!("{} Failed to browse: {}", "❌".red(), e),
+            Err(e) => {
+                println!("{} Failed to browse: {}", "❌".red(), e);
+                ci::fail_with(&e);
+            }
         }
     }
     Ok(())
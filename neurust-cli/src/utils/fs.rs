@@ -37,9 +37,8 @@ pub fn find_file_recursive(target_path_str: &str) -> Option<PathBuf> {
     let target_path = Path::new(target_path_str);
     let target_file_name = target_path.file_name()?;
 
-    // လက်ရှိ Folder အောက်က ဖိုင်အားလုံးကို လိုက်ရှာမယ်
+    // လက်ရှိ Folder အောက်က ဖိုင်အားလုံးကို လိုက်ရှာမယ် (Symlink တွေကို မလိုက်ဘူး - Project အပြင် ရောက်သွားနိုင်လို့)
     for entry in WalkDir::new(".")
-        .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
     {
@@ -0,0 +1,173 @@
+use crate::utils::cmd::CommandOutcome;
+use crate::utils::config::AgentConfig;
+use crate::utils::events::{self, Event};
+use serde_json::Value;
+
+// --- 🩹 AUTO-HEALING LEDGER ---
+// Top-level Plan တစ်ခုအတွက် Healing ကြိုးစားမှုတွေကို မှတ်ထားမယ်။
+// - Nested Healing Depth ကို ကန့်သတ်မယ် (Fix Plan ထဲက Command ပြန် Fail ရင် ထပ် Heal တာ)
+// - Fix တစ်ခုချင်းစီနဲ့ ရလဒ်ကို နောက် Request ထဲ ထည့်ပို့မယ် (Model က အရင် Fix ကို ပြန်မပေးအောင်)
+// - Fix အတူတူ ပြန်လာရင် ရပ်ပြီး Summary ထုတ်မယ်
+
+/// Fix တစ်ကြိမ်ရဲ့ မှတ်တမ်း
+#[derive(Debug, Clone)]
+pub struct HealingAttempt {
+    /// Fail ဖြစ်ခဲ့တဲ့ Command (e.g. "cargo check")
+    pub command: String,
+    /// Fix Plan ကို လူဖတ်လို့ရအောင် (e.g. "apply_patch src/lib.rs, run_cmd cargo check")
+    pub fix: String,
+    /// Repeat Detection အတွက် (`reason` တွေ ဖယ်ထားတဲ့ Plan JSON)
+    fingerprint: String,
+    /// None = Verify မလုပ်ရသေး, Some(Ok) = Healed, Some(Err(line)) = Still failing
+    pub result: Option<Result<(), String>>,
+}
+
+pub struct HealingLog {
+    pub settings: AgentConfig,
+    pub attempts: Vec<HealingAttempt>,
+    depth: usize,
+}
+
+impl HealingLog {
+    pub fn new(settings: AgentConfig) -> Self {
+        Self { settings, attempts: Vec::new(), depth: 0 }
+    }
+
+    /// Nested Healing ထပ်လုပ်လို့ ရသေးလား
+    pub fn can_descend(&self) -> bool {
+        self.depth < self.settings.max_healing_depth
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn enter(&mut self) {
+        self.depth += 1;
+    }
+
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+
+    /// Fix Plan တူ ပြန်လာပြီလား (`reason` မတူရုံနဲ့ အသစ်လို့ မယူဘူး)
+    pub fn is_repeat(&self, plan: &Value) -> bool {
+        let fingerprint = fingerprint(plan);
+        self.attempts.iter().any(|a| a.fingerprint == fingerprint)
+    }
+
+    /// Fix အသစ်ကို မှတ်ပြီး Index ပြန်ပေးမယ် (Verify ပြီးရင် `resolve` နဲ့ ရလဒ်ဖြည့်မယ်)
+    pub fn record(&mut self, command: &str, plan: &Value) -> usize {
+        self.attempts.push(HealingAttempt {
+            command: command.to_string(),
+            fix: describe_plan(plan),
+            fingerprint: fingerprint(plan),
+            result: None,
+        });
+        self.attempts.len() - 1
+    }
+
+    /// AI မပါဘဲ Local မှာ ပြင်တာ (Compiler Suggestions) ကို မှတ်မယ်
+    pub fn record_local(&mut self, command: &str, fix: String, fingerprint: String) -> usize {
+        self.attempts.push(HealingAttempt { command: command.to_string(), fix, fingerprint, result: None });
+        self.attempts.len() - 1
+    }
+
+    pub fn has_fingerprint(&self, fingerprint: &str) -> bool {
+        self.attempts.iter().any(|a| a.fingerprint == fingerprint)
+    }
+
+    pub fn resolve(&mut self, index: usize, outcome: &CommandOutcome) {
+        if let Some(attempt) = self.attempts.get_mut(index) {
+            attempt.result = Some(if outcome.success() { Ok(()) } else { Err(first_error_line(outcome)) });
+        }
+    }
+
+    /// Command တစ်ခုအတွက် ကြိုးစားခဲ့တဲ့ အကြိမ်ရေ
+    pub fn attempts_for(&self, command: &str) -> usize {
+        self.attempts.iter().filter(|a| a.command == command).count()
+    }
+
+    /// Model ဆီ ပို့မယ့် "ဘာတွေ စမ်းပြီးပြီလဲ" မှတ်တမ်း
+    pub fn prompt_record(&self, command: &str) -> String {
+        let lines: Vec<String> = self
+            .attempts
+            .iter()
+            .filter(|a| a.command == command)
+            .enumerate()
+            .map(|(i, a)| format!("Attempt {}: {} -> {}", i + 1, a.fix, describe_result(&a.result)))
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Healing ပြီးဆုံး (`why` = None: Healed၊ Some: ရပ်လိုက်ရတဲ့ အကြောင်း) -> `HealingFinished` Summary
+    pub fn finish(&self, command: &str, why: Option<&str>) {
+        let attempts = self
+            .attempts
+            .iter()
+            .filter(|a| a.command == command)
+            .map(|a| format!("{} -> {}", a.fix, describe_result(&a.result)))
+            .collect();
+        events::emit(Event::HealingFinished {
+            command: command.to_string(),
+            healed: why.is_none(),
+            reason: why.map(str::to_string),
+            attempts,
+        });
+    }
+}
+
+fn describe_result(result: &Option<Result<(), String>>) -> String {
+    match result {
+        Some(Ok(())) => "fixed".to_string(),
+        Some(Err(line)) => format!("still failing ({})", line),
+        None => "not verified".to_string(),
+    }
+}
+
+/// stderr/stdout ထဲက ပထမဆုံး `error` Line (မရှိရင် နောက်ဆုံး Line)
+pub fn first_error_line(outcome: &CommandOutcome) -> String {
+    let text = if outcome.stderr.trim().is_empty() { &outcome.stdout } else { &outcome.stderr };
+    text.lines()
+        .find(|l| l.trim_start().starts_with("error"))
+        .or_else(|| text.lines().rev().find(|l| !l.trim().is_empty()))
+        .map(|l| l.trim().chars().take(160).collect())
+        .unwrap_or_else(|| format!("exit code {:?}", outcome.exit_code))
+}
+
+fn describe_plan(plan: &Value) -> String {
+    let steps: Vec<String> = plan
+        .as_array()
+        .map(|actions| {
+            actions
+                .iter()
+                .map(|a| {
+                    let kind = a["action"].as_str().unwrap_or("?");
+                    let target = match kind {
+                        "run_cmd" => {
+                            let args: Vec<&str> = a["args"]
+                                .as_array()
+                                .map(|v| v.iter().filter_map(|x| x.as_str()).collect())
+                                .unwrap_or_default();
+                            format!("{} {}", a["program"].as_str().unwrap_or(""), args.join(" "))
+                        }
+                        "rename_file" => format!("{} -> {}", a["from"].as_str().unwrap_or(""), a["to"].as_str().unwrap_or("")),
+                        _ => a["path"].as_str().or(a["url"].as_str()).unwrap_or("").to_string(),
+                    };
+                    format!("{} {}", kind, target.trim())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    steps.join(", ")
+}
+
+fn fingerprint(plan: &Value) -> String {
+    let mut plan = plan.clone();
+    if let Some(actions) = plan.as_array_mut() {
+        for action in actions.iter_mut().filter_map(|a| a.as_object_mut()) {
+            action.remove("reason");
+        }
+    }
+    plan.to_string()
+}
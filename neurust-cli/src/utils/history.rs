@@ -0,0 +1,302 @@
+use crate::utils::cmd::{self, CommandOutcome};
+use crate::utils::config::HistoryConfig;
+use crate::utils::policy::{Assessment, Decision};
+use anyhow::{bail, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// --- ⏪ RUN HISTORY (Transactional Plan Execution) ---
+// Plan တစ်ခု Run တိုင်း ထိမယ့် File တွေရဲ့ မူလ Content ကို `.neurust/history/<run-id>/` ထဲ Snapshot ယူမယ်။
+// Run ခဲ့တဲ့ Command တွေကိုလည်း မှတ်မယ်။ `neurust undo` က Snapshot တွေကို ပြန်ထားပေးမယ်။
+// Snapshot ကို Write မလုပ်ခင် ချက်ချင်း Disk ပေါ် သိမ်းတာမို့ Plan တစ်ဝက်မှာ Error တက်လည်း Undo လုပ်လို့ရတယ်။
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunStatus {
+    Applied,
+    Undone,
+}
+
+/// Run တစ်ခုမှာ ထိခဲ့တဲ့ File
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    pub path: String,
+    /// false = Run က အသစ်ဆောက်ခဲ့တာ (Undo ရင် ဖျက်မယ်)
+    pub existed: bool,
+    /// Run ပြီးချိန် Content ရဲ့ SHA-256 (None = Run ပြီးချိန်မှာ မရှိ) - Undo မလုပ်ခင် Drift စစ်ဖို့
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandEntry {
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub skipped: bool,
+    pub duration_secs: f64,
+    /// "timed out" / "cancelled"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stopped: Option<String>,
+}
+
+/// Command Policy ဆုံးဖြတ်ချက်
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyEntry {
+    pub command: String,
+    pub decision: Decision,
+    pub risks: Vec<String>,
+    /// User Rule (None = Built-in Classification)
+    pub rule: Option<String>,
+    /// Confirm Decision မှာ User ရဲ့ အဖြေ
+    pub approved: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub id: String,
+    pub started_at: String,
+    /// Prompt (သို့) `neurust fix` စတဲ့ Command
+    pub label: String,
+    pub status: RunStatus,
+    pub files: Vec<FileEntry>,
+    pub commands: Vec<CommandEntry>,
+    #[serde(default)]
+    pub decisions: Vec<PolicyEntry>,
+    /// Command Output Log (`.neurust/logs/...`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log: Option<String>,
+}
+
+/// `.neurust/history`
+pub fn history_dir() -> PathBuf {
+    Path::new(".neurust").join("history")
+}
+
+fn run_dir(id: &str) -> PathBuf {
+    history_dir().join(id)
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// File ရဲ့ SHA-256 (မရှိရင် None) - Plan Files လည်း သုံးတယ်
+pub fn hash_file(path: &str) -> Option<String> {
+    fs::read(path).ok().map(|bytes| hash_bytes(&bytes))
+}
+
+/// Commit မလုပ်ရသေးတဲ့ Transaction အရေအတွက်
+static OPEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Plan က File တွေ ပြင်နေတုန်း (Transaction ဖွင့်ထားတုန်း) ဆိုရင် true - `serve` က Cancel လုပ်ရင် Future ကို မ Drop ဘဲ Commit အထိ စောင့်မယ်
+pub fn in_transaction() -> bool {
+    OPEN.load(Ordering::SeqCst) > 0
+}
+
+/// Transaction Drop / Commit မှာ လျှော့မယ်
+struct OpenRun;
+
+impl OpenRun {
+    fn enter() -> Self {
+        OPEN.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for OpenRun {
+    fn drop(&mut self) {
+        OPEN.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Plan Run တစ်ခုစာ Transaction (Healing Fix Plan တွေလည်း ဒီ Run ထဲ ဝင်မယ်)
+pub struct Transaction {
+    _open: OpenRun,
+    record: RunRecord,
+    enabled: bool,
+    /// Snapshot / Command တစ်ခုခု ရှိမှ Directory ဆောက်မယ် (Read-only Plan တွေအတွက် Run အလွတ် မဆောက်ဘူး)
+    started: bool,
+    keep: usize,
+}
+
+impl Transaction {
+    pub fn begin(label: &str, config: &HistoryConfig) -> Self {
+        let now = chrono::Local::now();
+        Self {
+            _open: OpenRun::enter(),
+            record: RunRecord {
+                id: format!("{}-{:03}", now.format("%Y%m%d-%H%M%S"), now.timestamp_subsec_millis()),
+                started_at: now.to_rfc3339(),
+                label: label.chars().take(200).collect(),
+                status: RunStatus::Applied,
+                files: Vec::new(),
+                commands: Vec::new(),
+                decisions: Vec::new(),
+                log: None,
+            },
+            enabled: config.enabled,
+            started: false,
+            keep: config.keep,
+        }
+    }
+
+    /// File ကို မပြင်ခင် ခေါ်ရမယ် (Run တစ်ခုမှာ File တစ်ခုကို ပထမဆုံးအကြိမ်ပဲ Snapshot ယူမယ်)
+    pub fn snapshot(&mut self, path: &str) -> Result<()> {
+        if !self.enabled || self.record.files.iter().any(|f| f.path == path) {
+            return Ok(());
+        }
+        self.start()?;
+
+        let existed = Path::new(path).is_file();
+        if existed {
+            let bytes = fs::read(path).with_context(|| format!("Failed to snapshot {}", path))?;
+            fs::write(self.snapshot_path(self.record.files.len()), bytes)?;
+        }
+        self.record.files.push(FileEntry { path: path.to_string(), existed, after: None });
+        self.save()
+    }
+
+    pub fn record_command(&mut self, outcome: &CommandOutcome) {
+        if !self.enabled || self.start().is_err() {
+            return;
+        }
+        self.record.commands.push(CommandEntry {
+            command: outcome.command.clone(),
+            exit_code: outcome.exit_code,
+            skipped: outcome.skipped,
+            duration_secs: outcome.duration.as_secs_f64(),
+            stopped: outcome.stopped().map(str::to_string),
+        });
+        if self.record.log.is_none() {
+            self.record.log = cmd::run_log_path().map(|p| p.to_string_lossy().to_string());
+        }
+        let _ = self.save();
+    }
+
+    pub fn record_decision(&mut self, command: &str, assessment: &Assessment, approved: Option<bool>) {
+        if !self.enabled || self.start().is_err() {
+            return;
+        }
+        self.record.decisions.push(PolicyEntry {
+            command: command.to_string(),
+            decision: assessment.decision,
+            risks: assessment.risks.clone(),
+            rule: assessment.rule.clone(),
+            approved,
+        });
+        let _ = self.save();
+    }
+
+    /// Run ပြီးချိန် Hash တွေ မှတ်ပြီး သိမ်းမယ် -> Run ID (ဘာမှ မထိခဲ့ရင် None)
+    pub fn commit(mut self) -> Option<String> {
+        if !self.started {
+            return None;
+        }
+        for file in self.record.files.iter_mut() {
+            file.after = hash_file(&file.path);
+        }
+        if let Err(e) = self.save() {
+            println!("{} Could not save run history: {}", "⚠️".yellow(), e);
+        }
+        prune(self.keep);
+        Some(self.record.id)
+    }
+
+    fn start(&mut self) -> Result<()> {
+        if !self.started {
+            fs::create_dir_all(run_dir(&self.record.id).join("files"))
+                .context("Failed to create run history directory")?;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    fn snapshot_path(&self, index: usize) -> PathBuf {
+        run_dir(&self.record.id).join("files").join(index.to_string())
+    }
+
+    fn save(&self) -> Result<()> {
+        self.record.save()
+    }
+}
+
+impl RunRecord {
+    fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(run_dir(&self.id).join("run.json"), content)?;
+        Ok(())
+    }
+
+    /// Run ပြီးနောက် တခြားတစ်ယောက် (သို့) နောက် Run က ထပ်ပြင်ထားတဲ့ Files
+    pub fn drifted(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .filter(|f| hash_file(&f.path) != f.after)
+            .map(|f| f.path.as_str())
+            .collect()
+    }
+
+    /// Snapshot တွေကို ပြောင်းပြန်အစဉ်နဲ့ ပြန်ထားမယ် (Run က ဆောက်ခဲ့တဲ့ File တွေကို ဖျက်မယ်) -> ပြန်ထားခဲ့တဲ့ Paths
+    pub fn rollback(&mut self) -> Result<Vec<String>> {
+        if self.status == RunStatus::Undone {
+            bail!("Run {} was already undone", self.id);
+        }
+
+        let mut restored = Vec::new();
+        for (index, file) in self.files.iter().enumerate().rev() {
+            let path = Path::new(&file.path);
+            if file.existed {
+                let snapshot = run_dir(&self.id).join("files").join(index.to_string());
+                let bytes = fs::read(&snapshot).with_context(|| format!("Missing snapshot for {}", file.path))?;
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, bytes).with_context(|| format!("Failed to restore {}", file.path))?;
+            } else if path.is_file() {
+                fs::remove_file(path).with_context(|| format!("Failed to remove {}", file.path))?;
+            }
+            restored.push(file.path.clone());
+        }
+
+        self.status = RunStatus::Undone;
+        self.save()?;
+        Ok(restored)
+    }
+}
+
+pub fn load_run(id: &str) -> Result<RunRecord> {
+    if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+        bail!("Invalid run ID: {}", id);
+    }
+    let path = run_dir(id).join("run.json");
+    let content = fs::read_to_string(&path).with_context(|| format!("No run named {} in {}", id, history_dir().display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Corrupt run record: {}", path.display()))
+}
+
+/// Runs (အသစ်ဆုံး အရင်)
+pub fn list_runs() -> Vec<RunRecord> {
+    let Ok(entries) = fs::read_dir(history_dir()) else { return Vec::new() };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .collect();
+    // Run ID = Timestamp ဖြစ်လို့ String Sort နဲ့ အစဉ်မှန်တယ်
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids.iter().filter_map(|id| load_run(id).ok()).collect()
+}
+
+/// နောက်ဆုံး Undo မလုပ်ရသေးတဲ့ Run
+pub fn latest_applied() -> Option<RunRecord> {
+    list_runs().into_iter().find(|r| r.status == RunStatus::Applied)
+}
+
+/// `[history].keep` ထက် ပိုတဲ့ Run အဟောင်းတွေကို ဖျက်မယ်
+fn prune(keep: usize) {
+    for old in list_runs().iter().skip(keep.max(1)) {
+        let _ = fs::remove_dir_all(run_dir(&old.id));
+    }
+}
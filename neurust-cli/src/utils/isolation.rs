@@ -0,0 +1,205 @@
+use crate::utils::cmd::CommandOutcome;
+use crate::utils::config::{NeurustConfig, SandboxConfig};
+use crate::utils::sandbox::Sandbox;
+use colored::*;
+use regex::Regex;
+use serde::Serialize;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// --- 📦 COMMAND ISOLATION (Linux / bubblewrap) ---
+// `--sandbox` (သို့) `[sandbox].exec = true` ဆိုရင် `cmd::run_captured` က Command တွေကို bwrap ထဲမှာ Run မယ်။
+// - Filesystem: `/` ကို Read-only, Project Dir + cargo/npm Caches ကိုပဲ Write ခွင့်, /tmp က Private
+// - Network: `[sandbox].network = false` ဆိုရင် ပိတ်မယ်
+// - Limits: timeout (coreutils) + ulimit (CPU seconds / Virtual Memory)
+//   `memory_mb` က `ulimit -v` (Address Space) မို့ RSS ထက် အများကြီး ကြီးရမယ် - node / rustc က Virtual Memory ကို GB နဲ့ချီ Reserve လုပ်တယ်
+// Sandbox က ပိတ်လိုက်တဲ့ Operation တွေကို Output ထဲက ရှာပြီး Structured Violations အဖြစ် Agent ဆီ ပြန်ပို့မယ်။
+
+/// `--sandbox` CLI Flag (Process တစ်ခုလုံးအတွက်)
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// `timeout` ရဲ့ Exit Code
+const TIMEOUT_EXIT: i32 = 124;
+/// 128 + SIGXCPU
+const CPU_LIMIT_EXIT: i32 = 152;
+/// 128 + SIGKILL (CPU Hard Limit / OOM)
+const KILLED_EXIT: i32 = 137;
+
+pub fn force_enable() {
+    FORCED.store(true, Ordering::Relaxed);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationKind {
+    Filesystem,
+    Network,
+    Timeout,
+    Cpu,
+    Memory,
+}
+
+/// Sandbox က ပိတ်လိုက်တဲ့ Operation တစ်ခု
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub kind: ViolationKind,
+    pub detail: String,
+}
+
+pub struct Isolation {
+    settings: SandboxConfig,
+    project: PathBuf,
+}
+
+impl Isolation {
+    /// Sandbox Mode ဖွင့်ထားရင် Some
+    pub fn active() -> Option<Self> {
+        let settings = NeurustConfig::load().sandbox;
+        if !(settings.exec || FORCED.load(Ordering::Relaxed)) {
+            return None;
+        }
+        let project = env::current_dir().ok()?.canonicalize().ok()?;
+        Some(Self { settings, project })
+    }
+
+    /// `[sandbox].network` ကို မကြည့်ဘဲ Network ဖွင့်ပေးမယ်
+    pub fn with_network(mut self) -> Self {
+        self.settings.network = true;
+        self
+    }
+
+    /// bwrap Command ဆောက်မယ် (Linux မဟုတ် / bwrap မရှိရင် Run မလုပ်ဘဲ Err - Fail Closed)
+    pub fn command(&self, program: &str, args: &[&str], cwd: Option<&str>) -> Result<Command, String> {
+        if !cfg!(target_os = "linux") {
+            return Err("sandbox mode is only supported on Linux".to_string());
+        }
+        let Some(bwrap) = find_in_path("bwrap") else {
+            return Err("sandbox mode needs bubblewrap (`bwrap`); install it or run without --sandbox".to_string());
+        };
+
+        let workdir = match cwd {
+            Some(dir) => Path::new(dir).canonicalize().map_err(|e| format!("bad working directory {}: {}", dir, e))?,
+            None => self.project.clone(),
+        };
+
+        let mut cmd = Command::new(bwrap);
+        cmd.args(["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc", "--tmpfs", "/tmp"]);
+        cmd.args(["--unshare-all", "--die-with-parent", "--new-session"]);
+        if self.settings.network {
+            cmd.arg("--share-net");
+        }
+
+        for dir in self.writable_dirs(&workdir) {
+            let dir = dir.to_string_lossy().to_string();
+            cmd.args(["--bind-try", &dir, &dir]);
+        }
+        // Project က Writable ဆိုပေမယ့် `[sandbox].protected` (.git / Keypairs / .neurust/config.toml ...) ကို Read-only ပြန်ထပ်မယ်
+        let sandbox = Sandbox::new(&self.settings).map_err(|e| format!("cannot resolve protected paths: {}", e))?;
+        for path in sandbox.protected_paths() {
+            let path = path.to_string_lossy().to_string();
+            cmd.args(["--ro-bind-try", &path, &path]);
+        }
+        cmd.arg("--chdir").arg(&workdir);
+        cmd.arg("--");
+
+        // timeout -k 5 <secs> [sh -c 'ulimit ...; exec "$@"' neurust] program args...
+        cmd.args(["timeout", "-k", "5", &self.settings.timeout_secs.to_string()]);
+        let mut limits = Vec::new();
+        if let Some(cpu) = self.settings.cpu_secs {
+            limits.push(format!("ulimit -t {}", cpu));
+        }
+        if let Some(mb) = self.settings.memory_mb {
+            limits.push(format!("ulimit -v {}", mb * 1024));
+        }
+        if !limits.is_empty() {
+            cmd.args(["sh", "-c", &format!("{} && exec \"$@\"", limits.join(" && ")), "neurust-sandbox"]);
+        }
+        cmd.arg(program).args(args);
+        Ok(cmd)
+    }
+
+    /// Project + Build/Package Caches + `[sandbox].writable`
+    fn writable_dirs(&self, workdir: &Path) -> Vec<PathBuf> {
+        let mut dirs = vec![self.project.clone()];
+        if !workdir.starts_with(&self.project) {
+            dirs.push(workdir.to_path_buf());
+        }
+        if let Some(home) = dirs::home_dir() {
+            for cache in [".cargo/registry", ".cargo/git", ".npm", ".yarn", ".cache/yarn", ".cache/pnpm", ".local/share/pnpm", ".cache/solana"] {
+                dirs.push(home.join(cache));
+            }
+        }
+        if let Ok(target) = env::var("CARGO_TARGET_DIR") {
+            dirs.push(PathBuf::from(target));
+        }
+        dirs.extend(self.settings.writable.iter().map(PathBuf::from));
+        dirs
+    }
+
+    /// Output / Exit Code ထဲက Sandbox Denials ကို ရှာမယ်
+    pub fn violations(&self, outcome: &CommandOutcome) -> Vec<Violation> {
+        let mut found: Vec<Violation> = Vec::new();
+        let mut push = |kind: ViolationKind, detail: String| {
+            if found.len() < 8 && !found.iter().any(|v| v.kind == kind && v.detail == detail) {
+                found.push(Violation { kind, detail });
+            }
+        };
+
+        match outcome.exit_code {
+            Some(TIMEOUT_EXIT) => push(ViolationKind::Timeout, format!("killed after {}s ([sandbox].timeout_secs)", self.settings.timeout_secs)),
+            Some(CPU_LIMIT_EXIT) => push(
+                ViolationKind::Cpu,
+                format!("CPU time limit of {}s exceeded ([sandbox].cpu_secs)", self.settings.cpu_secs.unwrap_or(0)),
+            ),
+            Some(KILLED_EXIT) if self.settings.cpu_secs.is_some() || self.settings.memory_mb.is_some() => push(
+                if self.settings.cpu_secs.is_some() { ViolationKind::Cpu } else { ViolationKind::Memory },
+                "killed by the kernel (CPU or memory limit reached)".to_string(),
+            ),
+            _ => {}
+        }
+
+        let patterns: [(ViolationKind, &str, bool); 3] = [
+            (ViolationKind::Filesystem, r"(?i)read-only file system|operation not permitted", true),
+            (
+                ViolationKind::Network,
+                r"(?i)could not resolve host|temporary failure in name resolution|network is unreachable|failed to lookup address|dns error|getaddrinfo|ENOTFOUND|EAI_AGAIN",
+                !self.settings.network,
+            ),
+            (ViolationKind::Memory, r"(?i)memory allocation of \d+ bytes failed|cannot allocate memory|out of memory", self.settings.memory_mb.is_some()),
+        ];
+        for (kind, pattern, enabled) in patterns {
+            if !enabled {
+                continue;
+            }
+            let re = Regex::new(pattern).unwrap();
+            for line in outcome.stderr.lines().chain(outcome.stdout.lines()).filter(|l| re.is_match(l)).take(3) {
+                push(kind, line.trim().chars().take(200).collect());
+            }
+        }
+        found
+    }
+}
+
+/// Violations ကို Model ဆီ ပို့မယ့် Text (JSON - Agent က Plan ကို ပြင်ဖို့)
+pub fn describe(violations: &[Violation]) -> String {
+    format!(
+        "--- sandbox denials (the command ran in a sandbox: writes only inside the project and package caches; adapt the plan instead of retrying) ---\n{}\n",
+        serde_json::to_string(violations).unwrap_or_default()
+    )
+}
+
+/// Run မလုပ်ခင် တစ်ကြိမ်ပဲ ပြမယ့် Banner
+pub fn print_banner(settings: &SandboxConfig) {
+    println!(
+        "{} Sandbox mode: writes limited to the project, network {}, timeout {}s",
+        "📦".cyan(),
+        if settings.network { "allowed" } else { "blocked" },
+        settings.timeout_secs
+    );
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    env::var_os("PATH").and_then(|paths| env::split_paths(&paths).map(|dir| dir.join(program)).find(|p| p.is_file()))
+}
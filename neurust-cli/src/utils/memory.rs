@@ -69,4 +69,33 @@ impl ProjectMemory {
     pub fn append_file_context(&mut self, path: &str, content: &str) {
         self.project_context.push_str(&format!("\n>>>> FILE START: {} <<<<\n{}\n>>>> FILE END: {} <<<<\n", path, content, path));
     }
-}
\ No newline at end of file
+
+    /// File ဖျက်/ပြင်လိုက်ရင် Context ထဲက အဟောင်း Block ကို ဖယ်မယ် (`./src/x` နဲ့ `src/x` နှစ်မျိုးလုံး)
+    pub fn forget_file_context(&mut self, path: &str) {
+        let path = path.trim_start_matches("./");
+        for variant in [path.to_string(), format!("./{}", path)] {
+            let start_marker = format!("\n>>>> FILE START: {} <<<<\n", variant);
+            let end_marker = format!("\n>>>> FILE END: {} <<<<\n", variant);
+
+            while let Some(start) = self.project_context.find(&start_marker) {
+                let Some(end) = self.project_context[start..].find(&end_marker) else { break };
+                self.project_context.replace_range(start..start + end + end_marker.len(), "");
+            }
+        }
+    }
+
+    /// 🔥 NEW: နောက်ဆုံးထိခဲ့တဲ့ File တွေကို မှတ်ထားမယ် (အသစ်ဆုံး နောက်ဆုံးမှာ၊ အများဆုံး ၂၀)
+    pub fn touch_file(&mut self, path: &str) {
+        self.active_files.retain(|f| f != path);
+        self.active_files.push(path.to_string());
+        if self.active_files.len() > 20 {
+            self.active_files.remove(0);
+        }
+    }
+
+    /// Delete / Rename / ask_user Answer လို Event တွေကို Summary ထဲ မှတ်မယ်
+    pub fn record_event(&mut self, entry: &str) {
+        self.last_interaction = chrono::Local::now().to_rfc3339();
+        self.summary.push_str(&format!("- {}\n", entry));
+    }
+}
@@ -1,8 +1,24 @@
+pub mod anchor;
+pub mod ci;
 pub mod cmd;
+pub mod config;
 pub mod context;
 pub mod deps;
+pub mod diagnostics;
 pub mod diff;
+pub mod events;
 pub mod fs;
+pub mod healing;
+pub mod history;
+pub mod isolation;
 pub mod repl;
+pub mod rules;
+pub mod sandbox;
+pub mod tools;
+pub mod verify;
 pub mod memory;
-pub mod executor;
\ No newline at end of file
+pub mod patch;
+pub mod policy;
+pub mod executor;
+pub mod testrun;
+pub mod planfile;
\ No newline at end of file
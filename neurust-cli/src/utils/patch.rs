@@ -0,0 +1,342 @@
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+
+// --- 🩹 PATCH ENGINE (`apply_patch` action) ---
+// File တစ်ခုလုံး ပြန်မရေးဘဲ လိုတဲ့နေရာလေးပဲ ပြင်မယ်။ Format ၃ မျိုး လက်ခံတယ်:
+// 1. `edits: [{ "search": "...", "replace": "..." }]`
+// 2. `patch` ထဲမှာ `<<<<<<< SEARCH` / `=======` / `>>>>>>> REPLACE` Blocks
+// 3. `patch` ထဲမှာ Unified Diff (`@@ -a,b +c,d @@` Hunks)
+// Model ရဲ့ Line Number / Indentation လွဲတာကို Fuzzy Anchoring နဲ့ ခံနိုင်အောင် လုပ်ထားတယ်။
+// File ရဲ့ Line Ending (CRLF / LF) ကို ထိန်းထားမယ်။
+
+const SEARCH_MARKER: &str = "<<<<<<< SEARCH";
+const DIVIDER_MARKER: &str = "=======";
+const REPLACE_MARKER: &str = ">>>>>>> REPLACE";
+
+/// Plan Action ထဲက Edit တွေကို `original` ပေါ် Apply လုပ်ပြီး File Content အသစ် ပြန်ပေးမယ်
+pub fn apply_action(original: &str, action: &Value) -> Result<String> {
+    if let Some(edits) = action["edits"].as_array() {
+        let mut content = original.to_string();
+        for (index, edit) in edits.iter().enumerate() {
+            let search = edit["search"]
+                .as_str()
+                .ok_or_else(|| anyhow!("edit #{} has no `search` text", index + 1))?;
+            let replace = edit["replace"].as_str().unwrap_or("");
+            content = search_replace(&content, search, replace)
+                .map_err(|e| anyhow!("edit #{}: {}", index + 1, e))?;
+        }
+        return Ok(content);
+    }
+
+    let patch = action["patch"]
+        .as_str()
+        .ok_or_else(|| anyhow!("apply_patch needs either `patch` or `edits`"))?;
+
+    if patch.contains(SEARCH_MARKER) {
+        let mut content = original.to_string();
+        for (index, (search, replace)) in parse_search_blocks(patch)?.iter().enumerate() {
+            content = search_replace(&content, search, replace)
+                .map_err(|e| anyhow!("block #{}: {}", index + 1, e))?;
+        }
+        Ok(content)
+    } else {
+        apply_unified_diff(original, patch)
+    }
+}
+
+/// `<<<<<<< SEARCH ... ======= ... >>>>>>> REPLACE` Blocks -> (search, replace)
+fn parse_search_blocks(patch: &str) -> Result<Vec<(String, String)>> {
+    enum State { Outside, Search, Replace }
+
+    let mut blocks = Vec::new();
+    let mut state = State::Outside;
+    let (mut search, mut replace) = (Vec::new(), Vec::new());
+
+    for line in patch.lines() {
+        match state {
+            State::Outside if line.trim_end() == SEARCH_MARKER => state = State::Search,
+            State::Outside => {}
+            State::Search if line.trim_end() == DIVIDER_MARKER => state = State::Replace,
+            State::Search => search.push(line),
+            State::Replace if line.trim_end() == REPLACE_MARKER => {
+                blocks.push((search.join("\n"), replace.join("\n")));
+                search.clear();
+                replace.clear();
+                state = State::Outside;
+            }
+            State::Replace => replace.push(line),
+        }
+    }
+
+    if !matches!(state, State::Outside) {
+        bail!("unterminated SEARCH/REPLACE block");
+    }
+    if blocks.is_empty() {
+        bail!("no SEARCH/REPLACE blocks found");
+    }
+    Ok(blocks)
+}
+
+/// Search Text ကို တစ်နေရာတည်းမှာပဲ တွေ့ရမယ် (Exact -> Whitespace-insensitive Line Match)
+fn search_replace(content: &str, search: &str, replace: &str) -> Result<String> {
+    if search.trim().is_empty() {
+        bail!("empty search text");
+    }
+
+    // CRLF File ဆိုရင် Model ရဲ့ LF Text ကိုလည်း CRLF ပြောင်းပြီးမှ ရှာ/အစားထိုး မယ်
+    let eol = line_ending(content);
+    let (search, replace) = (with_line_ending(search, eol), with_line_ending(replace, eol));
+    match content.matches(&search).count() {
+        1 => return Ok(content.replacen(&search, &replace, 1)),
+        0 => {}
+        n => bail!("search text matches {} places; include more surrounding lines", n),
+    }
+
+    // Fuzzy: Line တစ်ကြောင်းချင်းစီကို Trim လုပ်ပြီး နှိုင်းယှဉ်မယ်
+    let lines: Vec<&str> = content.lines().collect();
+    let needle = trim_blank_edges(search.lines().collect());
+
+    let positions = find_all(&lines, &needle, |a, b| a.trim() == b.trim());
+    let start = match positions.as_slice() {
+        [single] => *single,
+        [] => bail!("search text not found (first line: `{}`)", needle[0].trim()),
+        many => bail!("search text matches {} places; include more surrounding lines", many.len()),
+    };
+
+    // Model ရဲ့ Indentation နဲ့ File ရဲ့ Indentation မတူရင် Replace ကို ညှိပေးမယ်
+    let indents = indent_pairs(&needle, &lines[start..]);
+    let replacement: Vec<String> = trim_blank_edges(replace.lines().collect())
+        .into_iter()
+        .map(|line| reindent(line, &indents))
+        .collect();
+
+    let mut result: Vec<String> = lines[..start].iter().map(|l| l.to_string()).collect();
+    result.extend(replacement);
+    result.extend(lines[start + needle.len()..].iter().map(|l| l.to_string()));
+    Ok(join_lines(&result, content))
+}
+
+struct Hunk {
+    old_start: usize,
+    old: Vec<String>,
+    new: Vec<String>,
+}
+
+/// Unified Diff Hunks တွေကို ခွဲထုတ်မယ် (File Headers `---`/`+++` ကို ကျော်မယ်)
+fn parse_hunks(patch: &str) -> Result<Vec<Hunk>> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut lines = patch.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        if line.starts_with("--- ") && lines.peek().is_some_and(|next| next.starts_with("+++ ")) {
+            lines.next();
+            continue;
+        }
+        if let Some(header) = line.strip_prefix("@@") {
+            let old_start = header
+                .trim_start()
+                .strip_prefix('-')
+                .and_then(|rest| rest.split([',', ' ']).next())
+                .and_then(|n| n.parse::<usize>().ok())
+                .ok_or_else(|| anyhow!("malformed hunk header: {}", line))?;
+            hunks.push(Hunk { old_start, old: Vec::new(), new: Vec::new() });
+            continue;
+        }
+
+        let Some(hunk) = hunks.last_mut() else { continue }; // diff --git / index lines
+        match line.chars().next() {
+            Some('+') => hunk.new.push(line[1..].to_string()),
+            Some('-') => hunk.old.push(line[1..].to_string()),
+            Some('\\') => {} // "\ No newline at end of file"
+            Some(' ') => {
+                hunk.old.push(line[1..].to_string());
+                hunk.new.push(line[1..].to_string());
+            }
+            // Model အများစုက Context Line အလွတ်တွေရဲ့ Space ကို ဖြုတ်ပစ်တတ်တယ်
+            None => {
+                hunk.old.push(String::new());
+                hunk.new.push(String::new());
+            }
+            Some(_) => bail!("unexpected line in hunk: {}", line),
+        }
+    }
+
+    if hunks.is_empty() {
+        bail!("patch has no hunks (expected unified diff or SEARCH/REPLACE blocks)");
+    }
+    Ok(hunks)
+}
+
+/// Hunk တစ်ခုချင်းစီကို Header Line Number အနီးဆုံးမှာ ရှာပြီး Apply လုပ်မယ်
+fn apply_unified_diff(original: &str, patch: &str) -> Result<String> {
+    let mut lines: Vec<String> = original.lines().map(|l| l.to_string()).collect();
+    let mut offset: isize = 0;
+
+    for (index, hunk) in parse_hunks(patch)?.iter().enumerate() {
+        let mut new = hunk.new.clone();
+        let position = if hunk.old.is_empty() {
+            // Pure Insertion (`@@ -5,0 +6,2 @@` = Line 5 နောက်မှာ ထည့်)
+            (hunk.old_start as isize + offset).clamp(0, lines.len() as isize) as usize
+        } else {
+            let expected = (hunk.old_start as isize - 1 + offset).max(0) as usize;
+            let needle: Vec<&str> = hunk.old.iter().map(|l| l.as_str()).collect();
+            let haystack: Vec<&str> = lines.iter().map(|l| l.as_str()).collect();
+
+            match nearest(&find_all(&haystack, &needle, |a, b| a == b), expected) {
+                Some(position) => position,
+                None => {
+                    let position = nearest(&find_all(&haystack, &needle, |a, b| a.trim() == b.trim()), expected)
+                        .ok_or_else(|| {
+                            anyhow!("hunk #{} does not apply (expected near line {})", index + 1, hunk.old_start)
+                        })?;
+                    // Whitespace မတူဘဲ ကိုက်တာ -> Hunk ရဲ့ Indentation ကို File ရဲ့ Indentation နဲ့ ညှိမယ်
+                    let indents = indent_pairs(&needle, &haystack[position..]);
+                    new = new.iter().map(|line| reindent(line, &indents)).collect();
+                    position
+                }
+            }
+        };
+
+        lines.splice(position..position + hunk.old.len(), new);
+        // `@@ -0,1 ...` လို Line 0 Header (Model အမှား) ကိုလည်း Line 1 လို့ ယူမယ်
+        let anchor = if hunk.old.is_empty() { hunk.old_start } else { hunk.old_start.saturating_sub(1) };
+        offset = position as isize - anchor as isize + hunk.new.len() as isize - hunk.old.len() as isize;
+    }
+
+    Ok(join_lines(&lines, original))
+}
+
+fn find_all(haystack: &[&str], needle: &[&str], eq: impl Fn(&str, &str) -> bool) -> Vec<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return Vec::new();
+    }
+    (0..=haystack.len() - needle.len())
+        .filter(|&start| needle.iter().enumerate().all(|(i, n)| eq(haystack[start + i], n)))
+        .collect()
+}
+
+fn nearest(positions: &[usize], expected: usize) -> Option<usize> {
+    positions.iter().copied().min_by_key(|p| p.abs_diff(expected))
+}
+
+fn trim_blank_edges(mut lines: Vec<&str>) -> Vec<&str> {
+    while lines.first().is_some_and(|l| l.trim().is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn leading_ws(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// ကိုက်ခဲ့တဲ့ Lines တွေရဲ့ (Model Indent, File Indent) အတွဲများ (Level တစ်ခုချင်းစီ - e.g. 4 Spaces -> Tab)
+fn indent_pairs(needle: &[&str], matched: &[&str]) -> Vec<(String, String)> {
+    needle
+        .iter()
+        .zip(matched)
+        .filter(|(n, _)| !n.trim().is_empty())
+        .map(|(n, m)| (leading_ws(n).to_string(), leading_ws(m).to_string()))
+        .collect()
+}
+
+/// Line ရဲ့ Indent ကို အရှည်ဆုံး ကိုက်တဲ့ Model Indent နေရာမှာ File Indent နဲ့ အစားထိုးမယ်
+fn reindent(line: &str, indents: &[(String, String)]) -> String {
+    if line.trim().is_empty() {
+        return line.to_string();
+    }
+    match indents.iter().filter(|(from, _)| line.starts_with(from.as_str())).max_by_key(|(from, _)| from.len()) {
+        Some((from, to)) => format!("{}{}", to, &line[from.len()..]),
+        None => line.to_string(),
+    }
+}
+
+/// Original File ရဲ့ Line Ending နဲ့ Trailing Newline ကို ထိန်းထားမယ်
+fn join_lines(lines: &[String], original: &str) -> String {
+    let eol = line_ending(original);
+    let mut joined = lines.join(eol);
+    if original.ends_with('\n') || (original.is_empty() && !joined.is_empty()) {
+        joined.push_str(eol);
+    }
+    joined
+}
+
+/// CRLF Line ပါရင် "\r\n" (Mixed File ကို CRLF လို့ ယူမယ်)
+fn line_ending(content: &str) -> &'static str {
+    if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn with_line_ending(text: &str, eol: &str) -> String {
+    text.replace("\r\n", "\n").replace('\n', eol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(original: &str, search: &str, replace: &str) -> Result<String> {
+        apply_action(original, &json!({ "edits": [{ "search": search, "replace": replace }] }))
+    }
+
+    fn diff(original: &str, patch: &str) -> Result<String> {
+        apply_action(original, &json!({ "patch": patch }))
+    }
+
+    #[test]
+    fn exact_edit_keeps_crlf() {
+        let original = "fn a() {\r\n    1\r\n}\r\n";
+        let patched = edit(original, "    1\n}", "    2\n}").unwrap();
+        assert_eq!(patched, "fn a() {\r\n    2\r\n}\r\n");
+    }
+
+    #[test]
+    fn fuzzy_edit_keeps_crlf_and_reindents() {
+        let original = "mod m {\r\n    fn a() {\r\n        1\r\n    }\r\n}\r\n";
+        let patched = edit(original, "fn a() {\n    1\n}", "fn a() {\n    2\n}").unwrap();
+        assert_eq!(patched, "mod m {\r\n    fn a() {\r\n        2\r\n    }\r\n}\r\n");
+    }
+
+    #[test]
+    fn unified_diff_keeps_crlf() {
+        let original = "a\r\nb\r\nc\r\n";
+        let patched = diff(original, "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n").unwrap();
+        assert_eq!(patched, "a\r\nB\r\nc\r\n");
+    }
+
+    #[test]
+    fn trimmed_hunk_is_reindented_to_the_file() {
+        let original = "impl A {\n\tfn a() {\n\t\tx();\n\t}\n}\n";
+        let patch = "@@ -2,3 +2,4 @@\n     fn a() {\n         x();\n+        y();\n     }\n";
+        assert_eq!(diff(original, patch).unwrap(), "impl A {\n\tfn a() {\n\t\tx();\n\t\ty();\n\t}\n}\n");
+    }
+
+    #[test]
+    fn hunk_anchors_near_the_header_line() {
+        let original = "x\ny\nx\ny\n";
+        let patched = diff(original, "@@ -3,2 +3,2 @@\n x\n-y\n+z\n").unwrap();
+        assert_eq!(patched, "x\ny\nx\nz\n");
+    }
+
+    #[test]
+    fn pure_insertion_and_missing_trailing_newline() {
+        assert_eq!(diff("a\nb", "@@ -1,0 +2,1 @@\n+new\n").unwrap(), "a\nnew\nb");
+    }
+
+    #[test]
+    fn search_blocks_and_errors() {
+        let patch = "<<<<<<< SEARCH\nlet x = 1;\n=======\nlet x = 2;\n>>>>>>> REPLACE\n";
+        assert_eq!(diff("let x = 1;\n", patch).unwrap(), "let x = 2;\n");
+        assert!(diff("a\n", "<<<<<<< SEARCH\na\n=======\n").is_err());
+        assert!(edit("a\na\n", "a", "b").unwrap_err().to_string().contains("2 places"));
+        assert!(edit("a\n", "  ", "b").is_err());
+        assert!(diff("a\n", "@@ -1 +1 @@\n-zzz\n+y\n").is_err());
+    }
+}
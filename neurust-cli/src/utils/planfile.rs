@@ -0,0 +1,410 @@
+use crate::utils::config::NeurustConfig;
+use crate::utils::policy::{CommandPolicy, Decision};
+use crate::utils::sandbox::Sandbox;
+use crate::utils::{diff, fs, history, patch};
+use anyhow::{bail, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use similar::TextDiff;
+use std::collections::{BTreeMap, BTreeSet};
+
+// --- 📋 PLAN FILES (`neurust ask --dry-run / --plan-out`, `neurust apply`) ---
+// Plan ကို Disk မထိခင် Preview ပြမယ်၊ နောက်မှ ပြန် Apply ဖို့ JSON File အဖြစ် သိမ်းမယ်။
+// Plan ထိမယ့် File တိုင်းရဲ့ မူလ SHA-256 ကို မှတ်ထားပြီး Apply ချိန်မှာ Tree ပြောင်းသွားရင် ငြင်းမယ် (သို့)
+// `--rebase` နဲ့ Edit တွေကို လက်ရှိ Content ပေါ် ပြန်တင်မယ် (Conflict တစ်ခုရှိရင်တောင် ဘာမှ မထိဘဲ ရပ်မယ်)။
+
+pub const PLAN_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanFile {
+    pub version: u32,
+    pub created_at: String,
+    pub prompt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Server ပေးတဲ့ Plan Actions (မပြောင်းဘဲ)
+    pub plan: Value,
+    /// Plan ထိမယ့် File တွေရဲ့ မူလ State
+    pub files: Vec<BaseFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseFile {
+    /// Project-relative Path
+    pub path: String,
+    /// None = Plan သိမ်းတုန်းက File မရှိခဲ့
+    pub sha256: Option<String>,
+    /// `create_file` က ရှိပြီးသား File ကို Overwrite လုပ်ရင် မူလ -> အသစ် Unified Diff (`--rebase` အတွက်)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// Plan သိမ်းပြီးနောက် ပြောင်းသွားတဲ့ File
+pub struct Changed {
+    pub path: String,
+    /// "modified" / "deleted" / "created"
+    pub how: &'static str,
+}
+
+/// `--rebase` ရလဒ်
+pub struct Rebase {
+    pub plan: Value,
+    /// e.g. "src/lib.rs: rewrite replayed as a patch"
+    pub rebased: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+impl PlanFile {
+    /// Response ထဲက Plan + Target Files ရဲ့ လက်ရှိ Hash
+    pub fn capture(prompt: &str, response: &Value) -> Result<Self> {
+        let sandbox = Sandbox::new(&NeurustConfig::load().sandbox)?;
+        let actions = response["plan"].as_array().cloned().unwrap_or_default();
+
+        let mut files: Vec<BaseFile> = Vec::new();
+        for action in &actions {
+            for raw in targets(action) {
+                let path = normalize(Some(&sandbox), raw);
+                // ပထမဆုံး ထိတဲ့ Action မတိုင်ခင် State ကိုပဲ မှတ်မယ်
+                if files.iter().any(|f| f.path == path) {
+                    continue;
+                }
+                let original = fs::read_file(&path).ok();
+                let diff = match (action["action"].as_str(), &original) {
+                    (Some("create_file"), Some(old)) => {
+                        let new = action["content"].as_str().unwrap_or("");
+                        Some(TextDiff::from_lines(old.as_str(), new).unified_diff().context_radius(3).header(&path, &path).to_string())
+                    }
+                    _ => None,
+                };
+                files.push(BaseFile { sha256: history::hash_file(&path), path, diff });
+            }
+        }
+
+        Ok(Self {
+            version: PLAN_FILE_VERSION,
+            created_at: chrono::Local::now().to_rfc3339(),
+            prompt: prompt.to_string(),
+            message: response["message"].as_str().filter(|m| !m.trim().is_empty()).map(str::to_string),
+            plan: Value::Array(actions),
+            files,
+        })
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path).with_context(|| format!("Cannot read plan file {}", path))?;
+        let file: Self = serde_json::from_str(&content).with_context(|| format!("{} is not a Neurust plan file", path))?;
+        if file.version > PLAN_FILE_VERSION {
+            bail!("{} was written by a newer Neurust (plan file version {}); please upgrade", path, file.version);
+        }
+        Ok(file)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?).with_context(|| format!("Cannot write plan file {}", path))
+    }
+
+    pub fn action_count(&self) -> usize {
+        self.plan.as_array().map(|a| a.len()).unwrap_or(0)
+    }
+
+    /// Hash မတူတော့တဲ့ Files
+    pub fn changed(&self) -> Vec<Changed> {
+        self.files
+            .iter()
+            .filter_map(|file| {
+                let current = history::hash_file(&file.path);
+                let how = match (&file.sha256, &current) {
+                    (a, b) if a == b => return None,
+                    (Some(_), None) => "deleted",
+                    (None, Some(_)) => "created",
+                    _ => "modified",
+                };
+                Some(Changed { path: file.path.clone(), how })
+            })
+            .collect()
+    }
+
+    /// ပြောင်းသွားတဲ့ Files ပေါ်မှာ Plan ကို Memory ထဲမှာ အစဉ်လိုက် ပြန် Run ကြည့်မယ်
+    /// - `apply_patch`: Context နဲ့ ရှာတာမို့ လက်ရှိ Content ပေါ် တိုက်ရိုက် Apply လို့ရရမယ်
+    /// - `create_file` (Overwrite): မူလ -> အသစ် Diff ကို `apply_patch` အဖြစ် ပြောင်းမယ်
+    /// - `delete_file` / အသစ်ဆောက်မယ့် File ကို တခြားသူ ဆောက်ပြီးသား / `rename_file` ရဲ့ `to` ပြောင်းသွား: Conflict
+    pub fn rebase(&self, changed: &[Changed]) -> Rebase {
+        let changed: BTreeSet<&str> = changed.iter().map(|c| c.path.as_str()).collect();
+        let sandbox = Sandbox::new(&NeurustConfig::load().sandbox).ok();
+        let mut state = VirtualTree::default();
+        let mut touched: BTreeSet<String> = BTreeSet::new();
+        let mut result = Rebase { plan: Value::Null, rebased: Vec::new(), conflicts: Vec::new() };
+        let mut actions = Vec::new();
+
+        for action in self.plan.as_array().into_iter().flatten() {
+            let kind = action["action"].as_str().unwrap_or("");
+            let path = action["path"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+            let first_touch = !path.is_empty() && touched.insert(path.clone());
+            let drifted = first_touch && changed.contains(path.as_str());
+
+            match kind {
+                "create_file" => {
+                    let content = action["content"].as_str().unwrap_or("");
+                    if !drifted {
+                        state.set(&path, Some(content.to_string()));
+                        actions.push(action.clone());
+                        continue;
+                    }
+                    let base = self.files.iter().find(|f| f.path == path);
+                    match (base.and_then(|b| b.diff.as_deref()), state.get(&path)) {
+                        (Some(diff), Some(current)) => match patch::apply_action(&current, &json!({ "patch": diff })) {
+                            Ok(merged) => {
+                                state.set(&path, Some(merged));
+                                result.rebased.push(format!("{}: rewrite replayed as a patch on your changes", path));
+                                actions.push(json!({
+                                    "action": "apply_patch",
+                                    "path": path,
+                                    "patch": diff,
+                                    "reason": action["reason"],
+                                }));
+                            }
+                            Err(e) => result.conflicts.push(format!("{}: your changes overlap the planned rewrite ({})", path, e)),
+                        },
+                        (Some(_), None) => result.conflicts.push(format!("{}: deleted since the plan was saved", path)),
+                        (None, Some(current)) if current == content => {
+                            result.rebased.push(format!("{}: already has the planned content", path));
+                        }
+                        (None, Some(_)) => result.conflicts.push(format!("{}: created by someone else since the plan was saved", path)),
+                        (None, None) => {
+                            state.set(&path, Some(content.to_string()));
+                            actions.push(action.clone());
+                        }
+                    }
+                }
+                "apply_patch" => match state.get(&path) {
+                    Some(current) => match patch::apply_action(&current, action) {
+                        Ok(patched) => {
+                            if drifted {
+                                result.rebased.push(format!("{}: patch re-anchored on your changes", path));
+                            }
+                            state.set(&path, Some(patched));
+                            actions.push(action.clone());
+                        }
+                        Err(e) => result.conflicts.push(format!("{}: patch no longer applies ({})", path, e)),
+                    },
+                    None => result.conflicts.push(format!("{}: file to patch no longer exists", path)),
+                },
+                "delete_file" if drifted => {
+                    result.conflicts.push(format!("{}: changed since the plan was saved; refusing to delete your changes", path));
+                }
+                "delete_file" => {
+                    state.set(&path, None);
+                    actions.push(action.clone());
+                }
+                "rename_file" => {
+                    let from = action["from"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+                    let to = action["to"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+                    touched.insert(from.clone());
+                    let to_drifted = touched.insert(to.clone()) && changed.contains(to.as_str());
+                    let Some(content) = state.get(&from) else {
+                        result.conflicts.push(format!("{}: file to rename no longer exists", from));
+                        continue;
+                    };
+                    if to_drifted {
+                        result.conflicts.push(format!("{}: changed since the plan was saved; refusing to overwrite it with {}", to, from));
+                        continue;
+                    }
+                    state.set(&from, None);
+                    state.set(&to, Some(content));
+                    actions.push(action.clone());
+                }
+                _ => actions.push(action.clone()),
+            }
+        }
+
+        result.plan = Value::Array(actions);
+        result
+    }
+}
+
+/// Disk ကို မထိဘဲ Plan ရဲ့ File ပြောင်းလဲမှုတွေကို အစဉ်လိုက် လိုက်မှတ်မယ် (Preview / Rebase)
+#[derive(Default)]
+struct VirtualTree {
+    files: BTreeMap<String, Option<String>>,
+}
+
+impl VirtualTree {
+    fn get(&self, path: &str) -> Option<String> {
+        match self.files.get(path) {
+            Some(content) => content.clone(),
+            None => fs::read_file(path).ok(),
+        }
+    }
+
+    fn set(&mut self, path: &str, content: Option<String>) {
+        self.files.insert(path.to_string(), content);
+    }
+}
+
+/// `--dry-run`: Plan ထဲက Action တိုင်းကို (Diff / Command / URL) ပြမယ်၊ ဘာမှ မရေး မ Run ဘူး
+pub fn preview(plan: &Value) {
+    let config = NeurustConfig::load();
+    let sandbox = Sandbox::new(&config.sandbox).ok();
+    let policy = CommandPolicy::new(&config.policy);
+    let mut state = VirtualTree::default();
+    let (mut file_changes, mut commands, mut urls) = (0, 0, 0);
+
+    let actions = plan.as_array().cloned().unwrap_or_default();
+    println!("\n{} Plan preview ({} action(s)):", "📋".cyan(), actions.len());
+
+    for action in &actions {
+        let kind = action["action"].as_str().unwrap_or("");
+        let reason = action["reason"].as_str().unwrap_or("");
+        let path = action["path"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+
+        match kind {
+            "create_file" | "apply_patch" => {
+                file_changes += 1;
+                warn_blocked(sandbox.as_ref(), &path);
+                let old = state.get(&path);
+                let new = if kind == "create_file" {
+                    Ok(action["content"].as_str().unwrap_or("").to_string())
+                } else {
+                    match &old {
+                        Some(current) => patch::apply_action(current, action),
+                        None => Err(anyhow::anyhow!("file does not exist")),
+                    }
+                };
+                match new {
+                    Ok(new) => {
+                        diff::print_diff(&path, old.as_deref().unwrap_or(""), &new, reason);
+                        state.set(&path, Some(new));
+                    }
+                    Err(e) => println!("\n{} PATCH {} would fail: {}", "❌".red(), path, e),
+                }
+            }
+            "delete_file" => {
+                file_changes += 1;
+                let lines = state.get(&path).map(|c| c.lines().count()).unwrap_or(0);
+                println!("\n{} DELETE {} ({} lines) {}", "🗑️".red(), path.bold(), lines, reason.dimmed());
+                warn_blocked(sandbox.as_ref(), &path);
+                state.set(&path, None);
+            }
+            "rename_file" => {
+                file_changes += 1;
+                let from = action["from"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+                let to = action["to"].as_str().map(|p| normalize(sandbox.as_ref(), p)).unwrap_or_default();
+                println!("\n{} RENAME {} -> {} {}", "🔀".cyan(), from.bold(), to.bold(), reason.dimmed());
+                let content = state.get(&from);
+                state.set(&from, None);
+                state.set(&to, content);
+            }
+            "run_cmd" => {
+                commands += 1;
+                let program = action["program"].as_str().unwrap_or("");
+                let args: Vec<&str> = action["args"].as_array().map(|a| a.iter().filter_map(|v| v.as_str()).collect()).unwrap_or_default();
+                let command = std::iter::once(program).chain(args.iter().copied()).collect::<Vec<_>>().join(" ");
+                let assessment = policy.evaluate(program, &args);
+                let verdict = match assessment.decision {
+                    Decision::Allow => "allow".green(),
+                    Decision::Confirm => "confirm".yellow(),
+                    Decision::Deny => "deny".red(),
+                };
+                println!("\n{} RUN {}  [policy: {}]", "⚡".yellow(), command.bold(), verdict);
+                for risk in &assessment.risks {
+                    println!("   - {}", risk.yellow());
+                }
+            }
+            "read_url" => {
+                urls += 1;
+                println!("\n{} READ URL {}", "🌐".cyan(), action["url"].as_str().unwrap_or("").bold());
+            }
+            "read_file" | "list_dir" | "grep" => {
+                let target = action["pattern"].as_str().or(action["path"].as_str()).unwrap_or(".");
+                println!("\n{} {} {}", "🔎".blue(), kind, target);
+            }
+            "ask_user" => {
+                println!("\n{} ASK {}", "❓".blue(), action["question"].as_str().unwrap_or(""));
+            }
+            other => println!("\n{} Unknown action `{}` (the executor ignores it)", "⚠️".yellow(), other),
+        }
+    }
+
+    println!(
+        "\n{} {} file change(s), {} command(s), {} URL(s). Nothing was written or executed.",
+        "📋".cyan(),
+        file_changes,
+        commands,
+        urls
+    );
+}
+
+fn warn_blocked(sandbox: Option<&Sandbox>, path: &str) {
+    if let Some(Err(e)) = sandbox.map(|s| s.check_write(path)) {
+        println!("{} Would be blocked: {}", "🚫".red(), e);
+    }
+}
+
+/// Action က ထိမယ့် Paths (Plan File Hash အတွက်)
+fn targets(action: &Value) -> Vec<&str> {
+    match action["action"].as_str().unwrap_or("") {
+        "create_file" | "apply_patch" | "delete_file" => action["path"].as_str().into_iter().collect(),
+        "rename_file" => [action["from"].as_str(), action["to"].as_str()].into_iter().flatten().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Sandbox ရှိရင် Project-relative Path (Executor နဲ့ တူအောင်)၊ မရှိရင် `./` ကိုပဲ ဖယ်မယ်
+fn normalize(sandbox: Option<&Sandbox>, path: &str) -> String {
+    sandbox
+        .and_then(|s| s.check_write(path).ok())
+        .unwrap_or_else(|| path.trim_start_matches("./").to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan_file(plan: Value) -> PlanFile {
+        PlanFile { version: PLAN_FILE_VERSION, created_at: String::new(), prompt: String::new(), message: None, plan, files: Vec::new() }
+    }
+
+    fn changed(path: &str, how: &'static str) -> Changed {
+        Changed { path: path.to_string(), how }
+    }
+
+    #[test]
+    fn rename_onto_a_changed_target_conflicts() {
+        let file = plan_file(json!([
+            { "action": "create_file", "path": "rebase_fixture/old.rs", "content": "fn a() {}\n" },
+            { "action": "rename_file", "from": "rebase_fixture/old.rs", "to": "rebase_fixture/new.rs" },
+        ]));
+        let rebase = file.rebase(&[changed("rebase_fixture/new.rs", "created")]);
+        assert_eq!(rebase.conflicts.len(), 1);
+        assert!(rebase.conflicts[0].starts_with("rebase_fixture/new.rs: changed since the plan was saved"));
+        assert_eq!(rebase.plan.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rename_of_a_missing_file_conflicts() {
+        let file = plan_file(json!([{ "action": "rename_file", "from": "rebase_fixture/gone.rs", "to": "rebase_fixture/new.rs" }]));
+        let rebase = file.rebase(&[changed("rebase_fixture/gone.rs", "deleted")]);
+        assert_eq!(rebase.conflicts, vec!["rebase_fixture/gone.rs: file to rename no longer exists".to_string()]);
+    }
+
+    #[test]
+    fn patches_follow_earlier_renames() {
+        let file = plan_file(json!([
+            { "action": "create_file", "path": "rebase_fixture/old.rs", "content": "fn a() {}\n" },
+            { "action": "rename_file", "from": "rebase_fixture/old.rs", "to": "rebase_fixture/new.rs" },
+            { "action": "apply_patch", "path": "rebase_fixture/new.rs", "edits": [{ "search": "fn a() {}", "replace": "fn b() {}" }] },
+        ]));
+        let rebase = file.rebase(&[]);
+        assert!(rebase.conflicts.is_empty(), "{:?}", rebase.conflicts);
+        assert_eq!(rebase.plan.as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn deleting_a_drifted_file_conflicts() {
+        let file = plan_file(json!([{ "action": "delete_file", "path": "rebase_fixture/old.rs" }]));
+        let rebase = file.rebase(&[changed("rebase_fixture/old.rs", "modified")]);
+        assert_eq!(rebase.conflicts.len(), 1);
+        assert!(rebase.plan.as_array().unwrap().is_empty());
+    }
+}
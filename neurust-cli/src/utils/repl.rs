@@ -1,20 +1,54 @@
 use colored::*;
 use rustyline::error::ReadlineError;
 use rustyline::{Config, Editor};
-use rustyline::completion::FilenameCompleter;
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
 use rustyline::highlight::{Highlighter, MatchingBracketHighlighter};
 use rustyline::hint::HistoryHinter;
 use rustyline::validate::MatchingBracketValidator;
 use rustyline::{Completer, Helper, Hinter, Validator};
 use std::borrow::Cow;
 use std::path::PathBuf;
-use crate::commands::ask;
+use crate::commands::{ask, models};
+
+// 0. Completer: `--model <TAB>` ဆိုရင် Server Catalog ထဲက Model ID တွေ၊ ကျန်တာ Filename
+pub struct NeurustCompleter {
+    files: FilenameCompleter,
+    model_ids: Vec<String>,
+}
+
+impl Completer for NeurustCompleter {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let before = &line[..pos];
+        let word_start = before.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+        let prev_word = before[..word_start].split_whitespace().last();
+
+        if matches!(prev_word, Some("--model") | Some("-m")) {
+            let prefix = &before[word_start..];
+            let candidates = self
+                .model_ids
+                .iter()
+                .filter(|id| id.starts_with(prefix))
+                .map(|id| Pair { display: id.clone(), replacement: id.clone() })
+                .collect();
+            return Ok((word_start, candidates));
+        }
+
+        self.files.complete(line, pos, ctx)
+    }
+}
 
 // 1. Helper Struct
 #[derive(Helper, Completer, Hinter, Validator)]
 pub struct NeurustHelper {
     #[rustyline(Completer)]
-    completer: FilenameCompleter,
+    completer: NeurustCompleter,
     #[rustyline(Hinter)]
     hinter: HistoryHinter,
     #[rustyline(Validator)]
@@ -59,8 +93,14 @@ pub async fn start() -> anyhow::Result<()> {
         .completion_type(rustyline::CompletionType::List)
         .build();
 
+    // Model Catalog (Tab-completion အတွက်) - Server မရရင် Filename completion ပဲ သုံးမယ်
+    let model_ids = models::available_ids().await;
+
     let h = NeurustHelper {
-        completer: FilenameCompleter::new(),
+        completer: NeurustCompleter {
+            files: FilenameCompleter::new(),
+            model_ids,
+        },
         hinter: HistoryHinter {},
         validator: MatchingBracketValidator::new(),
         highlighter: MatchingBracketHighlighter::new(),
@@ -95,7 +135,9 @@ pub async fn start() -> anyhow::Result<()> {
                 }
 
                 println!(); 
-                if let Err(e) = ask::execute(input.to_string()).await {
+                let (prompt_text, model) = split_model_flag(input);
+                let options = ask::AskOptions { model, ..Default::default() };
+                if let Err(e) = ask::execute(prompt_text, options).await {
                     eprintln!("{} {}", "Error:".red().bold(), e);
                 }
                 println!("------------------------------------------------");
@@ -116,4 +158,21 @@ pub async fn start() -> anyhow::Result<()> {
     
     rl.save_history(&history_path)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// `--model <id>` (or `-m <id>`) ကို Prompt ထဲက ခွဲထုတ်မယ်
+fn split_model_flag(input: &str) -> (String, Option<String>) {
+    let mut model = None;
+    let mut words = Vec::new();
+    let mut iter = input.split_whitespace();
+
+    while let Some(word) = iter.next() {
+        if word == "--model" || word == "-m" {
+            model = iter.next().map(|m| m.to_string());
+        } else {
+            words.push(word);
+        }
+    }
+
+    (words.join(" "), model)
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project Rules (Agent က မဖြစ်မနေ လိုက်နာရမယ့် Conventions)
+/// ဖတ်မယ့်အစဉ်: `~/.neurust/rules.md` (Global) -> `.neurust/rules.md` (Project)
+/// Project Rules ကို နောက်မှာ ထားတာက Global နဲ့ ကွဲလွဲရင် Project က အနိုင်ရအောင်
+#[derive(Debug, Clone)]
+pub struct RulesSource {
+    pub label: &'static str,
+    pub path: PathBuf,
+    pub content: String,
+}
+
+pub fn project_path() -> PathBuf {
+    Path::new(".neurust").join("rules.md")
+}
+
+pub fn global_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".neurust").join("rules.md"))
+}
+
+/// ရှိတဲ့ Rules File တွေ (ဗလာ File တွေကို ကျော်မယ်)
+pub fn sources() -> Vec<RulesSource> {
+    let candidates = global_path()
+        .map(|p| ("Global", p))
+        .into_iter()
+        .chain(std::iter::once(("Project", project_path())));
+
+    candidates
+        .filter_map(|(label, path)| {
+            let content = fs::read_to_string(&path).ok()?;
+            if content.trim().is_empty() {
+                return None;
+            }
+            Some(RulesSource { label, path, content: content.trim().to_string() })
+        })
+        .collect()
+}
+
+/// Server ကို ပို့မယ့် Merged Rules (`rules` field)
+pub fn load_merged() -> Option<String> {
+    let sources = sources();
+    if sources.is_empty() {
+        return None;
+    }
+
+    let merged = sources
+        .iter()
+        .map(|s| format!("## {} Rules ({})\n{}", s.label, s.path.display(), s.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(merged)
+}
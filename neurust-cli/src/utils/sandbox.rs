@@ -0,0 +1,225 @@
+use crate::utils::config::SandboxConfig;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+// --- 🧱 WRITE SANDBOX ---
+// Model ပေးတဲ့ Path တွေကို Project Root (Canonical, Symlink Resolve ပြီး) အောက်မှာပဲ ရေးခွင့်ပေးမယ်။
+// Absolute Path / `../` / Project အပြင်ကို ညွှန်တဲ့ Symlink တွေကို ပိတ်မယ်။
+// `[sandbox].protected` Patterns (e.g. `.git`, `.env`, `**/target/deploy/*-keypair.json`) ကို ဘယ်တော့မှ မရေးဘူး။
+
+/// `[sandbox].protected` ကို Override လုပ်ထားလည်း အမြဲ Protected (`[sandbox]` / `[policy]` / `[auto]` ကိုယ်တိုင်)
+const ALWAYS_PROTECTED: &[&str] = &[".neurust/config.toml"];
+
+/// `protected_paths` က အထဲ မဝင်ဘဲ ကျော်မယ့် Directories (ကြီးလွန်းတယ်)
+const SKIP_DIRS: &[&str] = &["node_modules"];
+
+pub struct Sandbox {
+    root: PathBuf,
+    protected: Vec<(String, Regex)>,
+}
+
+impl Sandbox {
+    /// Root = လက်ရှိ Directory (Canonical)
+    pub fn new(config: &SandboxConfig) -> Result<Self> {
+        let root = env::current_dir()?.canonicalize()?;
+        let mut patterns = config.protected.clone();
+        for pattern in ALWAYS_PROTECTED {
+            if !patterns.iter().any(|p| p == pattern) {
+                patterns.push(pattern.to_string());
+            }
+        }
+        let protected = patterns
+            .into_iter()
+            .filter_map(|pattern| glob_to_regex(&pattern).map(|re| (pattern, re)))
+            .collect();
+        Ok(Self { root, protected })
+    }
+
+    /// ရေးလို့ရတဲ့ Path ဆိုရင် Project-relative Path ပြန်ပေးမယ် (Project အပြင် / Protected ဆိုရင် Error)
+    pub fn check_write(&self, path: &str) -> Result<String> {
+        let resolved = self.confine(path)?;
+        let relative = resolved
+            .strip_prefix(&self.root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+
+        if relative.is_empty() {
+            return Err(anyhow!("'{}' is the project root, not a file", path));
+        }
+        if let Some(pattern) = self.protected_by(&relative) {
+            return Err(anyhow!("'{}' is protected ([sandbox].protected: \"{}\")", relative, pattern));
+        }
+        Ok(relative)
+    }
+
+    /// Disk ပေါ်မှာ ရှိပြီးသား Protected Files / Directories (bwrap ထဲမှာ Read-only ပြန်ထပ်ဖို့ - `utils::isolation`)
+    /// `target/` ထဲမှာ `deploy/` (Keypairs) ကိုပဲ ဝင်ကြည့်မယ် (Build Artifacts တွေကို Walk မလုပ်ဘူး)
+    pub fn protected_paths(&self) -> Vec<PathBuf> {
+        let mut found = Vec::new();
+        self.collect_protected(&self.root, &mut found);
+        found
+    }
+
+    fn collect_protected(&self, dir: &Path, found: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else { return };
+        let in_target = dir.file_name().is_some_and(|name| name == "target");
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(relative) = path.strip_prefix(&self.root) else { continue };
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if self.protected_by(&relative).is_some() {
+                found.push(path);
+                continue;
+            }
+            let name = entry.file_name();
+            let descend = entry.file_type().is_ok_and(|t| t.is_dir())
+                && !SKIP_DIRS.iter().any(|skip| name == *skip)
+                && (!in_target || name == "deploy");
+            if descend {
+                self.collect_protected(&path, found);
+            }
+        }
+    }
+
+    fn protected_by(&self, relative: &str) -> Option<&str> {
+        self.protected.iter().find(|(_, re)| re.is_match(relative)).map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// ရှိပြီးသား အပိုင်းကို Canonicalize (Symlink Resolve) လုပ်ပြီး မရှိသေးတဲ့ အပိုင်းကို နောက်က ဆက်မယ်
+    fn confine(&self, path: &str) -> Result<PathBuf> {
+        if path.trim().is_empty() {
+            return Err(anyhow!("Empty path"));
+        }
+        let candidate = self.root.join(path);
+
+        // Symlink ကိုယ်တိုင်လည်း "ရှိတယ်" လို့ ယူမယ် (Broken Symlink ကို Canonicalize မရရင် ပိတ်မယ်)
+        let mut existing = candidate.as_path();
+        let mut rest: Vec<Component> = Vec::new();
+        while existing.symlink_metadata().is_err() {
+            let Some(parent) = existing.parent() else { break };
+            if let Some(last) = existing.components().next_back() {
+                rest.push(last);
+            }
+            existing = parent;
+        }
+
+        let mut resolved = existing
+            .canonicalize()
+            .map_err(|_| anyhow!("Cannot resolve '{}' (broken symlink?)", path))?;
+        for component in rest.into_iter().rev() {
+            match component {
+                Component::Normal(part) => resolved.push(part),
+                Component::CurDir => {}
+                _ => return Err(anyhow!("'{}' uses `..` through a missing directory", path)),
+            }
+        }
+
+        if !resolved.starts_with(&self.root) {
+            return Err(anyhow!("'{}' is outside the project ({})", path, self.root.display()));
+        }
+        Ok(resolved)
+    }
+}
+
+/// `*` = `/` မပါတဲ့ စာလုံးများ, `**/` = Directory အဆင့် ဘယ်နှစ်ဆင့်မဆို
+/// Pattern က Directory ကို ညွှန်ရင် အောက်က File အားလုံးပါ Protected ဖြစ်မယ်
+fn glob_to_regex(pattern: &str) -> Option<Regex> {
+    let pattern = pattern.trim().trim_start_matches("./").trim_end_matches('/');
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let mut re = String::from("^");
+    let mut rest = pattern;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("**/") {
+            re.push_str("(?:.*/)?");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("**") {
+            re.push_str(".*");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix('*') {
+            re.push_str("[^/]*");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix('?') {
+            re.push_str("[^/]");
+            rest = tail;
+        } else {
+            let ch = rest.chars().next()?;
+            re.push_str(&regex::escape(&ch.to_string()));
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    re.push_str("(?:/.*)?$");
+    Regex::new(&re).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        glob_to_regex(pattern).is_some_and(|re| re.is_match(path))
+    }
+
+    /// Temp Directory ထဲမှာ Root ထားတဲ့ Sandbox (Test တစ်ခုစီ Directory မတူ)
+    fn sandbox(name: &str, protected: &[&str]) -> Sandbox {
+        let root = env::temp_dir().join(format!("neurust-sandbox-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        let config = SandboxConfig { protected: protected.iter().map(|p| p.to_string()).collect(), ..SandboxConfig::default() };
+        let mut sandbox = Sandbox::new(&config).unwrap();
+        sandbox.root = root.canonicalize().unwrap();
+        sandbox
+    }
+
+    #[test]
+    fn globs_match_paths_and_directories() {
+        assert!(matches(".git", ".git/config"));
+        assert!(matches("./.env/", ".env"));
+        assert!(!matches(".env", ".envrc"));
+        assert!(matches("**/target/deploy/*-keypair.json", "target/deploy/vault-keypair.json"));
+        assert!(matches("**/target/deploy/*-keypair.json", "programs/a/target/deploy/a-keypair.json"));
+        assert!(!matches("**/target/deploy/*-keypair.json", "target/deploy/sub/a-keypair.json"));
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(!matches("src/*.rs", "src/bin/main.rs"));
+        assert!(matches("src/**", "src/bin/main.rs"));
+        assert!(matches("file?.txt", "file1.txt"));
+        assert!(matches("a+b.txt", "a+b.txt"));
+        assert!(glob_to_regex("  ").is_none());
+    }
+
+    #[test]
+    fn writes_stay_inside_the_project() {
+        let sandbox = sandbox("confine", &[".git"]);
+        assert_eq!(sandbox.check_write("./src/lib.rs").unwrap(), "src/lib.rs");
+        assert_eq!(sandbox.check_write("src/new/mod.rs").unwrap(), "src/new/mod.rs");
+        assert!(sandbox.check_write("../outside.rs").is_err());
+        assert!(sandbox.check_write("/etc/passwd").is_err());
+        assert!(sandbox.check_write("src/missing/../../..").is_err());
+        assert!(sandbox.check_write(".").is_err());
+        assert!(sandbox.check_write("").is_err());
+    }
+
+    #[test]
+    fn protected_paths_are_refused() {
+        let sandbox = sandbox("protected", &[".git"]);
+        assert!(sandbox.check_write(".git/config").unwrap_err().to_string().contains("protected"));
+        // `[sandbox].protected` ကို Override လုပ်ထားလည်း Config ကိုယ်တိုင်က Protected
+        assert!(sandbox.check_write(".neurust/config.toml").is_err());
+        assert!(sandbox.check_write(".neurust/memory.json").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_the_project_are_refused() {
+        let sandbox = sandbox("symlink", &[]);
+        std::os::unix::fs::symlink(env::temp_dir(), sandbox.root.join("escape")).unwrap();
+        assert!(sandbox.check_write("escape/file.txt").unwrap_err().to_string().contains("outside the project"));
+        std::os::unix::fs::symlink(sandbox.root.join("nowhere"), sandbox.root.join("broken")).unwrap();
+        assert!(sandbox.check_write("broken").is_err());
+    }
+}
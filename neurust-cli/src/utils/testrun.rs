@@ -0,0 +1,370 @@
+use crate::utils::cmd::{self, CommandOutcome};
+use crate::utils::context::{self, Stack};
+use regex::Regex;
+use std::fs;
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+// --- 🧪 TEST RUNNER ---
+// `cargo test` (libtest) / `anchor test` (mocha) Output ကို Test တစ်ခုချင်းစီရဲ့ ရလဒ်အဖြစ် ခွဲမယ်။
+// Fail ဖြစ်တဲ့ Test တွေအတွက် Panic Message, Assertion left/right, Captured Output ကို သိမ်းမယ်။
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    /// Test Binary (`unittests src/lib.rs`, `tests/api.rs`, `doc-tests my_crate`) — Binary မတူရင် နာမည်တူ Test တွေ ရှိနိုင်တယ်
+    pub binary: String,
+    pub name: String,
+    pub status: TestStatus,
+    /// `---- name stdout ----` Section (Panic Message ပါ)
+    pub output: String,
+    pub panic: Option<Panic>,
+}
+
+impl TestCase {
+    fn new(binary: &str, name: &str, status: TestStatus) -> Self {
+        TestCase { binary: binary.to_string(), name: name.to_string(), status, output: String::new(), panic: None }
+    }
+
+    /// Binary + Name (Re-run Result တွေကို တိုက်စစ်ဖို့)
+    pub fn same_test(&self, other: &TestCase) -> bool {
+        self.binary == other.binary && self.name == other.name
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Panic {
+    pub message: String,
+    /// `src/lib.rs:10:5` -> ("src/lib.rs", 10)
+    pub location: Option<(String, usize)>,
+    /// `assert_eq!` ရဲ့ (left, right)
+    pub assertion: Option<(String, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runner {
+    Cargo,
+    Anchor,
+}
+
+impl Runner {
+    /// Anchor Project ဆိုရင် `anchor test`, မဟုတ်ရင် `cargo test`
+    pub fn detect() -> Option<Self> {
+        let stacks = context::detect_stacks();
+        if stacks.contains(&Stack::Anchor) {
+            Some(Runner::Anchor)
+        } else if stacks.contains(&Stack::Rust) {
+            Some(Runner::Cargo)
+        } else {
+            None
+        }
+    }
+}
+
+pub struct TestRun {
+    pub outcome: CommandOutcome,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestRun {
+    pub fn count(&self, status: TestStatus) -> usize {
+        self.cases.iter().filter(|c| c.status == status).count()
+    }
+
+    pub fn failed(&self) -> Vec<&TestCase> {
+        self.cases.iter().filter(|c| c.status == TestStatus::Failed).collect()
+    }
+
+    /// Exit Code Fail ဖြစ်ပြီး Test တစ်ခုမှ မတွေ့ရင် Build / Setup Error
+    pub fn build_failed(&self) -> bool {
+        !self.outcome.success() && self.cases.is_empty()
+    }
+}
+
+/// Test တွေကို Run မယ် (`filters` = libtest Name Filters, Anchor မှာ မသုံးနိုင် -> Suite အပြည့် Run)
+pub async fn run(runner: Runner, filters: &[String], exact: bool) -> TestRun {
+    match runner {
+        Runner::Cargo => {
+            let mut args: Vec<&str> = vec!["test"];
+            if !filters.is_empty() {
+                args.push("--");
+                if exact {
+                    args.push("--exact");
+                }
+                args.extend(filters.iter().map(|f| f.as_str()));
+            }
+            let outcome = cmd::run_captured("cargo", &args, None).await;
+            let cases = parse_libtest(&outcome.stdout, &outcome.stderr);
+            TestRun { outcome, cases }
+        }
+        Runner::Anchor => {
+            let outcome = cmd::run_captured("anchor", &["test"], None).await;
+            let cases = parse_mocha(&outcome.stdout);
+            TestRun { outcome, cases }
+        }
+    }
+}
+
+/// libtest Output: `test a::b ... ok|FAILED|ignored` + `---- a::b stdout ----` Sections
+/// Binary နာမည်က stderr (`Running unittests src/lib.rs (...)` / `Doc-tests x`) မှာ ထွက်ပြီး
+/// stdout ရဲ့ `running N tests` Block တွေနဲ့ အစဉ်လိုက် တွဲမယ်
+pub fn parse_libtest(stdout: &str, stderr: &str) -> Vec<TestCase> {
+    static RESULT_LINE: OnceLock<Regex> = OnceLock::new();
+    static SECTION_HEADER: OnceLock<Regex> = OnceLock::new();
+    static BINARY_LINE: OnceLock<Regex> = OnceLock::new();
+    // Doc Test နာမည်မှာ Space ပါတယ်: `src/lib.rs - add (line 5)`
+    let result_line = RESULT_LINE.get_or_init(|| Regex::new(r"^test (.+?) \.\.\. (ok|FAILED|ignored)").unwrap());
+    let section_header = SECTION_HEADER.get_or_init(|| Regex::new(r"^---- (.+) stdout ----$").unwrap());
+    let binary_line = BINARY_LINE.get_or_init(|| Regex::new(r"^\s*(?:Running (.+?)(?: \(.*\))?|Doc-tests (\S+))\s*$").unwrap());
+
+    let binaries: Vec<String> = stderr
+        .lines()
+        .filter_map(|l| binary_line.captures(l))
+        .map(|caps| match caps.get(2) {
+            Some(krate) => format!("doc-tests {}", krate.as_str()),
+            None => caps[1].to_string(),
+        })
+        .collect();
+    let mut block = 0usize;
+    let mut binary = String::new();
+
+    let mut cases: Vec<TestCase> = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    let flush = |current: &mut Option<(String, Vec<&str>)>, cases: &mut Vec<TestCase>, binary: &str| {
+        if let Some((name, lines)) = current.take() {
+            let output = lines.join("\n").trim_end().to_string();
+            if let Some(case) = cases.iter_mut().rev().find(|c| c.name == name && c.binary == binary) {
+                case.panic = parse_panic(&output);
+                case.output = output;
+            }
+        }
+    };
+
+    for line in stdout.lines() {
+        if line.starts_with("running ") && (line.ends_with(" tests") || line.ends_with(" test")) {
+            flush(&mut current, &mut cases, &binary);
+            binary = binaries.get(block).cloned().unwrap_or_else(|| format!("binary #{}", block + 1));
+            block += 1;
+        } else if let Some(caps) = result_line.captures(line) {
+            let status = match &caps[2] {
+                "ok" => TestStatus::Passed,
+                "FAILED" => TestStatus::Failed,
+                _ => TestStatus::Ignored,
+            };
+            cases.push(TestCase::new(&binary, &caps[1], status));
+        } else if let Some(caps) = section_header.captures(line) {
+            flush(&mut current, &mut cases, &binary);
+            current = Some((caps[1].to_string(), Vec::new()));
+        } else if line == "failures:" || line.starts_with("test result:") {
+            flush(&mut current, &mut cases, &binary);
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    flush(&mut current, &mut cases, &binary);
+    cases
+}
+
+/// `thread 'x' panicked at src/lib.rs:10:5:\nmessage` (New) / `panicked at 'message', src/lib.rs:10:5` (Old)
+fn parse_panic(output: &str) -> Option<Panic> {
+    let new_style = Regex::new(r"panicked at ([^\s:]+):(\d+):\d+:\n((?s).*)").unwrap();
+    let old_style = Regex::new(r"panicked at '((?s).*?)', ([^\s:]+):(\d+):\d+").unwrap();
+
+    let (message, location) = if let Some(caps) = new_style.captures(output) {
+        let body = caps[3].lines().take_while(|l| !l.starts_with("note:") && !l.starts_with("stack backtrace:")).collect::<Vec<_>>().join("\n");
+        (body, Some((caps[1].to_string(), caps[2].parse().unwrap_or(0))))
+    } else if let Some(caps) = old_style.captures(output) {
+        (caps[1].to_string(), Some((caps[2].to_string(), caps[3].parse().unwrap_or(0))))
+    } else {
+        return None;
+    };
+
+    // `assert_eq!` -> "  left: 1\n right: 2" (New) / "left: `1`,\n right: `2`" (Old)
+    let side = |label: &str| {
+        message
+            .lines()
+            .find_map(|l| l.trim().strip_prefix(label))
+            .map(|v| v.trim().trim_end_matches(',').trim_matches('`').to_string())
+    };
+    let assertion = side("left:").zip(side("right:"));
+
+    Some(Panic { message: message.trim().to_string(), location, assertion })
+}
+
+/// mocha Output (`anchor test`): `✔ name (12ms)` / `1) name` + `N failing` Details
+pub fn parse_mocha(stdout: &str) -> Vec<TestCase> {
+    let passed = Regex::new(r"^\s+[✔✓] (.+?)(?: \(\d+ms\))?$").unwrap();
+    let failed = Regex::new(r"^\s+(\d+)\) (.+)$").unwrap();
+    let pending = Regex::new(r"^\s+- (.+)$").unwrap();
+
+    let mut cases = Vec::new();
+    let mut in_details = false;
+    let mut detail: Option<(usize, Vec<&str>)> = None;
+    let mut failed_names: Vec<String> = Vec::new();
+
+    for line in stdout.lines() {
+        if line.trim_end().ends_with("failing") && line.split_whitespace().count() == 2 {
+            in_details = true;
+            continue;
+        }
+        if !in_details {
+            if let Some(caps) = passed.captures(line) {
+                cases.push(TestCase::new("anchor test", &caps[1], TestStatus::Passed));
+            } else if let Some(caps) = failed.captures(line) {
+                failed_names.push(caps[2].to_string());
+                cases.push(TestCase::new("anchor test", &caps[2], TestStatus::Failed));
+            } else if let Some(caps) = pending.captures(line) {
+                cases.push(TestCase::new("anchor test", &caps[1], TestStatus::Ignored));
+            }
+            continue;
+        }
+
+        // Failure Details: "  1) suite" ... until next "  2) ..."
+        if let Some(caps) = failed.captures(line) {
+            attach_mocha_detail(&mut detail, &failed_names, &mut cases);
+            detail = Some((caps[1].parse().unwrap_or(0), vec![line.trim()]));
+        } else if let Some((_, lines)) = detail.as_mut() {
+            lines.push(line);
+        }
+    }
+    attach_mocha_detail(&mut detail, &failed_names, &mut cases);
+    cases
+}
+
+fn attach_mocha_detail(detail: &mut Option<(usize, Vec<&str>)>, failed_names: &[String], cases: &mut [TestCase]) {
+    let Some((index, lines)) = detail.take() else { return };
+    let Some(name) = failed_names.get(index.saturating_sub(1)) else { return };
+    if let Some(case) = cases.iter_mut().find(|c| &c.name == name && c.status == TestStatus::Failed) {
+        let output = lines.join("\n").trim_end().to_string();
+        let message = output.lines().find(|l| l.contains("Error")).unwrap_or("").trim().to_string();
+        case.panic = Some(Panic { message, location: None, assertion: None });
+        case.output = output;
+    }
+}
+
+/// Test Function ရဲ့ Source (`fn <last segment>` ကို `.rs` Files ထဲမှာ ရှာပြီး Brace အပြည့် ယူမယ်)
+pub fn find_function_source(name: &str) -> Option<(String, String)> {
+    let short = name.rsplit("::").next()?;
+    let signature = Regex::new(&format!(r"\bfn {}\s*[(<]", regex::escape(short))).ok()?;
+
+    for entry in WalkDir::new(".")
+        .into_iter()
+        .filter_entry(|e| !matches!(e.file_name().to_str(), Some("target" | ".git" | "node_modules")))
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+    {
+        let Ok(source) = fs::read_to_string(entry.path()) else { continue };
+        let Some(found) = signature.find(&source) else { continue };
+        let start = source[..found.start()].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        // Brace မပြည့်တဲ့ (Macro / Parse မရတဲ့) File ဆိုရင် နောက် File ဆက်ရှာမယ်
+        let Some(body) = extract_braced(&source[start..]) else { continue };
+        return Some((entry.path().display().to_string(), body.to_string()));
+    }
+    None
+}
+
+/// `fn ... { ... }` ကို Brace Balance နဲ့ ဖြတ်ယူမယ် (String/Comment ထဲက Brace ကို မခွဲခြားဘူး)
+fn extract_braced(source: &str) -> Option<&str> {
+    let open = source.find('{')?;
+    let mut depth = 0usize;
+    for (i, ch) in source[open..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[..open + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Source ထဲမှာ ခေါ်ထားတဲ့ Function နာမည်တွေ (Test က ဘာ Code ကို စမ်းနေလဲ ခန့်မှန်းဖို့)
+pub fn called_functions(source: &str) -> Vec<String> {
+    const SKIP: &[&str] = &["assert", "assert_eq", "assert_ne", "println", "format", "vec", "panic", "Some", "Ok", "Err", "unwrap", "expect", "new", "fn"];
+    let call = Regex::new(r"\b([a-z_][a-z0-9_]*)\s*\(").unwrap();
+
+    let mut names: Vec<String> = Vec::new();
+    for caps in call.captures_iter(source) {
+        let name = caps[1].to_string();
+        if !SKIP.contains(&name.as_str()) && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STDERR: &str = "   Compiling demo v0.1.0\n     Running unittests src/lib.rs (target/debug/deps/demo-1a2b)\n     Running tests/api.rs (target/debug/deps/api-3c4d)\n   Doc-tests demo\n";
+
+    #[test]
+    fn same_name_in_two_binaries_stays_separate() {
+        let stdout = "\nrunning 1 test\ntest it_works ... ok\n\ntest result: ok. 1 passed\n\n\
+                      running 1 test\ntest it_works ... FAILED\n\nfailures:\n\n---- it_works stdout ----\n\
+                      thread 'it_works' panicked at tests/api.rs:4:5:\nboom\n\nfailures:\n    it_works\n\ntest result: FAILED. 0 passed; 1 failed\n";
+        let cases = parse_libtest(stdout, STDERR);
+        assert_eq!(cases.len(), 2);
+        assert_eq!((cases[0].binary.as_str(), cases[0].status), ("unittests src/lib.rs", TestStatus::Passed));
+        assert_eq!((cases[1].binary.as_str(), cases[1].status), ("tests/api.rs", TestStatus::Failed));
+        assert!(cases[0].panic.is_none());
+        assert_eq!(cases[1].panic.as_ref().unwrap().location, Some(("tests/api.rs".to_string(), 4)));
+        assert!(!cases[0].same_test(&cases[1]));
+    }
+
+    #[test]
+    fn doc_tests_keep_their_full_name() {
+        let stdout = "\nrunning 0 tests\n\nrunning 0 tests\n\nrunning 1 test\ntest src/lib.rs - add (line 5) ... FAILED\n\n\
+                      failures:\n\n---- src/lib.rs - add (line 5) stdout ----\nTest executable failed.\n\ntest result: FAILED\n";
+        let cases = parse_libtest(stdout, STDERR);
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].name, "src/lib.rs - add (line 5)");
+        assert_eq!(cases[0].binary, "doc-tests demo");
+        assert_eq!(cases[0].output, "Test executable failed.");
+    }
+
+    #[test]
+    fn malformed_output_does_not_panic() {
+        let stdout = "running 2 tests\ntest half_line ...\n---- orphan stdout ----\nthread 'x' panicked at\ntest ok_one ... ok\n";
+        let cases = parse_libtest(stdout, "");
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].name, "ok_one");
+        assert_eq!(cases[0].binary, "binary #1");
+        assert!(parse_panic("thread 'x' panicked at").is_none());
+    }
+
+    #[test]
+    fn assertion_sides_are_extracted() {
+        let panic = parse_panic("thread 't' panicked at src/lib.rs:10:5:\nassertion `left == right` failed\n  left: 1\n right: 2\nnote: run with `RUST_BACKTRACE=1`").unwrap();
+        assert_eq!(panic.assertion, Some(("1".to_string(), "2".to_string())));
+        assert!(!panic.message.contains("note:"));
+    }
+
+    #[test]
+    fn mocha_failures_get_details() {
+        let stdout = "  vault\n    ✔ initializes (120ms)\n    1) deposits\n    - withdraws\n\n  1 passing\n  1 failing\n\n  1) vault\n       deposits:\n     Error: overflow\n";
+        let cases = parse_mocha(stdout);
+        assert_eq!(cases.len(), 3);
+        assert_eq!(cases[1].status, TestStatus::Failed);
+        assert_eq!(cases[1].panic.as_ref().unwrap().message, "Error: overflow");
+        assert_eq!(cases[2].status, TestStatus::Ignored);
+    }
+
+    #[test]
+    fn unbalanced_braces_yield_none() {
+        assert_eq!(extract_braced("fn a() { if x { }"), None);
+        assert_eq!(extract_braced("fn a() { b() }\nfn c() {}"), Some("fn a() { b() }"));
+    }
+}
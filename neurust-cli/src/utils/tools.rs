@@ -0,0 +1,110 @@
+use crate::utils::fs as nfs;
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// --- 🛠️ CLIENT-SIDE AGENT TOOLS ---
+// Server Agent Loop က `tool_call` ပို့လာရင် User ရဲ့ Project ထဲမှာ Run ပြီး Text ပြန်ပို့မယ်။
+// Read-only Tools သာ (Write တွေက Plan ထဲမှာပဲ ရှိပြီး Executor က Confirm မေးမယ်)
+
+const MAX_FILE_BYTES: u64 = 200_000;
+const MAX_GREP_MATCHES: usize = 200;
+const IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules", "dist", "build", ".next", "test-ledger"];
+
+/// Tool တစ်ခုကို Run မယ် (Error ဆိုရင် Server က Model ဆီ "ERROR: ..." အဖြစ် ပြန်ပြမယ်)
+pub fn execute(name: &str, args: &Value) -> Result<String> {
+    match name {
+        "read_file" => read_file(args["path"].as_str().unwrap_or("")),
+        "list_dir" => list_dir(args["path"].as_str().unwrap_or(".")),
+        "grep" => grep(args["pattern"].as_str().unwrap_or(""), args["path"].as_str().unwrap_or(".")),
+        other => Err(anyhow!("Unsupported client tool: {}", other)),
+    }
+}
+
+/// Project Root (cwd) အပြင်ဘက်ကို မထွက်ရအောင် စစ်မယ်
+fn resolve_in_project(path: &str) -> Result<PathBuf> {
+    let root = env::current_dir()?.canonicalize()?;
+    let candidate = root.join(path);
+    let resolved = candidate
+        .canonicalize()
+        .map_err(|_| anyhow!("Path not found: {}", path))?;
+
+    if !resolved.starts_with(&root) {
+        return Err(anyhow!("Access denied: '{}' is outside the project", path));
+    }
+    Ok(resolved)
+}
+
+fn read_file(path: &str) -> Result<String> {
+    // Direct path မရှိရင် File name နဲ့ Project ထဲ လိုက်ရှာမယ် (Legacy `read_file` action နဲ့ အတူတူ)
+    let resolved = resolve_in_project(path).or_else(|e| {
+        nfs::find_file_recursive(path)
+            .and_then(|found| resolve_in_project(&found.to_string_lossy()).ok())
+            .ok_or(e)
+    })?;
+
+    let size = fs::metadata(&resolved)?.len();
+    if size > MAX_FILE_BYTES {
+        return Err(anyhow!("File too large ({} bytes). Use grep to find the relevant part.", size));
+    }
+    Ok(fs::read_to_string(&resolved)?)
+}
+
+fn list_dir(path: &str) -> Result<String> {
+    let resolved = resolve_in_project(path)?;
+    let mut entries: Vec<String> = fs::read_dir(&resolved)?
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() { format!("{}/", name) } else { name }
+        })
+        .collect();
+    entries.sort();
+
+    if entries.is_empty() {
+        return Ok("(empty directory)".to_string());
+    }
+    Ok(entries.join("\n"))
+}
+
+fn grep(pattern: &str, path: &str) -> Result<String> {
+    let regex = Regex::new(pattern).map_err(|e| anyhow!("Invalid regex: {}", e))?;
+    let root = env::current_dir()?.canonicalize()?;
+    let start = resolve_in_project(path)?;
+
+    let mut matches = Vec::new();
+    let walker = WalkDir::new(&start)
+        .into_iter()
+        .filter_entry(|e| !IGNORED_DIRS.contains(&e.file_name().to_string_lossy().as_ref()));
+
+    for entry in walker.flatten() {
+        if !entry.file_type().is_file() || entry.metadata().map(|m| m.len() > MAX_FILE_BYTES).unwrap_or(true) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else { continue }; // Binary files
+
+        let display = relative_to(entry.path(), &root);
+        for (index, line) in content.lines().enumerate() {
+            if regex.is_match(line) {
+                matches.push(format!("{}:{}: {}", display, index + 1, line.trim()));
+                if matches.len() >= MAX_GREP_MATCHES {
+                    matches.push(format!("[... stopped after {} matches ...]", MAX_GREP_MATCHES));
+                    return Ok(matches.join("\n"));
+                }
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        return Ok("No matches.".to_string());
+    }
+    Ok(matches.join("\n"))
+}
+
+fn relative_to(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).display().to_string()
+}
@@ -0,0 +1,164 @@
+use crate::utils::cmd::{self, CommandOutcome};
+use crate::utils::config::VerifyConfig;
+use crate::utils::context::{self, Stack};
+use crate::utils::diagnostics;
+use crate::utils::events::{self, CommandSource, Event};
+use colored::*;
+use regex::Regex;
+use std::fs;
+
+// --- 🧪 VERIFICATION PIPELINE ---
+// Model က `cargo check` ထည့်မထည့် မမှီခိုဘဲ CLI ကိုယ်တိုင် Stack ကြည့်ပြီး Verify လုပ်မယ်။
+// `.neurust/config.toml` ရဲ့ `[verify].steps` ရှိရင် အဲ့ဒါကို သုံးမယ်။
+
+/// Verification Step တစ်ခု (e.g. "cargo check")
+#[derive(Debug, Clone)]
+pub struct VerifyStep {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl VerifyStep {
+    fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace().map(|p| p.to_string());
+        let program = parts.next()?;
+        Some(Self { program, args: parts.collect() })
+    }
+
+    pub fn command(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(|a| a.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Step တစ်ခုရဲ့ ရလဒ် (Output ထဲက Error/Warning အရေအတွက်ပါ)
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    pub outcome: CommandOutcome,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Verification {
+    pub results: Vec<StepResult>,
+}
+
+impl Verification {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.outcome.success())
+    }
+
+    /// e.g. "cargo check: 2 error(s), 1 warning(s); npm run lint: ok"
+    pub fn summary(&self) -> String {
+        self.results
+            .iter()
+            .map(|r| {
+                if r.outcome.success() && r.warnings == 0 {
+                    format!("{}: ok", r.outcome.command)
+                } else {
+                    format!("{}: {} error(s), {} warning(s)", r.outcome.command, r.errors, r.warnings)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Config ထဲမှာ Step မထည့်ထားရင် Stack ကနေ ရွေးမယ်
+/// - Anchor -> `anchor build` (Cargo Workspace ကိုပါ Build လုပ်ပြီးသား)
+/// - Rust   -> `cargo check`
+/// - Node   -> `npm run lint` (မရှိရင် `npm run build`)
+pub fn resolve_steps(config: &VerifyConfig) -> Vec<VerifyStep> {
+    if let Some(lines) = &config.steps {
+        return lines.iter().filter_map(|l| VerifyStep::parse(l)).collect();
+    }
+
+    let stacks = context::detect_stacks();
+    let mut steps = Vec::new();
+
+    if stacks.contains(&Stack::Anchor) {
+        steps.extend(VerifyStep::parse("anchor build"));
+    } else if stacks.contains(&Stack::Rust) {
+        steps.extend(VerifyStep::parse("cargo check"));
+    }
+
+    if stacks.contains(&Stack::Node) {
+        let scripts = fs::read_to_string("package.json")
+            .ok()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+            .map(|pkg| pkg["scripts"].clone())
+            .unwrap_or_default();
+        if scripts["lint"].is_string() {
+            steps.extend(VerifyStep::parse("npm run lint"));
+        } else if scripts["build"].is_string() {
+            steps.extend(VerifyStep::parse("npm run build"));
+        }
+    }
+
+    steps
+}
+
+/// Step တွေကို အစဉ်လိုက် Run ပြီး Console မှာ Summary ပြမယ် (Fail ဖြစ်လည်း ကျန်တာ ဆက် Run မယ်)
+pub async fn run(steps: &[VerifyStep]) -> Verification {
+    let mut verification = Verification::default();
+    if steps.is_empty() {
+        return verification;
+    }
+
+    println!("{} Verifying ({} step(s))...", "🧪".cyan(), steps.len());
+    for step in steps {
+        let args: Vec<&str> = step.args.iter().map(|a| a.as_str()).collect();
+
+        // Cargo ဆိုရင် JSON Diagnostics နဲ့ Run မယ် (Observation ထဲမှာ File အလိုက် Structured Report ပါမယ်)
+        let (outcome, errors, warnings) = if diagnostics::is_cargo_diagnostic(&step.program, &args) {
+            let run = diagnostics::run_cargo(&args, CommandSource::Check).await;
+            let errors = if run.report.errors() == 0 && !run.outcome.success() { 1 } else { run.report.errors() };
+            (run.outcome, errors, run.report.warnings())
+        } else {
+            let outcome = cmd::run_captured(&step.program, &args, None).await;
+            let (errors, warnings) = count_diagnostics(&outcome);
+            (outcome, errors, warnings)
+        };
+
+        let status = if outcome.success() { "✅".green() } else { "❌".red() };
+        println!(
+            "   {} {} ({} error(s), {} warning(s), {:.1}s)",
+            status,
+            step.command(),
+            errors,
+            warnings,
+            outcome.duration.as_secs_f64()
+        );
+
+        verification.results.push(StepResult { outcome, errors, warnings });
+    }
+    events::emit(Event::VerificationFinished { passed: verification.passed(), summary: verification.summary() });
+    verification
+}
+
+/// Cargo (`error[E0308]:`, `warning:`) နဲ့ ESLint (`✖ 3 problems (2 errors, 1 warning)`) Output ကို ရေတွက်မယ်
+fn count_diagnostics(outcome: &CommandOutcome) -> (usize, usize) {
+    let text = format!("{}\n{}", outcome.stdout, outcome.stderr);
+
+    let eslint = Regex::new(r"\((\d+) errors?, (\d+) warnings?\)").unwrap();
+    if let Some(caps) = eslint.captures(&text) {
+        return (caps[1].parse().unwrap_or(0), caps[2].parse().unwrap_or(0));
+    }
+
+    let errors = text
+        .lines()
+        .filter(|l| l.starts_with("error:") || l.starts_with("error["))
+        .filter(|l| !l.starts_with("error: could not compile") && !l.starts_with("error: aborting"))
+        .count();
+    let warnings = text
+        .lines()
+        .filter(|l| l.starts_with("warning:") && !l.contains("generated") && !l.contains("warnings emitted"))
+        .count();
+
+    // Exit Code က Fail ဖြစ်ပြီး Pattern မတွေ့ရင် အနည်းဆုံး Error ၁ ခု
+    let errors = if errors == 0 && !outcome.success() { 1 } else { errors };
+    (errors, warnings)
+}
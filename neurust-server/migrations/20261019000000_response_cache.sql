@@ -0,0 +1,14 @@
+-- ⚡ Response Cache (RESPONSE_CACHE_BACKEND=postgres)
+CREATE TABLE IF NOT EXISTS response_cache (
+    cache_key TEXT PRIMARY KEY,          -- "<scope>:<sha256>"
+    scope TEXT NOT NULL,                 -- "team:<uuid>" | "user:<uuid>"
+    response TEXT NOT NULL,              -- Serialized CachedResponse (JSON)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_response_cache_expires ON response_cache(expires_at);
+CREATE INDEX IF NOT EXISTS idx_response_cache_scope ON response_cache(scope);
+
+-- Cache Hit တွေကို Usage Log မှာ ခွဲမြင်ရအောင်
+ALTER TABLE usage_logs ADD COLUMN IF NOT EXISTS cached BOOLEAN NOT NULL DEFAULT FALSE;
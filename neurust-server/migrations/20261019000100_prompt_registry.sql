@@ -0,0 +1,10 @@
+-- 📝 Prompt Registry: Admin Rollback Pins (Pin မရှိရင် အသစ်ဆုံး Version ကို သုံးမယ်)
+CREATE TABLE IF NOT EXISTS prompt_pins (
+    name TEXT PRIMARY KEY,               -- "architect" | "auditor"
+    version TEXT NOT NULL,               -- "v1"
+    pinned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    pinned_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Plan/Audit တစ်ခုချင်းစီကို ဘယ် Prompt က ထုတ်ခဲ့လဲ ("architect@v1")
+ALTER TABLE usage_logs ADD COLUMN IF NOT EXISTS prompt_version TEXT;
@@ -0,0 +1,51 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use serde_json::{json, Value};
+use crate::models::{User, UserRole};
+use crate::AppState;
+
+/// Admin / SuperAdmin သာ ခွင့်ပြုမယ်
+fn require_admin(user: &User) -> Result<(), (StatusCode, Json<Value>)> {
+    if matches!(user.role, UserRole::SuperAdmin | UserRole::Admin) {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, Json(json!({ "status": "error", "error": "Admin access required" }))))
+    }
+}
+
+/// GET /api/admin/prompts
+/// Prompt တစ်ခုချင်းစီရဲ့ Active Version, Pin ထားမထား, ရှိသမျှ Version တွေ
+pub async fn list_prompts(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> impl IntoResponse {
+    if let Err(rejection) = require_admin(&user) {
+        return rejection.into_response();
+    }
+
+    Json(json!({ "status": "success", "prompts": state.prompt_registry.status() })).into_response()
+}
+
+/// POST /api/admin/prompts/rollback
+/// Body: { "name": "architect", "version": "v1" | "latest" | null }
+/// `version` မပါရင် လက်ရှိ Active ရဲ့ အရင် Version ကို ပြန်သွားမယ်
+pub async fn rollback_prompt(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<Value>,
+) -> impl IntoResponse {
+    if let Err(rejection) = require_admin(&user) {
+        return rejection.into_response();
+    }
+
+    let Some(name) = payload["name"].as_str() else {
+        return (StatusCode::BAD_REQUEST, Json(json!({ "status": "error", "error": "Missing 'name'" }))).into_response();
+    };
+    let version = payload["version"].as_str();
+
+    println!("⏪ Prompt rollback requested by {}: {} -> {}", user.wallet_address, name, version.unwrap_or("previous"));
+
+    match state.prompt_registry.rollback(name, version, user.id).await {
+        Ok(status) => Json(json!({ "status": "success", "prompt": status })).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(json!({ "status": "error", "error": e }))).into_response(),
+    }
+}
@@ -1,10 +1,15 @@
 use crate::AppState;
 use crate::models::User; // 🔥 Import User Model
+use crate::services::ai::{self, AiError, AiService, PlanInput};
+use crate::services::billing::BillingService;
+use crate::services::model_catalog::{self, ModelSelectionError};
+use crate::services::response_cache::{CacheKey, CachedResponse};
+use crate::prompts::{self, PromptVars};
 use axum::{
     extract::State, 
     Json, 
-    http::StatusCode, 
-    response::IntoResponse, 
+    http::{header::{CACHE_CONTROL, RETRY_AFTER}, HeaderMap, HeaderValue, StatusCode}, 
+    response::{IntoResponse, Response}, 
     Extension // 🔥 Middleware Data ယူရန်
 };
 use serde_json::{json, Value};
@@ -13,12 +18,19 @@ use serde_json::{json, Value};
 pub async fn handle_plan_request(
     State(state): State<AppState>, 
     Extension(user): Extension<User>, // 🔥 Gatekeeper ဆီက User အစစ်ကို လက်ခံရယူမယ်
+    headers: HeaderMap,
     Json(payload): Json<Value>,    
 ) -> impl IntoResponse {
-    let prompt = payload["prompt"].as_str().unwrap_or("");
-    let context = payload["context"].as_str().map(|s| s.to_string());
+    let mut input = PlanInput::from_payload(&payload);
+    input.user_tier = Some(format!("{:?}", user.role));
     
-    println!("🤖 User Prompt: {} (Wallet: {})", prompt, user.wallet_address);
+    println!("🤖 User Prompt: {} (Wallet: {})", input.prompt, user.wallet_address);
+
+    // 0. 🎛️ MODEL SELECTION: Client ရွေးလာတဲ့ Model ကို Role Allowlist နဲ့ စစ်မယ်
+    let requested_model = match resolve_requested_model(&user, &payload) {
+        Ok(model) => model,
+        Err(rejection) => return rejection.into_response(),
+    };
 
     // 1. 💰 PRE-CHECK: Credit လုံလောက်မှု ရှိမရှိ စစ်မယ်
     match state.billing_service.has_sufficient_credits(user.id).await {
@@ -38,36 +50,128 @@ pub async fn handle_plan_request(
         }
     }
 
-    // 2. 🧠 EXECUTE: AI Service ကို ခေါ်မယ်
+    // 2. ⚡ CACHE: Input အတူတူ မေးဖူးရင် LLM ကို ထပ်မခေါ်ဘူး
+    let cache_key = CacheKey::new(
+        &user,
+        "plan",
+        &AiService::route_plan_model(&input, requested_model),
+        &state.prompt_registry.active_version(prompts::ARCHITECT),
+        &[
+            Some(&input.prompt),
+            input.context.as_deref(),
+            input.history.as_deref(),
+            input.stack.as_deref(),
+            input.project_type.as_deref(),
+            input.user_tier.as_deref(),
+            input.rules.as_deref(),
+        ],
+    );
+    if let Some(hit) = lookup_cache(&state, &headers, &cache_key).await {
+        if let Err(e) = state.billing_service.deduct_cached_credits(
+            user.id, &hit.model, "generate_plan", hit.usage, hit.prompt_version.as_deref(), state.response_cache.hit_billing_rate()
+        ).await {
+            println!("❌ Failed to deduct credits: {}", e);
+        }
+
+        let mut plan = hit.content;
+        if let Some(obj) = plan.as_object_mut() {
+            obj.insert("context_report".to_string(), hit.context_report);
+            obj.insert("cached".to_string(), json!(true));
+            obj.insert("prompt_version".to_string(), json!(hit.prompt_version));
+        }
+        return Json(plan).into_response();
+    }
+
+    // 3. 🧠 EXECUTE: AI Service ကို ခေါ်မယ်
     // 🔥 Note: Now capturing 'used_model' (3rd return value)
-    match state.ai_service.generate_project_plan(prompt, context).await {
-        Ok((plan, usage, used_model)) => {
+    match state.ai_service.generate_project_plan(&input, requested_model, &user.role).await {
+        Ok(output) => {
+            let mut plan = output.content;
+            let used_model = output.model;
+            let usage = output.usage;
+
+            state.response_cache.put(&cache_key, CachedResponse {
+                content: plan.clone(),
+                model: used_model.clone(),
+                usage: usage.clone(),
+                context_report: json!(output.context_report),
+                prompt_version: Some(output.prompt_version.clone()),
+            }).await;
             println!("✅ Plan generated using [{}]! Usage: {} tokens", used_model, usage.total_tokens);
 
-            // 3. 💸 DEDUCT: ပိုက်ဆံဖြတ်မယ်
+            // 4. 💸 DEDUCT: ပိုက်ဆံဖြတ်မယ်
             // 🔥 Pass the REAL 'used_model' to billing service so it calculates price accurately
             if let Err(e) = state.billing_service.deduct_credits(
                 user.id, 
                 &used_model, // <--- Dynamic Model Name (Not hardcoded)
                 "generate_plan",
-                usage
+                usage,
+                Some(&output.prompt_version)
             ).await {
                 println!("❌ Failed to deduct credits: {}", e);
                 // Note: Plan is generated, returning it but logging billing failure.
             }
 
+            // 5. ✂️ Client ကို ဘာတွေ ဖြတ်လိုက်လဲ ပြန်ပြောမယ်
+            if let Some(obj) = plan.as_object_mut() {
+                obj.insert("context_report".to_string(), json!(output.context_report));
+                obj.insert("cached".to_string(), json!(false));
+                obj.insert("prompt_version".to_string(), json!(output.prompt_version));
+            }
+
             Json(plan).into_response()
         }
         Err(e) => {
-            eprintln!("❌ AI Error: {}", e);
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "status": "error",
-                    "error": format!("Neurust Brain Failure: {}", e)
-                }))
-            ).into_response()
+            eprintln!("❌ AI Error [{}]: {}", e.code(), e);
+            ai_error_response(&e)
+        }
+    }
+}
+
+/// Plan Response အတိုဆုံး ခန့်မှန်းချက် (Chat reply / small JSON plan)
+const ESTIMATED_MIN_OUTPUT_TOKENS: usize = 800;
+
+// --- ESTIMATE HANDLER (Preflight Cost) ---
+// `/plan` နဲ့ Payload အတူတူ။ LLM မခေါ်ဘူး၊ Credit မဖြတ်ဘူး။
+pub async fn handle_estimate_request(
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+    Json(payload): Json<Value>,
+) -> impl IntoResponse {
+    let mut input = PlanInput::from_payload(&payload);
+    input.user_tier = Some(format!("{:?}", user.role));
+
+    let requested_model = match resolve_requested_model(&user, &payload) {
+        Ok(model) => model,
+        Err(rejection) => return rejection.into_response(),
+    };
+
+    match state.ai_service.prepare_plan(&input, requested_model).await {
+        Ok(prepared) => {
+            let report = &prepared.budgeted.report;
+            let output_max = report.max_output_tokens;
+            let output_min = ESTIMATED_MIN_OUTPUT_TOKENS.min(output_max);
+
+            let estimate = BillingService::estimate(
+                &user.role,
+                &prepared.model,
+                report.input_tokens,
+                (output_min, output_max),
+            );
+
+            println!("🔮 Estimate for {}: [{}] {}-{} credits", user.wallet_address, prepared.model, estimate.credits_min, estimate.credits_max);
+
+            Json(json!({
+                "status": "success",
+                "model": prepared.model,
+                "routed": requested_model.is_none(),
+                "estimate": estimate,
+                "balance": user.credits,
+                "context_report": report,
+                "prompt_version": prepared.prompt_version,
+            })).into_response()
         }
+        Err(e) => ai_error_response(&e),
     }
 }
 
@@ -75,12 +179,18 @@ pub async fn handle_plan_request(
 pub async fn handle_audit_request(
     State(state): State<AppState>,
     Extension(user): Extension<User>, 
+    headers: HeaderMap,
     Json(payload): Json<Value>,
 ) -> impl IntoResponse {
     let code = payload["code"].as_str().unwrap_or("");
     
     println!("🕵️ Security Audit Request from {} (Size: {} chars)", user.wallet_address, code.len());
 
+    let requested_model = match resolve_requested_model(&user, &payload) {
+        Ok(model) => model,
+        Err(rejection) => return rejection.into_response(),
+    };
+
     // 1. Credit Check
     match state.billing_service.has_sufficient_credits(user.id).await {
         Ok(true) => {},
@@ -88,29 +198,139 @@ pub async fn handle_audit_request(
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": "Billing System Error" }))).into_response(),
     }
 
-    // 2. Execute Audit
+    let vars = PromptVars::detect(
+        payload["stack"].as_str(),
+        payload["project_type"].as_str(),
+        None,
+        Some(format!("{:?}", user.role)),
+    );
+    let rules = ai::rules_from_payload(&payload);
+
+    // 2. ⚡ Cache (CI re-audits unchanged files)
+    let cache_key = CacheKey::new(
+        &user,
+        "audit",
+        &AiService::route_audit_model(requested_model),
+        &state.prompt_registry.active_version(prompts::AUDITOR),
+        &[
+            Some(code),
+            vars.stack.as_deref(),
+            vars.project_type.as_deref(),
+            vars.user_tier.as_deref(),
+            rules.as_deref(),
+        ],
+    );
+    if let Some(hit) = lookup_cache(&state, &headers, &cache_key).await {
+        let _ = state.billing_service.deduct_cached_credits(
+            user.id, &hit.model, "audit_code", hit.usage, hit.prompt_version.as_deref(), state.response_cache.hit_billing_rate()
+        ).await;
+
+        return Json(json!({
+            "report": hit.content,
+            "context_report": hit.context_report,
+            "cached": true,
+            "prompt_version": hit.prompt_version,
+        })).into_response();
+    }
+
+    // 3. Execute Audit
     // 🔥 Capturing 'used_model' here too
-    match state.ai_service.audit_code(code).await {
-        Ok((report, usage, used_model)) => { 
-            // 3. Deduct Credits with REAL Model
+    match state.ai_service.audit_code(code, requested_model, &user.role, &vars, rules.as_deref()).await {
+        Ok(output) => { 
+            state.response_cache.put(&cache_key, CachedResponse {
+                content: json!(output.content),
+                model: output.model.clone(),
+                usage: output.usage.clone(),
+                context_report: json!(output.context_report),
+                prompt_version: Some(output.prompt_version.clone()),
+            }).await;
+
+            // 4. Deduct Credits with REAL Model
             let _ = state.billing_service.deduct_credits(
                 user.id, 
-                &used_model, // <--- Dynamic Model Name
+                &output.model, // <--- Dynamic Model Name
                 "audit_code", 
-                usage
+                output.usage,
+                Some(&output.prompt_version)
             ).await;
 
-            Json(json!({ "report": report })).into_response()
+            Json(json!({
+                "report": output.content,
+                "context_report": output.context_report,
+                "cached": false,
+                "prompt_version": output.prompt_version,
+            })).into_response()
         }, 
         Err(e) => {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "report": format!("Error generating audit: {}", e) }))
-            ).into_response()
+            eprintln!("❌ Audit Error [{}]: {}", e.code(), e);
+            ai_error_response(&e)
         },
     }
 }
 
+/// `Cache-Control: no-cache` ပါရင် Cache ကို ကျော်မယ် (Fresh အဖြေကိုတော့ ပြန်သိမ်းမယ်)
+async fn lookup_cache(state: &AppState, headers: &HeaderMap, key: &CacheKey) -> Option<CachedResponse> {
+    let bypass = headers
+        .get(CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').any(|directive| directive.trim().eq_ignore_ascii_case("no-cache")))
+        .unwrap_or(false);
+
+    if bypass {
+        println!("⚡ Cache bypass requested (Cache-Control: no-cache)");
+        return None;
+    }
+
+    let hit = state.response_cache.get(key).await;
+    if hit.is_some() {
+        println!("⚡ Cache HIT [{}]", &key.digest[..12]);
+    }
+    hit
+}
+
+/// Typed AiError -> HTTP Response (429/413/422/502/503/504 + machine-readable `code`)
+fn ai_error_response(e: &AiError) -> Response {
+    let mut response = (
+        e.status_code(),
+        Json(json!({
+            "status": "error",
+            "code": e.code(),
+            "error": format!("Neurust Brain Failure: {}", e)
+        }))
+    ).into_response();
+
+    if let Some(wait) = e.retry_after() {
+        response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(wait.as_secs()));
+    }
+    response
+}
+
+/// Payload ထဲက optional `model` ကို Pricing Table + Role Allowlist နဲ့ စစ်မယ်
+/// None = Server ရဲ့ Smart Routing ကို သုံးမယ်
+pub(crate) fn resolve_requested_model(
+    user: &User,
+    payload: &Value,
+) -> Result<Option<&'static str>, (StatusCode, Json<Value>)> {
+    let model_id = match payload["model"].as_str() {
+        Some(id) if !id.trim().is_empty() => id.trim(),
+        _ => return Ok(None),
+    };
+
+    match model_catalog::validate_selection(&user.role, model_id) {
+        Ok(model) => {
+            println!("🎛️ Client selected model: {}", model.id);
+            Ok(Some(model.id))
+        }
+        Err(e) => {
+            let status = match e {
+                ModelSelectionError::Unknown(_) => StatusCode::BAD_REQUEST,
+                ModelSelectionError::NotAllowed { .. } => StatusCode::FORBIDDEN,
+            };
+            Err((status, Json(json!({ "status": "error", "error": e.to_string() }))))
+        }
+    }
+}
+
 // --- BROWSER HANDLER ---
 pub async fn handle_browse_request(
     State(state): State<AppState>,
@@ -0,0 +1,29 @@
+use axum::{Json, response::IntoResponse, Extension};
+use serde_json::json;
+use crate::models::User;
+use crate::services::model_catalog::{self, MODEL_CATALOG};
+
+/// GET /api/models
+/// Catalog ထဲက Model အားလုံးကို စျေးနှုန်းနဲ့တကွ ပြမယ်၊ User Role အလိုက် ရွေးခွင့်ရှိမရှိ `available` နဲ့ ပြမယ်။
+pub async fn list_models(Extension(user): Extension<User>) -> impl IntoResponse {
+    let models: Vec<_> = MODEL_CATALOG
+        .iter()
+        .map(|model| {
+            json!({
+                "id": model.id,
+                "label": model.label,
+                "tier": model.tier,
+                "input_price_per_m": model.input_price_per_m,
+                "output_price_per_m": model.output_price_per_m,
+                "context_window": model.context_window,
+                "available": model_catalog::is_allowed(&user.role, model),
+            })
+        })
+        .collect();
+
+    Json(json!({
+        "role": user.role,
+        "max_tier": model_catalog::max_tier_for(&user.role),
+        "models": models
+    }))
+}
@@ -4,12 +4,24 @@ pub mod health; // Health Check (Placeholder needed)
 pub mod project;
 pub mod user;    // User Logic
 pub mod payment; // Payment Logic
+pub mod catalog; // Model Catalog (Pricing + Allowlists)
+pub mod admin;   // Admin Tools (Prompt Registry)
+pub mod session; // Server-side Agent Loop (WebSocket)
 
 // Re-export Auth Handlers (Matches main.rs imports)
 pub use auth::{initiate_device_flow, poll_device_flow, verify_device_login};
 
 // Re-export Agent Handlers
-pub use agent::{handle_plan_request, handle_audit_request, handle_browse_request};
+pub use agent::{handle_plan_request, handle_audit_request, handle_browse_request, handle_estimate_request};
+
+// Re-export Agent Session
+pub use session::agent_session;
+
+// Re-export Model Catalog
+pub use catalog::list_models;
+
+// Re-export Admin Handlers
+pub use admin::{list_prompts, rollback_prompt};
 
 // Re-export Health Check
 pub use health::health_check;
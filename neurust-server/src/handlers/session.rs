@@ -0,0 +1,88 @@
+use crate::AppState;
+use crate::models::User;
+use crate::services::agent_loop::{AgentLoop, LoopError};
+use crate::services::ai::PlanInput;
+use super::agent::resolve_requested_model;
+use axum::{
+    extract::{ws::{Message, WebSocket, WebSocketUpgrade}, State},
+    response::IntoResponse,
+    Extension, Json,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+// --- 🛰️ AGENT SESSION (WebSocket) ---
+// GET /api/agent/session -> Upgrade. Protocol details: services/agent_loop.rs
+pub async fn agent_session(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Extension(user): Extension<User>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| run_session(socket, state, user))
+}
+
+async fn run_session(socket: WebSocket, state: AppState, user: User) {
+    let (mut sink, mut stream) = socket.split();
+    let (out_tx, mut out_rx) = mpsc::channel::<Value>(32);
+    let (in_tx, in_rx) = mpsc::channel::<Value>(32);
+
+    // Writer: Loop Events -> Socket
+    let writer = tokio::spawn(async move {
+        while let Some(event) = out_rx.recv().await {
+            if sink.send(Message::Text(event.to_string())).await.is_err() {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    // Reader: Socket -> Loop Inbox (Invalid JSON ကို ကျော်မယ်)
+    let reader = tokio::spawn(async move {
+        while let Some(Ok(message)) = stream.next().await {
+            match message {
+                Message::Text(text) => {
+                    if let Ok(value) = serde_json::from_str::<Value>(&text) {
+                        if in_tx.send(value).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+    });
+
+    let mut agent = AgentLoop::new(state, user.clone(), out_tx, in_rx);
+
+    let result = match agent.receive_start().await {
+        Some(start) => match resolve_requested_model(&user, &start) {
+            Ok(model) => agent.run(PlanInput::from_payload(&start), model).await,
+            Err((_, Json(body))) => {
+                let _ = agent.send(json!({ "type": "error", "code": "model_rejected", "error": body["error"] })).await;
+                Ok(())
+            }
+        },
+        None => {
+            let _ = agent.send(json!({ "type": "error", "code": "bad_request", "error": "Expected a `start` message" })).await;
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        if !matches!(e, LoopError::ClientGone) {
+            eprintln!("❌ Agent Session Error [{}]: {}", e.code(), e);
+            let _ = agent.send(json!({
+                "type": "error",
+                "code": e.code(),
+                "error": format!("Neurust Brain Failure: {}", e),
+            })).await;
+        }
+    }
+
+    // Loop ပြီးရင် Outbox ကို ပိတ်မှ Writer က Socket ကို Close လုပ်မယ်
+    drop(agent);
+    let _ = writer.await;
+    reader.abort();
+}
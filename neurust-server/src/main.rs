@@ -19,6 +19,8 @@ use services::{
     ai::AiService,
     billing::BillingService, 
     scraper::ScraperService, 
+    response_cache::ResponseCache,
+    prompt_registry::PromptRegistry,
 };
 
 use tower_http::cors::{CorsLayer, Any}; 
@@ -29,9 +31,14 @@ use handlers::{
     // Auth Handlers (Device Flow - Public)
     initiate_device_flow, poll_device_flow, verify_device_login, 
     // Agent Handlers (Protected)
-    handle_plan_request, handle_audit_request, handle_browse_request, 
+    handle_plan_request, handle_audit_request, handle_browse_request, handle_estimate_request,
+    agent_session,
     // Project Scaffolding (Protected)
     create_project, delete_project,
+    // Model Catalog (Protected)
+    list_models,
+    // Admin (Protected + Admin Role)
+    list_prompts, rollback_prompt,
     // Health Check (Public)
     health_check,
 };
@@ -43,6 +50,8 @@ pub struct AppState {
     pub ai_service: Arc<AiService>,
     pub billing_service: Arc<BillingService>,
     pub scraper_service: Arc<ScraperService>,
+    pub response_cache: Arc<ResponseCache>,
+    pub prompt_registry: Arc<PromptRegistry>,
 }
 
 #[tokio::main]
@@ -71,9 +80,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // 🔥 INITIALIZE SERVICES
-    let ai_service = Arc::new(AiService::new(pool.clone()));
+    let prompt_registry = PromptRegistry::load(pool.clone()).await;
+    prompt_registry.start_hot_reload();
+    let ai_service = Arc::new(AiService::new(pool.clone(), prompt_registry.clone()));
     let billing_service = Arc::new(BillingService::new(pool.clone()));
     let scraper_service = Arc::new(ScraperService::new(pool.clone()));
+    let response_cache = Arc::new(ResponseCache::from_env(pool.clone()));
 
     println!("⏳ Starting Scheduler...");
     // Start the background worker for weekly updates
@@ -85,6 +97,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ai_service,
         billing_service,
         scraper_service,
+        response_cache,
+        prompt_registry,
     };
 
     // CORS Layer Setup
@@ -108,13 +122,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         // 3. Agent (AI Tools)
         .route("/api/agent/plan", post(handle_plan_request))
+        .route("/api/agent/estimate", post(handle_estimate_request))
         .route("/api/agent/browse", post(handle_browse_request))
         .route("/api/agent/audit", post(handle_audit_request))
+        .route("/api/agent/session", get(agent_session))
+        .route("/api/models", get(list_models))
         
         // 4. Project Management
         .route("/api/project/create", post(create_project))
         .route("/api/project/delete", post(delete_project))
 
+        // 5. Admin (Prompt Registry)
+        .route("/api/admin/prompts", get(list_prompts))
+        .route("/api/admin/prompts/rollback", post(rollback_prompt))
+
         // 🔥 Apply Gatekeeper Middleware to ALL routes above
         .layer(axum_middleware::from_fn_with_state(state.clone(), middleware::auth::auth_gatekeeper))
 
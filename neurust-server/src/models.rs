@@ -70,6 +70,8 @@ pub struct UsageLog {
     // For simplicity in this MVP, we might cast to f64 in queries or use string if needed.
     // Here we assume f64 via sqlx feature "postgres"
     pub cost_usd: f64, 
+    pub cached: bool, // ⚡ Served from Response Cache
+    pub prompt_version: Option<String>, // 📝 "architect@v1"
     pub created_at: DateTime<Utc>,
 }
 
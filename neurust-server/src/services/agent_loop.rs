@@ -0,0 +1,356 @@
+use crate::models::User;
+use crate::prompts::{self, PromptVars};
+use crate::services::ai::{AiError, PlanInput, ToolCall};
+use crate::services::{model_catalog, token_budget, util::env_or};
+use crate::AppState;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+// --- 🛰️ SERVER-SIDE AGENT LOOP (Native Tool Calling) ---
+// CLI က Context ကို Turn တိုင်း ပြန်မပို့တော့ဘူး။ Model က Tool တွေ ခေါ်ပြီး လိုတာကို ကိုယ်တိုင်ရှာမယ်။
+// - Server Tools: read_url, search_knowledge (ဒီမှာတင် Run မယ်)
+// - Client Tools: read_file, list_dir, grep (User ရဲ့ Disk ပေါ်မှာ Run ရမှာမို့ Session Channel ကနေ Proxy လုပ်မယ်)
+// - ask_user: User ဆီ မေးခွန်းပြန်ပို့ပြီး အဖြေကို စောင့်မယ်
+// နောက်ဆုံး Plan (သို့) Error တစ်ခုပဲ Client ဆီ ပြန်ရောက်မယ်။
+//
+// Session Protocol (JSON, `type` field):
+//   Client -> Server: start | tool_result {id, ok, output} | user_reply {message} | cancel
+//   Server -> Client: tool_call {id, name, arguments} | ask_user {id, question} | turn {turn, tools} | plan {...} | error {code, error}
+
+/// Client ဘက်မှာ Run ရမယ့် Tools
+pub const CLIENT_TOOLS: &[&str] = &["read_file", "list_dir", "grep"];
+
+/// Tool output ကို Context Window မပြည့်အောင် ဖြတ်မယ်
+const TRUNCATION_NOTE: &str = "\n[... output truncated by Neurust ...]";
+
+#[derive(Debug, Clone)]
+pub struct AgentLoopConfig {
+    pub max_turns: usize,
+    pub tool_call_credits: i32,
+    pub max_tool_output_chars: usize,
+    pub client_timeout: Duration,
+}
+
+impl AgentLoopConfig {
+    /// ENV: AGENT_MAX_TURNS (10), AGENT_TOOL_CALL_CREDITS (1), AGENT_MAX_TOOL_OUTPUT_CHARS (24000), AGENT_CLIENT_TIMEOUT_SECS (600)
+    pub fn from_env() -> Self {
+        Self {
+            max_turns: env_or("AGENT_MAX_TURNS", 10).max(1),
+            tool_call_credits: env_or("AGENT_TOOL_CALL_CREDITS", 1),
+            max_tool_output_chars: env_or("AGENT_MAX_TOOL_OUTPUT_CHARS", 24_000),
+            client_timeout: Duration::from_secs(env_or("AGENT_CLIENT_TIMEOUT_SECS", 600)),
+        }
+    }
+}
+
+/// OpenAI-compatible Tool Schemas
+pub fn tool_definitions() -> Vec<Value> {
+    let tool = |name: &str, description: &str, properties: Value, required: &[&str]| {
+        json!({
+            "type": "function",
+            "function": {
+                "name": name,
+                "description": description,
+                "parameters": { "type": "object", "properties": properties, "required": required }
+            }
+        })
+    };
+
+    vec![
+        tool(
+            "read_file",
+            "Read a file from the user's project. Path is relative to the project root.",
+            json!({ "path": { "type": "string" } }),
+            &["path"],
+        ),
+        tool(
+            "list_dir",
+            "List files and folders in a project directory (non-recursive).",
+            json!({ "path": { "type": "string", "description": "Directory, default \".\"" } }),
+            &[],
+        ),
+        tool(
+            "grep",
+            "Search project files with a regular expression. Returns path:line: text matches.",
+            json!({
+                "pattern": { "type": "string" },
+                "path": { "type": "string", "description": "Directory or file to search, default \".\"" }
+            }),
+            &["pattern"],
+        ),
+        tool(
+            "read_url",
+            "Fetch a web page (documentation, changelog) as plain text.",
+            json!({ "url": { "type": "string" } }),
+            &["url"],
+        ),
+        tool(
+            "search_knowledge",
+            "Search the Neurust knowledge base (Solana, Anchor, Rust crate docs and known error fixes).",
+            json!({ "query": { "type": "string" } }),
+            &["query"],
+        ),
+        tool(
+            "ask_user",
+            "Ask the user one short clarifying question and wait for the answer.",
+            json!({ "question": { "type": "string" } }),
+            &["question"],
+        ),
+    ]
+}
+
+/// Session ပြီးဆုံးပုံ
+#[derive(Debug)]
+pub enum LoopError {
+    Ai(AiError),
+    InsufficientCredits,
+    TurnLimit(usize),
+    ClientGone,
+    Cancelled,
+}
+
+impl LoopError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            LoopError::Ai(e) => e.code(),
+            LoopError::InsufficientCredits => "insufficient_credits",
+            LoopError::TurnLimit(_) => "turn_limit",
+            LoopError::ClientGone => "client_gone",
+            LoopError::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl std::fmt::Display for LoopError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoopError::Ai(e) => write!(f, "{}", e),
+            LoopError::InsufficientCredits => write!(f, "Insufficient Credits. Please top up."),
+            LoopError::TurnLimit(n) => write!(f, "Agent stopped after {} turns without a final plan", n),
+            LoopError::ClientGone => write!(f, "Client disconnected"),
+            LoopError::Cancelled => write!(f, "Session cancelled by user"),
+        }
+    }
+}
+
+impl From<AiError> for LoopError {
+    fn from(e: AiError) -> Self {
+        LoopError::Ai(e)
+    }
+}
+
+pub struct AgentLoop {
+    state: AppState,
+    user: User,
+    config: AgentLoopConfig,
+    outbox: mpsc::Sender<Value>,
+    inbox: mpsc::Receiver<Value>,
+}
+
+impl AgentLoop {
+    pub fn new(state: AppState, user: User, outbox: mpsc::Sender<Value>, inbox: mpsc::Receiver<Value>) -> Self {
+        Self { state, user, config: AgentLoopConfig::from_env(), outbox, inbox }
+    }
+
+    /// Client ရဲ့ ပထမဆုံး Message (`start`) ကို စောင့်မယ်
+    pub async fn receive_start(&mut self) -> Option<Value> {
+        match tokio::time::timeout(Duration::from_secs(30), self.inbox.recv()).await {
+            Ok(Some(msg)) if msg["type"] == "start" => Some(msg),
+            _ => None,
+        }
+    }
+
+    pub async fn send(&self, event: Value) -> Result<(), LoopError> {
+        self.outbox.send(event).await.map_err(|_| LoopError::ClientGone)
+    }
+
+    /// Loop ကို Run မယ်။ Plan ရရင် `plan` event ပို့ပြီး Ok၊ မရရင် Err (Handler က `error` event ပို့မယ်)
+    pub async fn run(&mut self, mut input: PlanInput, model_override: Option<&str>) -> Result<(), LoopError> {
+        input.user_tier = Some(format!("{:?}", self.user.role));
+        let ai = self.state.ai_service.clone();
+
+        let prepared = ai.prepare_plan(&input, model_override).await?;
+        let model = prepared.model.clone();
+        let prompt_version = prepared.prompt_version.clone();
+
+        let tool_vars = PromptVars { max_turns: Some(self.config.max_turns), ..input.prompt_vars() };
+        let tool_prompt = self.state.prompt_registry.render(prompts::AGENT_TOOLS, &tool_vars);
+
+        let mut messages = prepared.budgeted.messages();
+        messages.insert(1.min(messages.len()), json!({ "role": "system", "content": tool_prompt.text }));
+
+        let tools = tool_definitions();
+        let mut total_tool_calls = 0usize;
+
+        println!("🛰️ Agent session for {} (Model: {} | Max turns: {})", self.user.wallet_address, model, self.config.max_turns);
+
+        for turn in 1..=self.config.max_turns {
+            // 💰 Turn တိုင်း Credit စစ်မယ်
+            match self.state.billing_service.has_sufficient_credits(self.user.id).await {
+                Ok(true) => {}
+                Ok(false) => return Err(LoopError::InsufficientCredits),
+                Err(e) => {
+                    println!("❌ Billing Check Error: {}", e);
+                    return Err(LoopError::InsufficientCredits);
+                }
+            }
+
+            let max_tokens = remaining_output_tokens(&model, &messages)?;
+            let completion = ai.chat_with_tools(&model, &self.user.role, &messages, &tools, max_tokens).await?;
+
+            if let Err(e) = self.state.billing_service.deduct_credits(
+                self.user.id,
+                &completion.model,
+                "agent_turn",
+                completion.usage.clone(),
+                Some(&prompt_version),
+            ).await {
+                println!("❌ Failed to deduct credits: {}", e);
+            }
+
+            // 1. Tool Calls -> Execute, append results, next turn
+            if !completion.tool_calls.is_empty() {
+                messages.push(json!({
+                    "role": "assistant",
+                    "content": completion.content,
+                    "tool_calls": completion.tool_calls,
+                }));
+
+                let mut used = Vec::new();
+                for call in &completion.tool_calls {
+                    let output = self.execute_tool(call).await?;
+                    messages.push(json!({
+                        "role": "tool",
+                        "tool_call_id": call.id,
+                        "content": truncate_output(&output, self.config.max_tool_output_chars),
+                    }));
+                    used.push(call.function.name.clone());
+                }
+                total_tool_calls += used.len();
+
+                self.send(json!({ "type": "turn", "turn": turn, "tools": used })).await?;
+                continue;
+            }
+
+            // 2. Text Reply -> Final Plan
+            let text = completion.content.unwrap_or_default();
+            match ai.parse_plan(&text) {
+                Ok(mut plan) => {
+                    if let Some(obj) = plan.as_object_mut() {
+                        obj.insert("context_report".to_string(), json!(prepared.budgeted.report));
+                        obj.insert("prompt_version".to_string(), json!(prompt_version));
+                        obj.insert("cached".to_string(), json!(false));
+                    }
+                    println!("✅ Agent session finished in {} turn(s), {} tool call(s)", turn, total_tool_calls);
+                    return self.send(json!({
+                        "type": "plan",
+                        "plan": plan,
+                        "model": completion.model,
+                        "turns": turn,
+                        "tool_calls": total_tool_calls,
+                    })).await;
+                }
+                Err(e) => {
+                    println!("❌ Agent reply was not valid JSON (turn {}): {}", turn, e);
+                    messages.push(json!({ "role": "assistant", "content": text }));
+                    messages.push(json!({
+                        "role": "user",
+                        "content": format!("Your last reply was not valid JSON ({}). Reply with the final JSON plan only.", e),
+                    }));
+                }
+            }
+        }
+
+        Err(LoopError::TurnLimit(self.config.max_turns))
+    }
+
+    /// Tool တစ်ခုကို Run ပြီး Model ဆီ ပြန်ပို့မယ့် Text ပြန်ပေးမယ် (Tool Error ကိုလည်း Text အဖြစ်ပဲ ပြန်ပေးမယ်)
+    async fn execute_tool(&mut self, call: &ToolCall) -> Result<String, LoopError> {
+        let name = call.function.name.as_str();
+        let args: Value = serde_json::from_str(&call.function.arguments).unwrap_or(json!({}));
+        println!("🛠️ Tool call: {} {}", name, args);
+
+        let output = match name {
+            "ask_user" => {
+                let question = args["question"].as_str().unwrap_or("Could you clarify?");
+                self.send(json!({ "type": "ask_user", "id": call.id, "question": question })).await?;
+                let reply = self.await_client("user_reply", &call.id).await?;
+                // ask_user က User ဆီ ပြန်မေးတာမို့ Fee မယူဘူး
+                return Ok(reply["message"].as_str().unwrap_or("").to_string());
+            }
+            "read_url" => {
+                let url = args["url"].as_str().unwrap_or("");
+                // Public http(s) ပဲ၊ Shared Knowledge Store ထဲ မသိမ်းဘူး
+                match self.state.scraper_service.scrape_public_url(url).await {
+                    Ok(content) => content,
+                    Err(e) => format!("ERROR: {}", e),
+                }
+            }
+            "search_knowledge" => {
+                let query = args["query"].as_str().unwrap_or("");
+                let found = self.state.ai_service.search_knowledge(query).await;
+                if found.trim().is_empty() { "No matching knowledge found.".to_string() } else { found }
+            }
+            tool if CLIENT_TOOLS.contains(&tool) => {
+                self.send(json!({ "type": "tool_call", "id": call.id, "name": tool, "arguments": args })).await?;
+                let result = self.await_client("tool_result", &call.id).await?;
+                let output = result["output"].as_str().unwrap_or("").to_string();
+                if result["ok"].as_bool().unwrap_or(false) { output } else { format!("ERROR: {}", output) }
+            }
+            unknown => format!("ERROR: Unknown tool '{}'", unknown),
+        };
+
+        if let Err(e) = self.state.billing_service
+            .charge_tool_call(self.user.id, name, self.config.tool_call_credits)
+            .await
+        {
+            println!("❌ Failed to bill tool call: {}", e);
+        }
+
+        Ok(output)
+    }
+
+    /// Client ဆီက `expected` type + matching `id` ကို စောင့်မယ် (cancel / disconnect / timeout ဆို ရပ်မယ်)
+    async fn await_client(&mut self, expected: &str, id: &str) -> Result<Value, LoopError> {
+        loop {
+            let msg = match tokio::time::timeout(self.config.client_timeout, self.inbox.recv()).await {
+                Ok(Some(msg)) => msg,
+                Ok(None) | Err(_) => return Err(LoopError::ClientGone),
+            };
+
+            if msg["type"] == "cancel" {
+                return Err(LoopError::Cancelled);
+            }
+            if msg["type"] == expected && msg["id"].as_str().is_none_or(|msg_id| msg_id == id) {
+                return Ok(msg);
+            }
+            println!("⚠️ Ignoring unexpected session message: {}", msg["type"]);
+        }
+    }
+}
+
+/// Conversation ကြီးလာရင် Output အတွက် ကျန်တဲ့ Token (MIN_OUTPUT_TOKENS မကျန်တော့ရင် 413)
+fn remaining_output_tokens(model: &str, messages: &[Value]) -> Result<usize, AiError> {
+    let family = token_budget::ModelFamily::from_model(model);
+    let window = model_catalog::context_window_for(model);
+    let used = token_budget::estimate_tokens(&Value::Array(messages.to_vec()).to_string(), family);
+    let usable = window - window / 20; // 5% safety margin (same as token_budget)
+    let remaining = usable.saturating_sub(used);
+
+    if remaining < token_budget::MIN_OUTPUT_TOKENS {
+        return Err(AiError::ContextTooLarge { required_tokens: used, available_tokens: usable });
+    }
+    Ok(remaining.min(token_budget::MAX_OUTPUT_TOKENS))
+}
+
+fn truncate_output(output: &str, max_chars: usize) -> String {
+    if output.len() <= max_chars {
+        return output.to_string();
+    }
+    let mut cut = max_chars;
+    while !output.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    format!("{}{}", &output[..cut], TRUNCATION_NOTE)
+}
@@ -1,13 +1,144 @@
-use crate::prompts; 
+use crate::models::UserRole;
+use crate::prompts::{self, PromptVars};
 use crate::services::knowledge_store::KnowledgeStore;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use crate::services::prompt_registry::PromptRegistry;
+use crate::services::model_catalog;
+use crate::services::resilience::{CircuitBreaker, RetryPolicy};
+use crate::services::token_budget::{self, BudgetReport, BudgetedRequest, Section, SectionKind};
+use axum::http::StatusCode;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, RETRY_AFTER};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sqlx::PgPool;
 use std::env;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
+const PROVIDER: &str = "openrouter";
+
+// --- Typed Errors ---
+
+/// Provider Error များကို အမျိုးအစားခွဲထားမယ် (Handler က Status Code မှန်မှန် ပြန်ပေးနိုင်အောင်)
+#[derive(Debug, Clone)]
+pub enum AiError {
+    /// 429 - Provider Rate Limit (Retry-After ပါလာရင် သိမ်းထားမယ်)
+    RateLimited { model: String, retry_after: Option<Duration> },
+    /// 401/403 - Our API key is invalid, expired or out of funds
+    AuthFailed(String),
+    /// Prompt is larger than the model's context window
+    ContextLengthExceeded(String),
+    /// Provider moderation blocked the prompt or the completion
+    ContentFiltered(String),
+    /// Request timed out (network or provider)
+    Timeout(String),
+    /// 408/5xx - Provider side failure
+    ProviderUnavailable { status: u16, message: String },
+    /// Circuit breaker is open for this model
+    CircuitOpen { model: String },
+    /// Response could not be parsed (bad JSON plan, empty content, ...)
+    InvalidResponse(String),
+    /// Request won't fit the model's context window even after trimming (never sent)
+    ContextTooLarge { required_tokens: usize, available_tokens: usize },
+}
+
+impl AiError {
+    /// Retry လုပ်ရင် ရနိုင်တဲ့ Error လား (Transient)
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            AiError::RateLimited { .. } | AiError::Timeout(_) | AiError::ProviderUnavailable { .. }
+        )
+    }
+
+    /// Fallback Model ကို ပြောင်းစမ်းသင့်လား
+    pub fn should_fallback(&self) -> bool {
+        self.is_retryable() || matches!(self, AiError::CircuitOpen { .. })
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            AiError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// Machine-readable code for clients
+    pub fn code(&self) -> &'static str {
+        match self {
+            AiError::RateLimited { .. } => "rate_limited",
+            AiError::AuthFailed(_) => "provider_auth_failed",
+            AiError::ContextLengthExceeded(_) => "context_length_exceeded",
+            AiError::ContentFiltered(_) => "content_filtered",
+            AiError::Timeout(_) => "timeout",
+            AiError::ProviderUnavailable { .. } => "provider_unavailable",
+            AiError::CircuitOpen { .. } => "circuit_open",
+            AiError::InvalidResponse(_) => "invalid_response",
+            AiError::ContextTooLarge { .. } => "context_budget_exceeded",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AiError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            AiError::AuthFailed(_) => StatusCode::BAD_GATEWAY,
+            AiError::ContextLengthExceeded(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AiError::ContentFiltered(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AiError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            AiError::ProviderUnavailable { .. } | AiError::CircuitOpen { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            AiError::InvalidResponse(_) => StatusCode::BAD_GATEWAY,
+            AiError::ContextTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+        }
+    }
+
+    /// Provider ရဲ့ HTTP Error Response ကို Typed Error အဖြစ် ခွဲခြားမယ်
+    fn from_provider_response(model: &str, status: reqwest::StatusCode, retry_after: Option<Duration>, body: &str) -> Self {
+        let lower = body.to_lowercase();
+
+        if lower.contains("context length")
+            || lower.contains("context_length")
+            || lower.contains("maximum context")
+            || lower.contains("too many tokens")
+        {
+            return AiError::ContextLengthExceeded(body.to_string());
+        }
+
+        if lower.contains("moderation") || lower.contains("flagged") || lower.contains("content_filter") {
+            return AiError::ContentFiltered(body.to_string());
+        }
+
+        match status.as_u16() {
+            429 => AiError::RateLimited { model: model.to_string(), retry_after },
+            401..=403 => AiError::AuthFailed(format!("API Error {}: {}", status, body)),
+            408 | 500..=599 => AiError::ProviderUnavailable { status: status.as_u16(), message: body.to_string() },
+            _ => AiError::InvalidResponse(format!("API Error {}: {}", status, body)),
+        }
+    }
+}
+
+impl fmt::Display for AiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AiError::RateLimited { model, retry_after } => match retry_after {
+                Some(wait) => write!(f, "Rate limited on {} (retry after {}s)", model, wait.as_secs()),
+                None => write!(f, "Rate limited on {}", model),
+            },
+            AiError::AuthFailed(msg) => write!(f, "Provider authentication failed: {}", msg),
+            AiError::ContextLengthExceeded(msg) => write!(f, "Context length exceeded: {}", msg),
+            AiError::ContentFiltered(msg) => write!(f, "Content filtered by provider: {}", msg),
+            AiError::Timeout(msg) => write!(f, "Request timed out: {}", msg),
+            AiError::ProviderUnavailable { status, message } => write!(f, "Provider unavailable ({}): {}", status, message),
+            AiError::CircuitOpen { model } => write!(f, "Model {} is temporarily disabled (circuit open)", model),
+            AiError::InvalidResponse(msg) => write!(f, "{}", msg),
+            AiError::ContextTooLarge { required_tokens, available_tokens } => write!(
+                f,
+                "Request too large: system prompt and request need ~{} tokens but only {} fit the model's context window. Shorten the prompt or pick a larger-context model.",
+                required_tokens, available_tokens
+            ),
+        }
+    }
+}
 
 // --- Data Structures ---
 
@@ -18,7 +149,7 @@ struct ChatResponse {
     model: String, // 🔥 Added: Capture the actual model used by API
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UsageStats {
     pub prompt_tokens: i32,
     pub completion_tokens: i32,
@@ -28,11 +159,130 @@ pub struct UsageStats {
 #[derive(Deserialize, Debug)]
 struct Choice {
     message: MessageContent,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Deserialize, Debug)]
 struct MessageContent {
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCall>>,
+}
+
+/// Native Function Calling (OpenAI-compatible `tool_calls`)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type", default = "function_kind")]
+    pub kind: String,
+    pub function: FunctionCall,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FunctionCall {
+    pub name: String,
+    pub arguments: String, // JSON-encoded string (Provider format)
+}
+
+fn function_kind() -> String {
+    "function".to_string()
+}
+
+/// Single completion (Text and/or Tool Calls)
+#[derive(Debug, Clone)]
+pub struct ChatCompletion {
+    pub content: Option<String>,
+    pub tool_calls: Vec<ToolCall>,
+    pub usage: UsageStats,
+    pub model: String,
+}
+
+/// Plan Request Input (`/plan` နဲ့ `/estimate` Payload အတူတူ)
+#[derive(Debug, Clone, Default)]
+pub struct PlanInput {
+    pub prompt: String,
+    pub context: Option<String>,
+    pub history: Option<String>,
+    /// Prompt Template Variables (Client က မပို့ရင် Context ကနေ ခန့်မှန်းမယ်)
+    pub stack: Option<String>,
+    pub project_type: Option<String>,
+    /// Handler က User Role ကနေ ဖြည့်မယ် (Payload ကနေ မယူဘူး)
+    pub user_tier: Option<String>,
+    /// Project Rules (`.neurust/rules.md` + Global) - Truncation မခံရတဲ့ System Section
+    pub rules: Option<String>,
+}
+
+impl PlanInput {
+    pub fn from_payload(payload: &Value) -> Self {
+        Self {
+            prompt: payload["prompt"].as_str().unwrap_or("").to_string(),
+            context: payload["context"].as_str().map(|s| s.to_string()),
+            history: payload["history"].as_str().map(|s| s.to_string()),
+            stack: payload["stack"].as_str().map(|s| s.to_string()),
+            project_type: payload["project_type"].as_str().map(|s| s.to_string()),
+            user_tier: None,
+            rules: rules_from_payload(payload),
+        }
+    }
+
+    pub fn prompt_vars(&self) -> PromptVars {
+        PromptVars::detect(
+            self.stack.as_deref(),
+            self.project_type.as_deref(),
+            self.context.as_deref(),
+            self.user_tier.clone(),
+        )
+    }
+}
+
+/// Rules ကို Required Section အဖြစ် ထည့်မှာမို့ Size ကန့်သတ်မယ် (Context Window ကို မလုယူအောင်)
+pub const MAX_RULES_CHARS: usize = 16_000;
+
+/// Payload `rules` (Empty ဆိုရင် None, MAX_RULES_CHARS ထက်ကျော်ရင် ဖြတ်မယ်)
+pub fn rules_from_payload(payload: &Value) -> Option<String> {
+    let rules = payload["rules"].as_str()?.trim();
+    if rules.is_empty() {
+        return None;
+    }
+    if rules.len() <= MAX_RULES_CHARS {
+        return Some(rules.to_string());
+    }
+
+    let mut cut = MAX_RULES_CHARS;
+    while !rules.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    println!("✂️ Project rules truncated ({} -> {} chars)", rules.len(), cut);
+    Some(format!("{}\n[... rules truncated at {} chars ...]", &rules[..cut], MAX_RULES_CHARS))
+}
+
+/// Rules Section (System role: User Request ထက် အာဏာပိုရှိအောင်)
+fn rules_section(rules: &str) -> Section {
+    Section::new(
+        SectionKind::Rules,
+        "system",
+        format!("--- PROJECT RULES (MANDATORY - THESE OVERRIDE DEFAULT CONVENTIONS) ---\n{}", rules),
+    )
+}
+
+/// LLM ဆီ ပို့ဖို့ အသင့်ဖြစ်နေတဲ့ Request (Routed model + budgeted messages)
+#[derive(Debug)]
+pub struct PreparedPlan {
+    pub model: String,
+    pub is_complex: bool,
+    pub budgeted: BudgetedRequest,
+    pub prompt_version: String, // "architect@v1"
+}
+
+/// AI Call ရလဒ် (Billing + Client Report အတွက် လိုအပ်တာ အကုန်)
+#[derive(Debug)]
+pub struct AiOutput<T> {
+    pub content: T,
+    pub usage: UsageStats,
+    pub model: String, // Model that ACTUALLY answered
+    pub context_report: BudgetReport,
+    pub prompt_version: String, // Prompt Registry version that produced this output
 }
 
 // Service Implementation
@@ -40,10 +290,14 @@ pub struct AiService {
     client: reqwest::Client,
     api_key: String,
     knowledge_store: KnowledgeStore,
+    retry_policy: RetryPolicy,
+    circuit_breaker: CircuitBreaker,
+    fallback_model: Option<String>, // 🔀 MODEL_FALLBACK (Secondary model when primary is down)
+    prompts: Arc<PromptRegistry>,
 }
 
 impl AiService {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PgPool, prompts: Arc<PromptRegistry>) -> Self {
         let api_key = env::var("OPENROUTER_API_KEY").expect("OPENROUTER_API_KEY must be set");
         Self {
             client: reqwest::Client::builder()
@@ -52,59 +306,106 @@ impl AiService {
                 .unwrap(),
             api_key,
             knowledge_store: KnowledgeStore::new(pool),
+            retry_policy: RetryPolicy::from_env(),
+            circuit_breaker: CircuitBreaker::from_env(),
+            fallback_model: env::var("MODEL_FALLBACK").ok().filter(|m| !m.trim().is_empty()),
+            prompts,
         }
     }
 
-    /// Smart Generation with TIERED ROUTING & BILLING SUPPORT
-    /// Returns: (JSON Plan, Token Usage Stats, Used Model Name)
-    pub async fn generate_project_plan(
-        &self,
-        user_prompt: &str,
-        context: Option<String>,
-    ) -> Result<(Value, UsageStats, String), String> { // 🔥 Return Tuple updated
-        
-        // 🔥 SMART ROUTING LOGIC
-        let is_complex = user_prompt.len() > 100 
+    /// 🔥 SMART ROUTING LOGIC: Plan အတွက် သုံးမယ့် Model (Response Cache Key လည်း ဒါကို သုံးမယ်)
+    pub fn route_plan_model(input: &PlanInput, model_override: Option<&str>) -> String {
+        // Client က Model ရွေးလာရင် (Handler မှာ Allowlist စစ်ပြီးသား) Routing ကို ကျော်မယ်
+        match model_override {
+            Some(selected) => selected.to_string(),
+            None if Self::is_complex(input) => {
+                env::var("MODEL_THINKING").unwrap_or("openai/gpt-5.1-codex-max".to_string())
+            }
+            None => env::var("MODEL_FAST").unwrap_or("openai/gpt-5.1-codex-mini".to_string()),
+        }
+    }
+
+    fn is_complex(input: &PlanInput) -> bool {
+        let user_prompt = input.prompt.as_str();
+        user_prompt.len() > 100 
             || user_prompt.contains("create") 
             || user_prompt.contains("audit")
             || user_prompt.contains("refactor")
             || user_prompt.contains("fix")
-            || context.is_some();
+            || input.context.is_some()
+    }
 
-        let model = if is_complex {
-            env::var("MODEL_THINKING").unwrap_or("openai/gpt-5.1-codex-max".to_string()) 
-        } else {
-            env::var("MODEL_FAST").unwrap_or("openai/gpt-5.1-codex-mini".to_string())
-        };
+    /// Audit အတွက် သုံးမယ့် Model (Override မပါရင် Thinking Model)
+    pub fn route_audit_model(model_override: Option<&str>) -> String {
+        match model_override {
+            Some(selected) => selected.to_string(),
+            None => env::var("MODEL_THINKING").unwrap_or("openai/gpt-5.1-codex-max".to_string()),
+        }
+    }
 
-        println!("🚀 Consulting Architect (Model: {} | Complex: {})", model, is_complex);
+    /// Routing + RAG + Token Budget (LLM မခေါ်ခင် အဆင့်)
+    /// `/plan` နဲ့ `/estimate` နှစ်ခုလုံး ဒီ Logic တစ်ခုတည်းကို သုံးမယ် (Estimate က Plan အစစ်နဲ့ ကိုက်ညီအောင်)
+    pub async fn prepare_plan(
+        &self,
+        input: &PlanInput,
+        model_override: Option<&str>,
+    ) -> Result<PreparedPlan, AiError> {
+        let user_prompt = input.prompt.as_str();
+        let is_complex = Self::is_complex(input);
+        let model = Self::route_plan_model(input, model_override);
 
         // 1. RAG Search (Inject Knowledge from DB)
         let rag_content = self.knowledge_store.search(user_prompt).await;
 
-        // 2. Construct System Message
-        let system_instruction = prompts::get_architect_prompt();
+        // 2. Construct System Message (Prompt Registry: Active Version + Template Variables)
+        let system_prompt = self.prompts.render(prompts::ARCHITECT, &input.prompt_vars());
 
-        // 3. Construct Context Message
-        let mut context_block = String::from("--- PROJECT CONTEXT (READ ONLY) ---\n");
-        if let Some(ctx) = context {
-            context_block.push_str(&ctx);
+        // 3. Sections (Priority အလိုက် Token Budget ခွဲဝေမယ်)
+        let mut sections = vec![Section::new(SectionKind::System, "system", system_prompt.text)];
+        if let Some(rules) = &input.rules {
+            sections.push(rules_section(rules));
+        }
+        if let Some(ctx) = &input.context {
+            sections.push(Section::new(
+                SectionKind::ProjectContext,
+                "user",
+                format!("--- PROJECT CONTEXT (READ ONLY) ---\n{}", ctx),
+            ));
+        }
+        if !rag_content.trim().is_empty() {
+            sections.push(Section::new(SectionKind::Rag, "user", format!("--- RAG KNOWLEDGE ---\n{}", rag_content)));
+        }
+        if let Some(hist) = input.history.as_ref().filter(|h| !h.trim().is_empty()) {
+            sections.push(Section::new(SectionKind::History, "user", format!("--- CONVERSATION HISTORY ---\n{}", hist)));
         }
-        context_block.push_str("\n\n--- RAG KNOWLEDGE ---\n");
-        context_block.push_str(&rag_content);
 
         // 4. User Request
         let user_request_block = format!(
             "--- USER REQUEST ---\n{}\n\nGenerate the JSON execution plan.", 
             user_prompt
         );
+        sections.push(Section::new(SectionKind::UserRequest, "user", user_request_block));
 
-        // 5. API Payload
-        let messages = vec![
-            json!({ "role": "system", "content": system_instruction }),
-            json!({ "role": "user", "content": context_block }), 
-            json!({ "role": "user", "content": user_request_block }) 
-        ];
+        // 5. 🧮 Token Budget
+        let budgeted = Self::fit_budget(&model, sections)?;
+
+        Ok(PreparedPlan { model, is_complex, budgeted, prompt_version: system_prompt.version })
+    }
+
+    /// Smart Generation with TIERED ROUTING & BILLING SUPPORT
+    /// Returns: JSON Plan + Token Usage Stats + Used Model Name + Context Budget Report
+    pub async fn generate_project_plan(
+        &self,
+        input: &PlanInput,
+        model_override: Option<&str>,
+        role: &UserRole,
+    ) -> Result<AiOutput<Value>, AiError> {
+        let PreparedPlan { model, is_complex, budgeted, prompt_version } = self.prepare_plan(input, model_override).await?;
+
+        println!("🚀 Consulting Architect (Model: {} | Complex: {} | Prompt: {})", model, is_complex, prompt_version);
+
+        let messages = budgeted.messages();
+        let max_tokens = budgeted.report.max_output_tokens;
 
         let max_retries = 3;
 
@@ -116,7 +417,7 @@ impl AiService {
 
             // Call API and get Usage + Model
             let (response_text, usage, used_model) = self
-                .call_openrouter_with_messages(&model, messages.clone())
+                .call_openrouter_with_messages(&model, role, messages.clone(), max_tokens)
                 .await?;
 
             let raw_json = self.clean_json_markdown(&response_text);
@@ -125,39 +426,208 @@ impl AiService {
             match serde_json::from_str::<Value>(&sanitized_json) {
                 Ok(plan) => {
                     // ✅ SUCCESS: Return Plan, Usage AND Real Model Name
-                    return Ok((plan, usage, used_model));
+                    return Ok(AiOutput {
+                        content: plan,
+                        usage,
+                        model: used_model,
+                        context_report: budgeted.report,
+                        prompt_version,
+                    });
                 },
                 Err(e) => {
                     println!("❌ JSON Error on attempt {}: {}", attempt, e);
                     if attempt == max_retries {
-                        return Err(format!("Failed to parse JSON. Error: {}", e));
+                        return Err(AiError::InvalidResponse(format!("Failed to parse JSON. Error: {}", e)));
                     }
                 }
             }
         }
 
-        Err("Auto-healing failed.".to_string())
+        Err(AiError::InvalidResponse("Auto-healing failed.".to_string()))
     }
 
     /// Audit Code -> Returns (Report, Usage, Model)
-    pub async fn audit_code(&self, code: &str) -> Result<(String, UsageStats, String), String> {
-        let model = env::var("MODEL_THINKING").unwrap_or("openai/gpt-5.1-codex-max".to_string());
+    pub async fn audit_code(
+        &self,
+        code: &str,
+        model_override: Option<&str>,
+        role: &UserRole,
+        vars: &PromptVars,
+        rules: Option<&str>,
+    ) -> Result<AiOutput<String>, AiError> {
+        let model = Self::route_audit_model(model_override);
         println!("🕵️ Auditing with Model: {}", model);
         
-        let messages = vec![
-            json!({ "role": "system", "content": prompts::SYSTEM_AUDITOR }),
-            json!({ "role": "user", "content": code })
-        ];
+        let system_prompt = self.prompts.render(prompts::AUDITOR, vars);
+        let mut sections = vec![Section::new(SectionKind::System, "system", system_prompt.text)];
+        if let Some(rules) = rules {
+            sections.push(rules_section(rules));
+        }
+        sections.push(Section::new(SectionKind::ProjectContext, "user", code));
+        let budgeted = Self::fit_budget(&model, sections)?;
+
+        let (report, usage, used_model) = self
+            .call_openrouter_with_messages(&model, role, budgeted.messages(), budgeted.report.max_output_tokens)
+            .await?;
+
+        Ok(AiOutput {
+            content: report,
+            usage,
+            model: used_model,
+            context_report: budgeted.report,
+            prompt_version: system_prompt.version,
+        })
+    }
 
-        self.call_openrouter_with_messages(&model, messages).await
+    /// Token Budget ထဲ ထည့်မယ်၊ မဆန့်ရင် 413 (ContextTooLarge)
+    fn fit_budget(model: &str, sections: Vec<Section>) -> Result<BudgetedRequest, AiError> {
+        let budgeted = token_budget::fit_to_budget(model, sections).map_err(|e| AiError::ContextTooLarge {
+            required_tokens: e.required_tokens,
+            available_tokens: e.available_tokens,
+        })?;
+
+        let report = &budgeted.report;
+        println!("🧮 Token Budget [{}]: ~{} input / {} window (max output {})",
+            model, report.input_tokens, report.context_window, report.max_output_tokens);
+        if report.was_trimmed() {
+            println!("✂️ Context trimmed to fit. Dropped files: {}", report.dropped_files.len());
+        }
+        Ok(budgeted)
     }
 
     // Unified API Call Function (Now returns UsageStats AND Model Name)
+    // 🛡️ Retry + Circuit Breaker + Fallback Model
+    // Returned model name = Model that ACTUALLY answered (Billing က ဒါကို သုံးမယ်)
     async fn call_openrouter_with_messages(
         &self,
         model: &str,
+        role: &UserRole,
         messages: Vec<Value>,
-    ) -> Result<(String, UsageStats, String), String> { // 🔥 Return Tuple updated
+        max_tokens: usize,
+    ) -> Result<(String, UsageStats, String), AiError> { // 🔥 Return Tuple updated
+        let completion = self.complete(model, role, &messages, max_tokens, None).await?;
+        let content = completion.content
+            .ok_or_else(|| AiError::InvalidResponse("AI returned no content".to_string()))?;
+        Ok((content, completion.usage, completion.model))
+    }
+
+    /// 🛠️ Agent Loop: Tool definitions ပါတဲ့ Completion (Text or Tool Calls)
+    pub async fn chat_with_tools(
+        &self,
+        model: &str,
+        role: &UserRole,
+        messages: &[Value],
+        tools: &[Value],
+        max_tokens: usize,
+    ) -> Result<ChatCompletion, AiError> {
+        self.complete(model, role, messages, max_tokens, Some(tools)).await
+    }
+
+    /// Knowledge Base (RAG) Search - Agent `search_knowledge` tool
+    pub async fn search_knowledge(&self, query: &str) -> String {
+        self.knowledge_store.search(query).await
+    }
+
+    /// Model ရဲ့ Text Reply ကို Plan JSON အဖြစ် Parse မယ် (Markdown fence + raw newline cleanup)
+    pub fn parse_plan(&self, text: &str) -> Result<Value, serde_json::Error> {
+        let raw_json = self.clean_json_markdown(text);
+        serde_json::from_str(&self.sanitize_json_string(&raw_json))
+    }
+
+    /// Primary Model -> Fallback Model
+    /// Fallback ကို User ရဲ့ Tier က ရွေးခွင့်ရှိမှ သုံးမယ် (Free User ကို Flagship Model နဲ့ မဖြေဘူး)
+    async fn complete(
+        &self,
+        model: &str,
+        role: &UserRole,
+        messages: &[Value],
+        max_tokens: usize,
+        tools: Option<&[Value]>,
+    ) -> Result<ChatCompletion, AiError> {
+        let mut candidates = vec![model.to_string()];
+        if let Some(fallback) = &self.fallback_model {
+            // Fallback Model ရဲ့ Context Window ထဲ ဆန့်မှ ထည့်မယ်
+            let family = token_budget::ModelFamily::from_model(fallback);
+            let input_tokens = token_budget::estimate_tokens(&Value::Array(messages.to_vec()).to_string(), family);
+            let fits = input_tokens + token_budget::MIN_OUTPUT_TOKENS <= model_catalog::context_window_for(fallback);
+            let allowed = model_catalog::find(fallback).is_some_and(|info| model_catalog::is_allowed(role, info));
+            if fallback != model && fits && allowed {
+                candidates.push(fallback.clone());
+            }
+        }
+
+        let mut last_error = None;
+        for (index, candidate) in candidates.iter().enumerate() {
+            if index > 0 {
+                println!("🔀 Falling back to secondary model: {}", candidate);
+            }
+
+            match self.call_with_retries(candidate, messages, max_tokens, tools).await {
+                Ok(result) => return Ok(result),
+                Err(e) if e.should_fallback() => {
+                    println!("⚠️ Model [{}] failed: {}", candidate, e);
+                    last_error = Some(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| AiError::InvalidResponse("No model candidates".to_string())))
+    }
+
+    /// One model, many attempts (Jittered Exponential Backoff, honours Retry-After)
+    async fn call_with_retries(
+        &self,
+        model: &str,
+        messages: &[Value],
+        max_tokens: usize,
+        tools: Option<&[Value]>,
+    ) -> Result<ChatCompletion, AiError> {
+        let breaker_key = format!("{}:{}", PROVIDER, model);
+
+        for attempt in 0..self.retry_policy.max_attempts {
+            if !self.circuit_breaker.allow(&breaker_key) {
+                return Err(AiError::CircuitOpen { model: model.to_string() });
+            }
+
+            match self.send_chat_request(model, messages, max_tokens, tools).await {
+                Ok(result) => {
+                    self.circuit_breaker.record_success(&breaker_key);
+                    return Ok(result);
+                }
+                Err(e) if e.is_retryable() => {
+                    self.circuit_breaker.record_failure(&breaker_key);
+
+                    if attempt + 1 >= self.retry_policy.max_attempts {
+                        return Err(e);
+                    }
+                    let Some(delay) = self.retry_policy.delay_for(attempt, e.retry_after()) else {
+                        // Retry-After က ကြာလွန်းတယ် -> စောင့်မနေဘဲ Fallback ကို လွှဲမယ်
+                        return Err(e);
+                    };
+
+                    println!("🔁 [{}] {} -> retrying in {:?} (attempt {}/{})",
+                        model, e, delay, attempt + 2, self.retry_policy.max_attempts);
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    self.circuit_breaker.release(&breaker_key);
+                    return Err(e);
+                }
+            }
+        }
+
+        Err(AiError::ProviderUnavailable { status: 0, message: "Retries exhausted".to_string() })
+    }
+
+    /// Single HTTP request to OpenRouter
+    async fn send_chat_request(
+        &self,
+        model: &str,
+        messages: &[Value],
+        max_tokens: usize,
+        tools: Option<&[Value]>,
+    ) -> Result<ChatCompletion, AiError> {
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         let mut auth_val = HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap();
@@ -167,32 +637,52 @@ impl AiService {
         headers.insert("HTTP-Referer", HeaderValue::from_static("https://neurust.app"));
         headers.insert("X-Title", HeaderValue::from_static("Neurust AI"));
 
-        let payload = json!({
+        let mut payload = json!({
             "model": model,
             "messages": messages,
             "temperature": 0.2,
-            "max_tokens": 16000, 
+            "max_tokens": max_tokens,
         });
+        if let Some(tools) = tools {
+            payload["tools"] = json!(tools);
+            payload["tool_choice"] = json!("auto");
+        }
 
         let res = self.client.post(OPENROUTER_URL)
             .headers(headers)
             .json(&payload)
             .send()
             .await
-            .map_err(|e| format!("Request Failed: {}", e))?;
+            .map_err(|e| {
+                if e.is_timeout() {
+                    AiError::Timeout(e.to_string())
+                } else {
+                    AiError::ProviderUnavailable { status: 0, message: format!("Request Failed: {}", e) }
+                }
+            })?;
 
         let status = res.status();
         if !status.is_success() {
+            let retry_after = parse_retry_after(res.headers());
             let error_text = res.text().await.unwrap_or_default();
-            return Err(format!("API Error {}: {}", status, error_text));
+            return Err(AiError::from_provider_response(model, status, retry_after, &error_text));
         }
 
-        let body: ChatResponse = res.json().await.map_err(|e| format!("JSON Parse Error: {}", e))?;
+        let body: ChatResponse = res.json().await
+            .map_err(|e| AiError::InvalidResponse(format!("JSON Parse Error: {}", e)))?;
         
-        let content = body.choices.first()
-            .ok_or("No response choices")?
-            .message.content.clone()
-            .ok_or("AI returned no content".to_string())?;
+        let choice = body.choices.first()
+            .ok_or_else(|| AiError::InvalidResponse("No response choices".to_string()))?;
+
+        if choice.finish_reason.as_deref() == Some("content_filter") {
+            return Err(AiError::ContentFiltered(format!("Completion from {} was blocked", body.model)));
+        }
+
+        let content = choice.message.content.clone().filter(|c| !c.trim().is_empty());
+        let tool_calls = choice.message.tool_calls.clone().unwrap_or_default();
+        if content.is_none() && tool_calls.is_empty() {
+            return Err(AiError::InvalidResponse("AI returned no content".to_string()));
+        }
 
         // Capture Usage
         let usage = body.usage.unwrap_or(UsageStats { 
@@ -202,7 +692,7 @@ impl AiService {
         });
 
         // 🔥 Return Content, Usage, AND Actual Model Name
-        Ok((content, usage, body.model))
+        Ok(ChatCompletion { content, tool_calls, usage, model: body.model })
     }
 
     fn clean_json_markdown(&self, input: &str) -> String {
@@ -224,4 +714,17 @@ impl AiService {
         }
         output
     }
-}
\ No newline at end of file
+}
+
+/// `Retry-After` header: seconds ("30") or HTTP-date
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let raw = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = raw.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(raw).ok()?;
+    let wait = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    wait.to_std().ok()
+}
@@ -1,8 +1,22 @@
+use serde::Serialize;
 use sqlx::PgPool;
 use crate::models::UserRole;
 use crate::services::ai::UsageStats;
+use crate::services::model_catalog;
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Serialize)]
+pub struct CostEstimate {
+    pub model: String,
+    pub input_tokens: usize,
+    pub output_tokens_min: usize,
+    pub output_tokens_max: usize,
+    pub cost_usd_min: f64,
+    pub cost_usd_max: f64,
+    pub credits_min: i32,
+    pub credits_max: i32,
+}
+
 pub struct BillingService {
     pool: PgPool,
 }
@@ -12,6 +26,56 @@ impl BillingService {
         Self { pool }
     }
 
+    /// (Input USD, Output USD)
+    /// Formula: (Tokens / 1,000,000) * Price
+    pub fn cost_usd(model: &str, input_tokens: usize, output_tokens: usize) -> (f64, f64) {
+        let (input_price_per_m, output_price_per_m) = model_catalog::price_for(model);
+        let input_cost_usd = (input_tokens as f64 / 1_000_000.0) * input_price_per_m;
+        let output_cost_usd = (output_tokens as f64 / 1_000_000.0) * output_price_per_m;
+        (input_cost_usd, output_cost_usd)
+    }
+
+    /// USD Cost -> Credits (Role အလိုက်)
+    /// Admin/Team: 0 | Users: (Cost * 1.2) / 0.01, minimum 1 credit
+    pub fn credits_for(role: &UserRole, total_cost_usd: f64) -> i32 {
+        if matches!(role, UserRole::SuperAdmin | UserRole::Admin | UserRole::Team) {
+            return 0;
+        }
+
+        // Profit Margin: 20% (x 1.2)
+        // Credit Rate: 1 Credit = $0.01
+        let credits = ((total_cost_usd * 1.2) / 0.01).ceil() as i32;
+
+        // Ensure at least 1 credit is deducted for paying users (Micro-transaction safety)
+        credits.max(1)
+    }
+
+    /// 🔮 Preflight Estimate: deduct_credits နဲ့ Formula အတူတူ၊ DB မထိဘူး
+    pub fn estimate(
+        role: &UserRole,
+        model: &str,
+        input_tokens: usize,
+        output_tokens: (usize, usize),
+    ) -> CostEstimate {
+        let (output_min, output_max) = output_tokens;
+        let (in_usd, out_min_usd) = Self::cost_usd(model, input_tokens, output_min);
+        let (_, out_max_usd) = Self::cost_usd(model, input_tokens, output_max);
+
+        let cost_usd_min = in_usd + out_min_usd;
+        let cost_usd_max = in_usd + out_max_usd;
+
+        CostEstimate {
+            model: model.to_string(),
+            input_tokens,
+            output_tokens_min: output_min,
+            output_tokens_max: output_max,
+            cost_usd_min,
+            cost_usd_max,
+            credits_min: Self::credits_for(role, cost_usd_min),
+            credits_max: Self::credits_for(role, cost_usd_max),
+        }
+    }
+
     /// Check if user has enough credits
     pub async fn has_sufficient_credits(&self, user_id: Uuid) -> Result<bool, sqlx::Error> {
         let rec = sqlx::query!(
@@ -37,24 +101,75 @@ impl BillingService {
         model: &str, // Real model name from API
         action: &str,
         usage: UsageStats,
+        prompt_version: Option<&str>,
     ) -> Result<(), sqlx::Error> {
-        
-        // --- 💰 PRICING CONFIGURATION (Per 1 Million Tokens) ---
-        // 1. Model ပေါ်မူတည်ပြီး စျေးနှုန်းခွဲခြားခြင်း
-        let (input_price_per_m, output_price_per_m) = if model.contains("max") || model.contains("gpt-4") || model.contains("opus") {
-            // High-End Models (Smart)
-            // Example: $1.25 Input / $10.00 Output
-            (1.25, 10.00) 
+        self.charge(user_id, model, action, usage, prompt_version, None).await
+    }
+
+    /// ⚡ Cache Hit: LLM မခေါ်ရလို့ Original Cost ရဲ့ `rate` ပုံ (CACHE_HIT_BILLING_RATE) ပဲ ဖြတ်မယ်
+    pub async fn deduct_cached_credits(
+        &self,
+        user_id: Uuid,
+        model: &str,
+        action: &str,
+        usage: UsageStats,
+        prompt_version: Option<&str>,
+        rate: f64,
+    ) -> Result<(), sqlx::Error> {
+        self.charge(user_id, model, action, usage, prompt_version, Some(rate)).await
+    }
+
+    /// 🛠️ Agent Tool Call Fee (Tool တစ်ခါခေါ်တိုင်း Flat Credits, Admin/Team Free)
+    pub async fn charge_tool_call(&self, user_id: Uuid, tool: &str, credits: i32) -> Result<(), sqlx::Error> {
+        let user_role = sqlx::query!(
+            "SELECT role as \"role: UserRole\" FROM users WHERE id = $1",
+            user_id
+        ).fetch_one(&self.pool).await?.role;
+
+        let deduction = if matches!(user_role, UserRole::SuperAdmin | UserRole::Admin | UserRole::Team) {
+            0
         } else {
-            // Fast/Mini Models (Standard)
-            // Example: $0.25 Input / $2.00 Output
-            (0.25, 2.00) 
+            credits.max(0)
         };
 
-        // 2. 🔥 SEPARATE CALCULATION (Input vs Output)
-        // Formula: (Tokens / 1,000,000) * Price
-        let input_cost_usd = (usage.prompt_tokens as f64 / 1_000_000.0) * input_price_per_m;
-        let output_cost_usd = (usage.completion_tokens as f64 / 1_000_000.0) * output_price_per_m;
+        let mut tx = self.pool.begin().await?;
+        if deduction > 0 {
+            sqlx::query!("UPDATE users SET credits = credits - $1 WHERE id = $2", deduction, user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        sqlx::query!(
+            "INSERT INTO usage_logs (user_id, action, model_used, input_tokens, output_tokens, cost_usd)
+             VALUES ($1, $2, 'tool', 0, 0, 0)",
+            user_id,
+            format!("tool_call:{}", tool)
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        println!("🛠️ Billing [tool:{}]: Deducted {} credits", tool, deduction);
+        Ok(())
+    }
+
+    /// `cache_hit_rate`: None = Fresh LLM call, Some(rate) = Served from Response Cache
+    async fn charge(
+        &self,
+        user_id: Uuid,
+        model: &str,
+        action: &str,
+        usage: UsageStats,
+        prompt_version: Option<&str>,
+        cache_hit_rate: Option<f64>,
+    ) -> Result<(), sqlx::Error> {
+        
+        // --- 💰 PRICING (Per 1 Million Tokens, Catalog Pricing Table) ---
+        // 1-2. 🔥 SEPARATE CALCULATION (Input vs Output)
+        let (input_cost_usd, output_cost_usd) = Self::cost_usd(
+            model,
+            usage.prompt_tokens.max(0) as usize,
+            usage.completion_tokens.max(0) as usize,
+        );
 
         // Total Cost (User ကုန်ကျစရိတ် အရင်း)
         let total_cost_usd = input_cost_usd + output_cost_usd;
@@ -66,22 +181,13 @@ impl BillingService {
         ).fetch_one(&self.pool).await?.role;
 
         // 4. Determine Deduct Amount (With Profit Margin)
-        // Admin/Team: 0
-        // Users: (Cost * 1.2) / 0.01
-        let credits_to_deduct = if matches!(user_role, UserRole::SuperAdmin | UserRole::Admin | UserRole::Team) {
-            0 
-        } else {
-            // Profit Margin: 20% (x 1.2)
-            // Credit Rate: 1 Credit = $0.01
-            ((total_cost_usd * 1.2) / 0.01).ceil() as i32
-        };
-        
-        // Ensure at least 1 credit is deducted for paying users (Micro-transaction safety)
-        let final_deduction = if credits_to_deduct < 1 && !matches!(user_role, UserRole::SuperAdmin | UserRole::Admin | UserRole::Team) { 
-            1 
-        } else { 
-            credits_to_deduct 
+        // Cache Hit: Provider ကို ပိုက်ဆံမပေးရဘူး (Internal Cost = 0)၊ User ကို Reduced Rate နဲ့ ဖြတ်မယ်
+        let (final_deduction, internal_cost_usd) = match cache_hit_rate {
+            None => (Self::credits_for(&user_role, total_cost_usd), total_cost_usd),
+            Some(rate) if rate <= 0.0 => (0, 0.0),
+            Some(rate) => (Self::credits_for(&user_role, total_cost_usd * rate), 0.0),
         };
+        let cached = cache_hit_rate.is_some();
 
         // 5. DB Transaction
         let mut tx = self.pool.begin().await?;
@@ -99,21 +205,25 @@ impl BillingService {
 
         // 🔥 LOG EVERYTHING: Track exact USD cost for Audit
         sqlx::query!(
-            "INSERT INTO usage_logs (user_id, action, model_used, input_tokens, output_tokens, cost_usd)
-             VALUES ($1, $2, $3, $4, $5, $6::FLOAT8)",
+            "INSERT INTO usage_logs (user_id, action, model_used, input_tokens, output_tokens, cost_usd, cached, prompt_version)
+             VALUES ($1, $2, $3, $4, $5, $6::FLOAT8, $7, $8)",
             user_id,
             action,
             model,
             usage.prompt_tokens,
             usage.completion_tokens,
-            total_cost_usd // Storing the Real Internal Cost
+            internal_cost_usd, // Storing the Real Internal Cost
+            cached,
+            prompt_version
         )
         .execute(&mut *tx)
         .await?;
 
         tx.commit().await?;
 
-        if final_deduction == 0 {
+        if cached {
+             println!("⚡ Billing [{}]: Cache hit -> Deducted {} credits (Original Cost: ${:.6})", model, final_deduction, total_cost_usd);
+        } else if final_deduction == 0 {
              println!("👑 Billing [{}]: Free usage for Team/Admin (Internal Cost: ${:.6})", model, total_cost_usd);
         } else {
              println!("💰 Billing [{}]: Cost ${:.6} (In: ${:.6}, Out: ${:.6}) -> Deducted {} credits", 
@@ -2,4 +2,11 @@ pub mod ai;
 pub mod knowledge_store;
 pub mod scheduler;
 pub mod scraper;
-pub mod billing;
\ No newline at end of file
+pub mod billing;
+pub mod model_catalog;
+pub mod resilience;
+pub mod token_budget;
+pub mod response_cache;
+pub mod prompt_registry;
+pub mod agent_loop;
+pub mod util;
@@ -0,0 +1,151 @@
+use crate::models::UserRole;
+use serde::Serialize;
+
+// --- 📚 MODEL CATALOG (Pricing Table + Tier Allowlists) ---
+// Client က `model` ကို ရွေးလာရင် ဒီ Table ထဲမှာ ရှိမှ လက်ခံမယ်။
+// Prices are USD per 1 Million tokens (OpenRouter list price).
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTier {
+    Standard,
+    Premium,
+    Flagship,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub tier: ModelTier,
+    pub input_price_per_m: f64,
+    pub output_price_per_m: f64,
+    pub context_window: usize, // Max tokens (input + output)
+}
+
+/// Catalog ထဲမရှိတဲ့ Model အတွက် Safe Default
+pub const DEFAULT_CONTEXT_WINDOW: usize = 128_000;
+
+pub const MODEL_CATALOG: &[ModelInfo] = &[
+    ModelInfo {
+        id: "openai/gpt-5.1-codex-mini",
+        label: "GPT-5.1 Codex Mini (Fast)",
+        tier: ModelTier::Standard,
+        input_price_per_m: 0.25,
+        output_price_per_m: 2.00,
+        context_window: 400_000,
+    },
+    ModelInfo {
+        id: "deepseek/deepseek-chat-v3.1",
+        label: "DeepSeek V3.1",
+        tier: ModelTier::Standard,
+        input_price_per_m: 0.27,
+        output_price_per_m: 1.10,
+        context_window: 163_840,
+    },
+    ModelInfo {
+        id: "openai/gpt-5.1-codex-max",
+        label: "GPT-5.1 Codex Max (Thinking)",
+        tier: ModelTier::Premium,
+        input_price_per_m: 1.25,
+        output_price_per_m: 10.00,
+        context_window: 400_000,
+    },
+    ModelInfo {
+        id: "google/gemini-2.5-pro",
+        label: "Gemini 2.5 Pro",
+        tier: ModelTier::Premium,
+        input_price_per_m: 1.25,
+        output_price_per_m: 10.00,
+        context_window: 1_048_576,
+    },
+    ModelInfo {
+        id: "anthropic/claude-sonnet-4.5",
+        label: "Claude Sonnet 4.5",
+        tier: ModelTier::Flagship,
+        input_price_per_m: 3.00,
+        output_price_per_m: 15.00,
+        context_window: 200_000,
+    },
+    ModelInfo {
+        id: "anthropic/claude-opus-4.1",
+        label: "Claude Opus 4.1",
+        tier: ModelTier::Flagship,
+        input_price_per_m: 15.00,
+        output_price_per_m: 75.00,
+        context_window: 200_000,
+    },
+];
+
+/// Catalog ထဲက Model ကို ID နဲ့ ရှာမယ်
+pub fn find(model_id: &str) -> Option<&'static ModelInfo> {
+    MODEL_CATALOG.iter().find(|m| m.id == model_id)
+}
+
+/// Role တစ်ခုချင်းစီ ရွေးခွင့်ရှိတဲ့ အမြင့်ဆုံး Tier
+/// Free: Standard only | Pro: up to Premium | Team/Admin: Everything
+pub fn max_tier_for(role: &UserRole) -> ModelTier {
+    match role {
+        UserRole::SuperAdmin | UserRole::Admin | UserRole::Team => ModelTier::Flagship,
+        UserRole::Pro => ModelTier::Premium,
+        UserRole::Free => ModelTier::Standard,
+    }
+}
+
+pub fn is_allowed(role: &UserRole, model: &ModelInfo) -> bool {
+    model.tier <= max_tier_for(role)
+}
+
+/// Client က ရွေးလာတဲ့ Model ကို စစ်ဆေးမယ် (Pricing Table + Role Allowlist)
+/// Error ကို Handler က 400/403 အဖြစ် ပြန်ပေးမယ်။
+pub fn validate_selection(role: &UserRole, model_id: &str) -> Result<&'static ModelInfo, ModelSelectionError> {
+    let model = find(model_id).ok_or_else(|| ModelSelectionError::Unknown(model_id.to_string()))?;
+
+    if !is_allowed(role, model) {
+        return Err(ModelSelectionError::NotAllowed {
+            model: model.id.to_string(),
+            role: role.clone(),
+        });
+    }
+
+    Ok(model)
+}
+
+/// USD price per 1M tokens (input, output).
+/// Catalog ထဲမှာ မရှိတဲ့ Model (e.g. MODEL_THINKING env override) အတွက် အရင် Heuristic ကို ဆက်သုံးမယ်။
+pub fn price_for(model_id: &str) -> (f64, f64) {
+    if let Some(model) = find(model_id) {
+        return (model.input_price_per_m, model.output_price_per_m);
+    }
+
+    if model_id.contains("max") || model_id.contains("gpt-4") || model_id.contains("opus") {
+        // High-End Models (Smart)
+        (1.25, 10.00)
+    } else {
+        // Fast/Mini Models (Standard)
+        (0.25, 2.00)
+    }
+}
+
+pub fn context_window_for(model_id: &str) -> usize {
+    find(model_id).map(|m| m.context_window).unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
+#[derive(Debug)]
+pub enum ModelSelectionError {
+    Unknown(String),
+    NotAllowed { model: String, role: UserRole },
+}
+
+impl std::fmt::Display for ModelSelectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelSelectionError::Unknown(id) => {
+                write!(f, "Unknown model '{}'. See GET /api/models for available models.", id)
+            }
+            ModelSelectionError::NotAllowed { model, role } => {
+                write!(f, "Model '{}' is not available on the {:?} plan. Please upgrade.", model, role)
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use crate::prompts::{self, PromptVars};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use uuid::Uuid;
+
+// --- 📝 PROMPT REGISTRY (Versioned Templates + Hot Reload) ---
+// Layout: `$PROMPTS_DIR/<name>/<version>.md` (e.g. prompts/architect/v2.md)
+// Active version = Admin က Pin ထားတာ (prompt_pins table) သို့မဟုတ် အသစ်ဆုံး Version
+// Disk ပေါ်က File ပြင်ရင် Restart မလိုဘဲ PROMPT_RELOAD_SECS အတွင်း အသက်ဝင်မယ်။
+
+#[derive(Debug, Clone)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub version: String,
+    pub body: String,
+    pub source: String, // File path or "builtin"
+}
+
+/// Render ပြီးသား Prompt + Usage Log မှာ မှတ်မယ့် Version ID ("architect@v2")
+#[derive(Debug, Clone)]
+pub struct RenderedPrompt {
+    pub version: String,
+    pub text: String,
+}
+
+/// Admin Endpoint Response
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptStatus {
+    pub name: String,
+    pub active_version: String,
+    pub latest_version: String,
+    pub pinned: bool,
+    pub source: String,
+    pub versions: Vec<String>,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    templates: HashMap<String, Vec<PromptTemplate>>, // Oldest -> Newest
+    pins: HashMap<String, String>,
+    fingerprint: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+pub struct PromptRegistry {
+    dir: PathBuf,
+    pool: PgPool,
+    state: RwLock<RegistryState>,
+}
+
+impl PromptRegistry {
+    /// ENV: PROMPTS_DIR (default ./prompts)
+    pub async fn load(pool: PgPool) -> Arc<Self> {
+        let dir = PathBuf::from(env::var("PROMPTS_DIR").unwrap_or("./prompts".to_string()));
+        let registry = Arc::new(Self { dir, pool, state: RwLock::new(RegistryState::default()) });
+
+        registry.reload_templates();
+        registry.reload_pins().await;
+
+        for status in registry.status() {
+            println!("📝 Prompt [{}]: active {} ({} version(s), source: {})",
+                status.name, status.active_version, status.versions.len(), status.source);
+        }
+        registry
+    }
+
+    /// ENV: PROMPT_RELOAD_SECS (default 10, 0 = Hot Reload ပိတ်မယ်)
+    pub fn start_hot_reload(self: &Arc<Self>) {
+        let secs: u64 = env::var("PROMPT_RELOAD_SECS").ok().and_then(|v| v.parse().ok()).unwrap_or(10);
+        if secs == 0 {
+            return;
+        }
+
+        let registry = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(secs));
+            loop {
+                interval.tick().await;
+                // Disk: File ပြောင်းမှ ပြန်ဖတ်မယ် | Pins: Instance အများကြား ညီအောင် အမြဲ ပြန်ဖတ်မယ်
+                if registry.fingerprint_changed() {
+                    println!("🔄 Prompt templates changed on disk. Reloading...");
+                    registry.reload_templates();
+                }
+                registry.reload_pins().await;
+            }
+        });
+    }
+
+    /// Active Template ကို Variables နဲ့ Render လုပ်မယ်
+    pub fn render(&self, name: &str, vars: &PromptVars) -> RenderedPrompt {
+        match self.active(name) {
+            Some(template) => RenderedPrompt {
+                version: format!("{}@{}", template.name, template.version),
+                text: prompts::render(&template.body, vars),
+            },
+            None => {
+                println!("⚠️ Prompt '{}' not found in registry", name);
+                RenderedPrompt { version: format!("{}@missing", name), text: String::new() }
+            }
+        }
+    }
+
+    /// "architect@v2" (Response Cache Key + Usage Log)
+    pub fn active_version(&self, name: &str) -> String {
+        match self.active(name) {
+            Some(template) => format!("{}@{}", template.name, template.version),
+            None => format!("{}@missing", name),
+        }
+    }
+
+    fn active(&self, name: &str) -> Option<PromptTemplate> {
+        let state = self.state.read().unwrap();
+        let index = self.active_index(&state, name)?;
+        state.templates.get(name)?.get(index).cloned()
+    }
+
+    pub fn status(&self) -> Vec<PromptStatus> {
+        let state = self.state.read().unwrap();
+        let mut statuses: Vec<PromptStatus> = state
+            .templates
+            .iter()
+            .filter_map(|(name, versions)| {
+                let latest = versions.last()?;
+                let pinned = state
+                    .pins
+                    .get(name)
+                    .and_then(|pin| versions.iter().find(|t| &t.version == pin));
+                let active = pinned.unwrap_or(latest);
+                Some(PromptStatus {
+                    name: name.clone(),
+                    active_version: active.version.clone(),
+                    latest_version: latest.version.clone(),
+                    pinned: pinned.is_some(),
+                    source: active.source.clone(),
+                    versions: versions.iter().map(|t| t.version.clone()).collect(),
+                })
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// Rollback / Pin
+    /// - `version: None` -> လက်ရှိ Active ရဲ့ အရင် Version
+    /// - `version: Some("latest")` -> Pin ဖြုတ်ပြီး အသစ်ဆုံးကို ပြန်လိုက်မယ်
+    /// - `version: Some("v1")` -> အဲ့ Version ကို Pin မယ်
+    pub async fn rollback(&self, name: &str, version: Option<&str>, admin_id: Uuid) -> Result<PromptStatus, String> {
+        let target = {
+            let state = self.state.read().unwrap();
+            let versions = state.templates.get(name).ok_or(format!("Unknown prompt '{}'", name))?;
+            let active = self.active_index(&state, name).unwrap_or(0);
+
+            match version {
+                Some("latest") => None,
+                Some(v) => {
+                    if !versions.iter().any(|t| t.version == v) {
+                        return Err(format!("Prompt '{}' has no version '{}'", name, v));
+                    }
+                    Some(v.to_string())
+                }
+                None if active == 0 => {
+                    return Err(format!("Prompt '{}' is already on its oldest version", name));
+                }
+                None => Some(versions[active - 1].version.clone()),
+            }
+        };
+
+        match &target {
+            Some(version) => {
+                sqlx::query!(
+                    "INSERT INTO prompt_pins (name, version, pinned_by) VALUES ($1, $2, $3)
+                     ON CONFLICT (name) DO UPDATE SET version = EXCLUDED.version, pinned_by = EXCLUDED.pinned_by, pinned_at = NOW()",
+                    name,
+                    version,
+                    admin_id
+                )
+                .execute(&self.pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            }
+            None => {
+                sqlx::query!("DELETE FROM prompt_pins WHERE name = $1", name)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        {
+            let mut state = self.state.write().unwrap();
+            match &target {
+                Some(version) => state.pins.insert(name.to_string(), version.clone()),
+                None => state.pins.remove(name),
+            };
+        }
+
+        println!("⏪ Prompt [{}] now on {}", name, target.as_deref().unwrap_or("latest"));
+        self.status()
+            .into_iter()
+            .find(|s| s.name == name)
+            .ok_or(format!("Unknown prompt '{}'", name))
+    }
+
+    fn active_index(&self, state: &RegistryState, name: &str) -> Option<usize> {
+        let versions = state.templates.get(name)?;
+        let pinned = state
+            .pins
+            .get(name)
+            .and_then(|pin| versions.iter().position(|t| &t.version == pin));
+        pinned.or(versions.len().checked_sub(1))
+    }
+
+    async fn reload_pins(&self) {
+        match sqlx::query!("SELECT name, version FROM prompt_pins").fetch_all(&self.pool).await {
+            Ok(rows) => {
+                let pins = rows.into_iter().map(|r| (r.name, r.version)).collect();
+                self.state.write().unwrap().pins = pins;
+            }
+            Err(e) => println!("⚠️ Failed to load prompt pins: {}", e),
+        }
+    }
+
+    fn reload_templates(&self) {
+        let mut templates: HashMap<String, Vec<PromptTemplate>> = HashMap::new();
+
+        for (name, version, body) in scan_templates(&self.dir) {
+            templates.entry(name.clone()).or_default().push(PromptTemplate {
+                source: self.dir.join(&name).join(format!("{}.md", version)).display().to_string(),
+                name,
+                version,
+                body,
+            });
+        }
+
+        // Disk ပေါ်မှာ မရှိတဲ့ Built-in Version တွေကို ဖြည့်မယ်
+        for (name, version, body) in prompts::BUILTIN_TEMPLATES {
+            let versions = templates.entry(name.to_string()).or_default();
+            if !versions.iter().any(|t| t.version == *version) {
+                versions.push(PromptTemplate {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    body: body.to_string(),
+                    source: "builtin".to_string(),
+                });
+            }
+        }
+
+        for versions in templates.values_mut() {
+            versions.sort_by_key(|t| version_key(&t.version));
+        }
+
+        let mut state = self.state.write().unwrap();
+        state.templates = templates;
+        state.fingerprint = fingerprint(&self.dir);
+    }
+
+    fn fingerprint_changed(&self) -> bool {
+        let current = fingerprint(&self.dir);
+        self.state.read().unwrap().fingerprint != current
+    }
+}
+
+/// (name, version, body) for every `<dir>/<name>/<version>.md`
+fn scan_templates(dir: &Path) -> Vec<(String, String, String)> {
+    let mut found = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else { return found };
+
+    for entry in entries.flatten() {
+        let prompt_dir = entry.path();
+        if !prompt_dir.is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Ok(files) = fs::read_dir(&prompt_dir) else { continue };
+
+        for file in files.flatten() {
+            let path = file.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(version) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else { continue };
+            match fs::read_to_string(&path) {
+                Ok(body) => found.push((name.clone(), version, body)),
+                Err(e) => println!("⚠️ Failed to read prompt {}: {}", path.display(), e),
+            }
+        }
+    }
+    found
+}
+
+/// Template File တွေရဲ့ (Path, Modified Time) — ပြောင်းမပြောင်း စစ်ဖို့
+fn fingerprint(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut prints: Vec<(PathBuf, Option<SystemTime>)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .flat_map(|e| fs::read_dir(e.path()).into_iter().flatten().flatten())
+        .map(|f| {
+            let modified = f.metadata().and_then(|m| m.modified()).ok();
+            (f.path(), modified)
+        })
+        .collect();
+    prints.sort();
+    prints
+}
+
+/// Natural ordering: "v2" < "v10", "2024-12-01" < "2025-01-15"
+fn version_key(version: &str) -> (Vec<u64>, String) {
+    let numbers = version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty())
+        .filter_map(|part| part.parse().ok())
+        .collect();
+    (numbers, version.to_string())
+}
@@ -0,0 +1,117 @@
+use crate::services::util::env_or;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// --- 🛡️ PROVIDER RESILIENCE (Retry Backoff + Circuit Breaker) ---
+
+/// Jittered Exponential Backoff Policy
+/// delay = min(max_delay, base_delay * 2^attempt) * random(0.5..=1.0)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// ENV: LLM_MAX_ATTEMPTS (default 3), LLM_RETRY_BASE_MS (default 500), LLM_RETRY_MAX_SECS (default 30)
+    pub fn from_env() -> Self {
+        Self {
+            max_attempts: env_or("LLM_MAX_ATTEMPTS", 3).max(1),
+            base_delay: Duration::from_millis(env_or("LLM_RETRY_BASE_MS", 500)),
+            max_delay: Duration::from_secs(env_or("LLM_RETRY_MAX_SECS", 30)),
+        }
+    }
+
+    /// နောက်တစ်ကြိမ် မစမ်းခင် စောင့်ရမယ့်အချိန်
+    /// Provider က `Retry-After` ပေးလာရင် အဲ့ဒါကို လေးစားမယ်။ max_delay ထက် ကျော်နေရင် None (= Retry မလုပ်တော့ဘဲ Fallback သွားမယ်)
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if let Some(wait) = retry_after {
+            return if wait <= self.max_delay { Some(wait) } else { None };
+        }
+
+        let exp = self.base_delay.saturating_mul(2u32.saturating_pow(attempt));
+        let capped = exp.min(self.max_delay);
+        let jitter: f64 = rand::rng().random_range(0.5..=1.0);
+        Some(capped.mul_f64(jitter))
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Half-Open Probe စလွှတ်တဲ့အချိန် (Probe ပြီးမှ နောက်တစ်ခု ခွင့်ပြုမယ်)
+    half_open_in_flight: Option<Instant>,
+}
+
+/// Per provider/model Circuit Breaker
+/// Failure အဆက်မပြတ် `threshold` ကြိမ် ဖြစ်ရင် `cooldown` အတွင်း အဲ့ Model ကို မခေါ်တော့ဘူး (Fallback ကို တန်းသွားမယ်)
+/// Cooldown ပြီးရင် Half-Open: Probe Request တစ်ခုတည်းကိုပဲ ပြန်စမ်းခွင့်ပေးမယ် (ပြီးတဲ့အထိ ကျန်တာ Fallback)။
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    states: Mutex<HashMap<String, BreakerState>>,
+}
+
+impl CircuitBreaker {
+    /// ENV: CIRCUIT_FAILURE_THRESHOLD (default 5), CIRCUIT_COOLDOWN_SECS (default 60)
+    pub fn from_env() -> Self {
+        Self {
+            threshold: env_or("CIRCUIT_FAILURE_THRESHOLD", 5).max(1),
+            cooldown: Duration::from_secs(env_or("CIRCUIT_COOLDOWN_SECS", 60)),
+            states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Request ပို့ခွင့်ရှိမရှိ (Closed / Half-Open Probe = true, Open / Probe သွားနေတုန်း = false)
+    pub fn allow(&self, key: &str) -> bool {
+        let mut states = self.states.lock().unwrap();
+        let state = states.entry(key.to_string()).or_default();
+
+        match state.opened_at {
+            None => true,
+            Some(opened) if opened.elapsed() >= self.cooldown => {
+                // Probe က ပြန်မလာဘဲ ပျောက်သွားရင် (Request Cancel) Cooldown တစ်ခါစာ ကြာမှ နောက်တစ်ခု ခွင့်ပြုမယ်
+                if state.half_open_in_flight.is_some_and(|started| started.elapsed() < self.cooldown) {
+                    return false;
+                }
+                // Half-Open: Trial request တစ်ခုတည်း ခွင့်ပြုမယ်၊ Fail ရင် ချက်ချင်း ပြန်ပိတ်မယ်
+                state.half_open_in_flight = Some(Instant::now());
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    pub fn record_success(&self, key: &str) {
+        let mut states = self.states.lock().unwrap();
+        states.remove(key);
+    }
+
+    /// Retry မလုပ်ရတဲ့ Error (Provider က ဖြေတော့ ဖြေတယ်) -> Probe ပြီးပြီ၊ Breaker State မပြောင်းဘူး
+    pub fn release(&self, key: &str) {
+        let mut states = self.states.lock().unwrap();
+        if let Some(state) = states.get_mut(key) {
+            state.half_open_in_flight = None;
+        }
+    }
+
+    pub fn record_failure(&self, key: &str) {
+        let mut states = self.states.lock().unwrap();
+        let state = states.entry(key.to_string()).or_default();
+        state.consecutive_failures += 1;
+
+        if state.half_open_in_flight.take().is_some() {
+            println!("🔌 Circuit re-OPENED for [{}]: half-open probe failed", key);
+            state.opened_at = Some(Instant::now());
+            return;
+        }
+        if state.consecutive_failures >= self.threshold && state.opened_at.is_none() {
+            println!("🔌 Circuit OPEN for [{}] after {} failures (cooldown {:?})", key, state.consecutive_failures, self.cooldown);
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::models::User;
+use crate::services::ai::UsageStats;
+use crate::services::util::env_or;
+use moka::future::Cache;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+// --- ⚡ RESPONSE CACHE (Content-Addressed) ---
+// CI က မပြောင်းတဲ့ File တွေကို ထပ်ခါထပ်ခါ Audit လုပ်တာ၊ မေးခွန်းအတူတူ ပြန်မေးတာတွေအတွက်
+// LLM ကို ထပ်မခေါ်ဘဲ အရင်အဖြေကို ပြန်ပေးမယ်။
+// Key = SHA-256(kind, model, prompt version, inputs) | Scope = Team (ရှိရင်) သို့မဟုတ် User
+
+/// Cache ထဲ သိမ်းထားတဲ့ AI အဖြေ (Billing အတွက် Original Usage ပါ သိမ်းမယ်)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub content: Value,
+    pub model: String,
+    pub usage: UsageStats,
+    pub context_report: Value,
+    #[serde(default)]
+    pub prompt_version: Option<String>,
+}
+
+/// Storage Backend (ENV: RESPONSE_CACHE_BACKEND = memory | postgres | off)
+pub enum CacheBackend {
+    /// Process-local (moka). Server restart ရင် ပျောက်မယ်
+    Memory(Cache<String, CachedResponse>),
+    /// `response_cache` table. Instance အများကြား Share လို့ရမယ်
+    Postgres(PgPool),
+    Disabled,
+}
+
+pub struct ResponseCache {
+    backend: CacheBackend,
+    ttl: Duration,
+    hit_billing_rate: f64,
+}
+
+/// Request တစ်ခုရဲ့ Cache Identity
+#[derive(Debug, Clone)]
+pub struct CacheKey {
+    pub scope: String,
+    pub digest: String,
+}
+
+impl CacheKey {
+    /// `kind` = "plan" | "audit", `inputs` = Request content (order matters)
+    pub fn new(user: &User, kind: &str, model: &str, prompt_version: &str, inputs: &[Option<&str>]) -> Self {
+        let mut hasher = Sha256::new();
+        for part in [Some(kind), Some(model), Some(prompt_version)].iter().chain(inputs) {
+            // Length-prefixed so ("ab", "c") and ("a", "bc") never collide; None != ""
+            match part {
+                Some(text) => {
+                    hasher.update([1u8]);
+                    hasher.update((text.len() as u64).to_le_bytes());
+                    hasher.update(text.as_bytes());
+                }
+                None => hasher.update([0u8]),
+            }
+        }
+
+        let digest = hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        Self { scope: scope_for(user), digest }
+    }
+
+    fn storage_key(&self) -> String {
+        format!("{}:{}", self.scope, self.digest)
+    }
+}
+
+/// Team ထဲက Member တွေ အဖြေကို Share မယ်၊ Team မရှိရင် User တစ်ယောက်တည်း
+fn scope_for(user: &User) -> String {
+    match user.team_id {
+        Some(team_id) => format!("team:{}", team_id),
+        None => format!("user:{}", user.id),
+    }
+}
+
+impl ResponseCache {
+    /// ENV: RESPONSE_CACHE_BACKEND (default memory), RESPONSE_CACHE_TTL_SECS (default 3600),
+    /// RESPONSE_CACHE_MAX_ENTRIES (default 10000, memory only), CACHE_HIT_BILLING_RATE (default 0.1)
+    pub fn from_env(pool: PgPool) -> Self {
+        let ttl = Duration::from_secs(env_or("RESPONSE_CACHE_TTL_SECS", 3600));
+        let backend_name = env::var("RESPONSE_CACHE_BACKEND").unwrap_or("memory".to_string());
+
+        let backend = match backend_name.to_lowercase().as_str() {
+            "postgres" => CacheBackend::Postgres(pool),
+            "off" | "none" | "disabled" => CacheBackend::Disabled,
+            _ => CacheBackend::Memory(
+                Cache::builder()
+                    .max_capacity(env_or("RESPONSE_CACHE_MAX_ENTRIES", 10_000))
+                    .time_to_live(ttl)
+                    .build(),
+            ),
+        };
+
+        let cache = Self {
+            backend,
+            ttl,
+            hit_billing_rate: env_or("CACHE_HIT_BILLING_RATE", 0.1_f64).clamp(0.0, 1.0),
+        };
+        println!("⚡ Response Cache: {} (TTL {:?}, hit rate x{})", cache.backend_name(), cache.ttl, cache.hit_billing_rate);
+        cache
+    }
+
+    fn backend_name(&self) -> &'static str {
+        match self.backend {
+            CacheBackend::Memory(_) => "memory",
+            CacheBackend::Postgres(_) => "postgres",
+            CacheBackend::Disabled => "disabled",
+        }
+    }
+
+    /// Cache Hit ဖြစ်ရင် Original Cost ရဲ့ ဘယ်နှစ်ပုံ ဖြတ်မလဲ (0.0 = Free, 1.0 = Full price)
+    pub fn hit_billing_rate(&self) -> f64 {
+        self.hit_billing_rate
+    }
+
+    /// Cache Error တွေက Request ကို မပျက်စေရဘူး (Miss အဖြစ်ပဲ သတ်မှတ်မယ်)
+    pub async fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+        match &self.backend {
+            CacheBackend::Memory(cache) => cache.get(&key.storage_key()).await,
+            CacheBackend::Postgres(pool) => {
+                let row = sqlx::query!(
+                    "SELECT response FROM response_cache WHERE cache_key = $1 AND expires_at > NOW()",
+                    key.storage_key()
+                )
+                .fetch_optional(pool)
+                .await;
+
+                match row {
+                    Ok(Some(row)) => serde_json::from_str(&row.response).ok(),
+                    Ok(None) => None,
+                    Err(e) => {
+                        println!("⚠️ Response cache read failed: {}", e);
+                        None
+                    }
+                }
+            }
+            CacheBackend::Disabled => None,
+        }
+    }
+
+    pub async fn put(&self, key: &CacheKey, response: CachedResponse) {
+        match &self.backend {
+            CacheBackend::Memory(cache) => cache.insert(key.storage_key(), response).await,
+            CacheBackend::Postgres(pool) => {
+                let Ok(serialized) = serde_json::to_string(&response) else { return };
+
+                // Expired rows ကို ဒီမှာပဲ တစ်ခါတည်း ရှင်းမယ်
+                let _ = sqlx::query!("DELETE FROM response_cache WHERE expires_at <= NOW()")
+                    .execute(pool)
+                    .await;
+
+                let result = sqlx::query!(
+                    "INSERT INTO response_cache (cache_key, scope, response, expires_at)
+                     VALUES ($1, $2, $3, NOW() + ($4::FLOAT8 * INTERVAL '1 second'))
+                     ON CONFLICT (cache_key) DO UPDATE
+                     SET response = EXCLUDED.response, expires_at = EXCLUDED.expires_at, created_at = NOW()",
+                    key.storage_key(),
+                    key.scope,
+                    serialized,
+                    self.ttl.as_secs_f64()
+                )
+                .execute(pool)
+                .await;
+
+                if let Err(e) = result {
+                    println!("⚠️ Response cache write failed: {}", e);
+                }
+            }
+            CacheBackend::Disabled => {}
+        }
+    }
+}
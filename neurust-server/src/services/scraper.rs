@@ -1,8 +1,12 @@
 use crate::services::knowledge_store::KnowledgeStore;
-use reqwest::Client;
+use reqwest::{redirect, Client, Url};
 use sqlx::PgPool;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::time::Duration;
 
+/// Agent Browsing မှာ Redirect ဘယ်နှစ်ခါ လိုက်မလဲ
+const MAX_REDIRECTS: usize = 5;
+
 pub struct ScraperService {
     client: Client,
     store: KnowledgeStore,
@@ -41,14 +45,54 @@ impl ScraperService {
             .await
             .map_err(|e| format!("Failed to read text: {}", e))?;
 
-        // 🔥 HTML to Clean Text (Using html2text crate)
-        // width 80 characters နဲ့ စာစီပေးမယ်
-        let clean_text = html2text::from_read(html_content.as_bytes(), 80);
+        Ok(clean_text(&html_content))
+    }
+
+    // 🛡️ Model ရွေးတဲ့ URL (Agent `read_url`) - SSRF မဖြစ်အောင် http(s) + Public IP ပဲ ခွင့်ပြုမယ်
+    // Redirect တိုင်းကို ပြန်စစ်ပြီး စစ်ထားတဲ့ IP ကိုပဲ ချိတ်မယ် (DNS Rebinding ကာဖို့)
+    // Knowledge Store ထဲ မသိမ်းဘူး (User တစ်ယောက်ရဲ့ Agent က အားလုံးရဲ့ RAG ကို မထိအောင်)
+    pub async fn scrape_public_url(&self, url: &str) -> Result<String, String> {
+        let mut url = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let addr = resolve_public(&url).await?;
+            let host = url.host_str().unwrap_or_default().to_string();
+            let client = Client::builder()
+                .timeout(Duration::from_secs(10))
+                .user_agent("Neurust-Agent/1.0")
+                .redirect(redirect::Policy::none())
+                .resolve(&host, addr)
+                .build()
+                .map_err(|e| format!("Failed to build client: {}", e))?;
+
+            println!("🕷️ Scraping URL (agent): {}", url);
+            let response = client
+                .get(url.clone())
+                .send()
+                .await
+                .map_err(|e| format!("Failed to connect: {}", e))?;
 
-        // စာအရမ်းရှည်ရင် AI Token ပြည့်သွားနိုင်လို့ အလုံးရေ ၈၀၀၀ လောက်ပဲ ယူမယ်
-        let truncated_text: String = clean_text.chars().take(8000).collect();
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|l| l.to_str().ok())
+                    .ok_or_else(|| format!("Error: HTTP {} without Location", response.status()))?;
+                url = url.join(location).map_err(|e| format!("Invalid redirect: {}", e))?;
+                continue;
+            }
+            if !response.status().is_success() {
+                return Err(format!("Error: HTTP {}", response.status()));
+            }
 
-        Ok(truncated_text)
+            let html_content = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read text: {}", e))?;
+            return Ok(clean_text(&html_content));
+        }
+
+        Err(format!("Too many redirects (max {})", MAX_REDIRECTS))
     }
 
     // URL ကိုဖတ်မယ်၊ ပြီးရင် Database ထဲသိမ်းမယ်
@@ -66,4 +110,71 @@ impl ScraperService {
         println!("💾 Knowledge stored for topic: {}", topic);
         Ok(content)
     }
-}
\ No newline at end of file
+}
+
+// 🔥 HTML to Clean Text (Using html2text crate)
+// width 80 characters နဲ့ စာစီပေးမယ်၊ စာအရမ်းရှည်ရင် AI Token ပြည့်သွားနိုင်လို့ အလုံးရေ ၈၀၀၀ လောက်ပဲ ယူမယ်
+fn clean_text(html: &str) -> String {
+    html2text::from_read(html.as_bytes(), 80).chars().take(8000).collect()
+}
+
+/// http(s) URL ရဲ့ Host ကို Resolve လုပ်ပြီး Address အားလုံး Public ဖြစ်မှ ပထမ Address ကို ပြန်ပေးမယ်
+async fn resolve_public(url: &Url) -> Result<SocketAddr, String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Blocked URL scheme '{}': only http and https are allowed", url.scheme()));
+    }
+    let host = url.host_str().ok_or("URL has no host")?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    // IPv6 Literal: `[::1]` -> `::1`
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Cannot resolve {}: {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("Cannot resolve {}", host));
+    }
+    if let Some(blocked) = addrs.iter().find(|a| !is_public(a.ip())) {
+        return Err(format!("Blocked URL: {} resolves to a non-public address ({})", host, blocked.ip()));
+    }
+    Ok(addrs[0])
+}
+
+/// Loopback / Private / Link-local (Cloud Metadata) / CGNAT / Multicast / Reserved မဟုတ်ရင် Public
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(v6),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_multicast()
+        || a == 0
+        || (a == 100 && (64..128).contains(&b)) // CGNAT 100.64.0.0/10
+        || (a == 192 && b == 0 && c == 0) // 192.0.0.0/24
+        || (a == 198 && (b == 18 || b == 19)) // Benchmarking 198.18.0.0/15
+        || a >= 240) // Reserved 240.0.0.0/4
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || (first & 0xfe00) == 0xfc00 // Unique Local fc00::/7
+        || (first & 0xffc0) == 0xfe80 // Link-local fe80::/10
+        || (first == 0x64 && ip.segments()[1] == 0xff9b) // NAT64 64:ff9b::/96
+        || (first == 0x2001 && ip.segments()[1] == 0x0db8)) // Documentation 2001:db8::/32
+}
@@ -0,0 +1,320 @@
+use crate::services::model_catalog;
+use serde::Serialize;
+
+// --- 🧮 TOKEN BUDGETING (Context Window Allocation) ---
+// Request တစ်ခုကို LLM ဆီ မပို့ခင် Context Window ထဲ ဆန့်မဆန့် တွက်မယ်။
+// မဆန့်ရင် Priority နိမ့်တဲ့ Section တွေကို အရင်ဖြတ်မယ် (RAG -> Project Context -> History)။
+
+/// Completion အတွက် အများဆုံး ချန်ထားမယ့် Token (Previously hardcoded `max_tokens: 16000`)
+pub const MAX_OUTPUT_TOKENS: usize = 16_000;
+/// Completion အတွက် အနည်းဆုံး လိုအပ်တဲ့ Token (ဒီထက်နည်းရင် Plan JSON မပြည့်စုံနိုင်)
+pub const MIN_OUTPUT_TOKENS: usize = 2_000;
+/// Estimator error margin (chat template overhead, tokenizer drift)
+const SAFETY_MARGIN_RATIO: f64 = 0.05;
+/// Per-message overhead (role markers etc.)
+const MESSAGE_OVERHEAD_TOKENS: usize = 8;
+
+const FILE_START_MARKER: &str = "\n>>>> FILE START: ";
+
+/// Tokenizer family - Model ID prefix ကို ကြည့်ပြီး ခွဲမယ်
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFamily {
+    OpenAi,
+    Anthropic,
+    Google,
+    DeepSeek,
+    Other,
+}
+
+impl ModelFamily {
+    pub fn from_model(model_id: &str) -> Self {
+        let provider = model_id.split('/').next().unwrap_or("");
+        match provider {
+            "openai" => ModelFamily::OpenAi,
+            "anthropic" => ModelFamily::Anthropic,
+            "google" => ModelFamily::Google,
+            "deepseek" => ModelFamily::DeepSeek,
+            _ => ModelFamily::Other,
+        }
+    }
+
+    /// Average ASCII characters per token (Source code heavy text)
+    fn chars_per_token(&self) -> f64 {
+        match self {
+            ModelFamily::OpenAi => 4.0,
+            ModelFamily::Google => 4.0,
+            ModelFamily::DeepSeek => 3.6,
+            ModelFamily::Anthropic => 3.5,
+            ModelFamily::Other => 3.3, // Unknown tokenizer -> be conservative
+        }
+    }
+}
+
+/// Token အရေအတွက်ကို ခန့်မှန်းမယ် (Tokenizer မပါဘဲ)
+/// ASCII: chars / chars_per_token | Non-ASCII (e.g. မြန်မာစာ, emoji): ~1 token per char
+pub fn estimate_tokens(text: &str, family: ModelFamily) -> usize {
+    let mut ascii = 0usize;
+    let mut non_ascii = 0usize;
+    for c in text.chars() {
+        if c.is_ascii() { ascii += 1; } else { non_ascii += 1; }
+    }
+    (ascii as f64 / family.chars_per_token()).ceil() as usize + non_ascii
+}
+
+/// Section အမျိုးအစား (Priority မြင့် = နောက်ဆုံးမှ ဖြတ်မယ်)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionKind {
+    System,
+    /// Project Rules (`.neurust/rules.md`) - System နဲ့ တန်းတူ၊ ဘယ်တော့မှ မဖြတ်ဘူး
+    Rules,
+    UserRequest,
+    History,
+    ProjectContext,
+    Rag,
+}
+
+impl SectionKind {
+    fn priority(&self) -> u8 {
+        match self {
+            SectionKind::System => 100,
+            SectionKind::Rules => 95,
+            SectionKind::UserRequest => 90,
+            SectionKind::History => 50,
+            SectionKind::ProjectContext => 30,
+            SectionKind::Rag => 10,
+        }
+    }
+
+    /// Required sections are never trimmed
+    fn is_required(&self) -> bool {
+        matches!(self, SectionKind::System | SectionKind::Rules | SectionKind::UserRequest)
+    }
+
+    /// History ကို ဖြတ်ရင် နောက်ဆုံး (အသစ်ဆုံး) အပိုင်းကို ထားမယ်
+    fn keeps_tail(&self) -> bool {
+        matches!(self, SectionKind::History)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub kind: SectionKind,
+    pub role: &'static str, // "system" | "user"
+    pub content: String,
+}
+
+impl Section {
+    pub fn new(kind: SectionKind, role: &'static str, content: impl Into<String>) -> Self {
+        Self { kind, role, content: content.into() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionReport {
+    pub section: SectionKind,
+    pub original_tokens: usize,
+    pub kept_tokens: usize,
+    pub trimmed: bool,
+}
+
+/// Client ဆီ ပြန်ပို့မယ့် Report (ဘာတွေ ဖြတ်လိုက်လဲ)
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetReport {
+    pub model: String,
+    pub model_family: ModelFamily,
+    pub context_window: usize,
+    pub input_tokens: usize,
+    pub max_output_tokens: usize,
+    pub sections: Vec<SectionReport>,
+    pub dropped_files: Vec<String>,
+}
+
+impl BudgetReport {
+    pub fn was_trimmed(&self) -> bool {
+        self.sections.iter().any(|s| s.trimmed)
+    }
+}
+
+/// Budget ထဲ ဆန့်အောင် ပြင်ပြီးသား Request
+#[derive(Debug, Clone)]
+pub struct BudgetedRequest {
+    pub sections: Vec<Section>,
+    pub report: BudgetReport,
+}
+
+impl BudgetedRequest {
+    /// OpenRouter `messages` array
+    pub fn messages(&self) -> Vec<serde_json::Value> {
+        self.sections
+            .iter()
+            .filter(|s| !s.content.trim().is_empty())
+            .map(|s| serde_json::json!({ "role": s.role, "content": s.content }))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    pub required_tokens: usize,
+    pub available_tokens: usize,
+}
+
+/// Sections တွေကို Model ရဲ့ Context Window ထဲ ခွဲဝေမယ်
+pub fn fit_to_budget(model: &str, mut sections: Vec<Section>) -> Result<BudgetedRequest, BudgetExceeded> {
+    let family = ModelFamily::from_model(model);
+    let window = model_catalog::context_window_for(model);
+    let usable = window - (window as f64 * SAFETY_MARGIN_RATIO) as usize;
+
+    let count = |s: &Section| estimate_tokens(&s.content, family) + MESSAGE_OVERHEAD_TOKENS;
+    let original: Vec<usize> = sections.iter().map(count).collect();
+
+    // Input budget = Window - Output reserve (Output ကို အနည်းဆုံး MIN_OUTPUT_TOKENS ချန်မယ်)
+    let input_budget = usable.saturating_sub(MIN_OUTPUT_TOKENS);
+
+    let required: usize = sections
+        .iter()
+        .zip(&original)
+        .filter(|(s, _)| s.kind.is_required())
+        .map(|(_, t)| *t)
+        .sum();
+
+    if required > input_budget {
+        return Err(BudgetExceeded { required_tokens: required, available_tokens: input_budget });
+    }
+
+    let mut dropped_files = Vec::new();
+    let mut total: usize = original.iter().sum();
+
+    // Priority နိမ့်ရာကနေ စဖြတ်မယ်
+    let mut order: Vec<usize> = (0..sections.len()).filter(|&i| !sections[i].kind.is_required()).collect();
+    order.sort_by_key(|&i| sections[i].kind.priority());
+
+    for index in order {
+        if total <= input_budget {
+            break;
+        }
+        let current = count(&sections[index]);
+        let overflow = total - input_budget;
+        let target = current.saturating_sub(overflow);
+
+        let section = &mut sections[index];
+        if section.kind == SectionKind::ProjectContext {
+            section.content = drop_file_blocks(&section.content, target, family, &mut dropped_files);
+        }
+        if count(section) > target {
+            let content_target = target.saturating_sub(MESSAGE_OVERHEAD_TOKENS);
+            section.content = truncate_to_tokens(&section.content, content_target, family, section.kind.keeps_tail());
+        }
+
+        total = total - current + count(section);
+    }
+
+    let max_output_tokens = usable.saturating_sub(total).min(MAX_OUTPUT_TOKENS);
+
+    let report = BudgetReport {
+        model: model.to_string(),
+        model_family: family,
+        context_window: window,
+        input_tokens: total,
+        max_output_tokens,
+        sections: sections
+            .iter()
+            .zip(&original)
+            .map(|(s, &orig)| {
+                let kept = count(s);
+                SectionReport { section: s.kind, original_tokens: orig, kept_tokens: kept, trimmed: kept < orig }
+            })
+            .collect(),
+        dropped_files,
+    };
+
+    Ok(BudgetedRequest { sections, report })
+}
+
+/// Project Context ထဲက File Block တွေကို အကြီးဆုံးကနေ စဖြုတ်မယ် (Header/Memory ကို ထားမယ်)
+fn drop_file_blocks(content: &str, target: usize, family: ModelFamily, dropped: &mut Vec<String>) -> String {
+    let mut blocks: Vec<&str> = Vec::new();
+    let mut rest = content;
+    let head_end = rest.find(FILE_START_MARKER).unwrap_or(rest.len());
+    let head = &rest[..head_end];
+    rest = &rest[head_end..];
+
+    while !rest.is_empty() {
+        let next = rest[1..].find(FILE_START_MARKER).map(|i| i + 1).unwrap_or(rest.len());
+        blocks.push(&rest[..next]);
+        rest = &rest[next..];
+    }
+
+    let mut sizes: Vec<(usize, usize)> = blocks.iter().enumerate().map(|(i, b)| (i, estimate_tokens(b, family))).collect();
+    sizes.sort_by_key(|&(_, size)| std::cmp::Reverse(size));
+
+    let mut total = estimate_tokens(content, family);
+    let mut removed = vec![false; blocks.len()];
+    let already_dropped = dropped.len();
+    for (index, size) in sizes {
+        if total <= target {
+            break;
+        }
+        removed[index] = true;
+        total = total.saturating_sub(size);
+        dropped.push(file_name_of(blocks[index]));
+    }
+
+    let mut output = head.to_string();
+    for (index, block) in blocks.iter().enumerate() {
+        if !removed[index] {
+            output.push_str(block);
+        }
+    }
+    let omitted = &dropped[already_dropped..];
+    if omitted.is_empty() {
+        return output;
+    }
+    output.push_str(&format!(
+        "\n--- NOTE: {} file(s) omitted to fit the context window: {} ---\n",
+        omitted.len(),
+        omitted.join(", ")
+    ));
+    output
+}
+
+fn file_name_of(block: &str) -> String {
+    block
+        .trim_start_matches(FILE_START_MARKER)
+        .split(" <<<<")
+        .next()
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Line boundary မှာ ဖြတ်ပြီး Marker ထည့်မယ်
+fn truncate_to_tokens(content: &str, target: usize, family: ModelFamily, keep_tail: bool) -> String {
+    let marker = "\n[... truncated by Neurust token budget ...]\n";
+    let budget = target.saturating_sub(estimate_tokens(marker, family));
+    if budget == 0 {
+        return String::new();
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+    let mut used = 0usize;
+
+    let iter: Box<dyn Iterator<Item = &&str>> = if keep_tail { Box::new(lines.iter().rev()) } else { Box::new(lines.iter()) };
+    for line in iter {
+        let cost = estimate_tokens(line, family) + 1;
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        kept.push(line);
+    }
+
+    if keep_tail {
+        kept.reverse();
+        format!("{}{}", marker.trim_start(), kept.join("\n"))
+    } else {
+        format!("{}{}", kept.join("\n"), marker)
+    }
+}
@@ -0,0 +1,6 @@
+use std::env;
+
+/// Env Var ကို Parse လုပ်မယ် (မရှိ / Parse မရရင် Default)
+pub fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}